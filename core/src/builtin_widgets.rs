@@ -41,6 +41,10 @@ mod void;
 pub use void::Void;
 mod unconstrained_box;
 pub use unconstrained_box::*;
+mod relayout_boundary;
+pub use relayout_boundary::*;
+mod repaint_boundary;
+pub use repaint_boundary::*;
 mod opacity;
 pub use opacity::*;
 mod anchor;
@@ -666,6 +670,28 @@ impl<T> FatObj<T> {
     init_text_style!(self, overflow, v)
   }
 
+  /// Initializes how the white spaces of the text are handled in this widget.
+  pub fn with_white_space<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<WhiteSpace>, K>,
+  ) -> &mut Self {
+    init_text_style!(self, white_space, v)
+  }
+
+  /// Initializes the writing mode of the text of this widget.
+  pub fn with_writing_mode<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<WritingMode>, K>,
+  ) -> &mut Self {
+    init_text_style!(self, writing_mode, v)
+  }
+
+  /// Initializes the orientation of the characters in the vertical lines of the
+  /// text of this widget.
+  pub fn with_text_orientation<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<TextOrientation>, K>,
+  ) -> &mut Self {
+    init_text_style!(self, text_orientation, v)
+  }
+
   /// Initializes the background of the widget.
   pub fn with_background<K: ?Sized>(&mut self, v: impl RInto<PipeValue<Brush>, K>) -> &mut Self {
     init_sub_widget!(self, background, background, v)
@@ -1019,6 +1045,27 @@ impl<T> FatObj<T> {
     part_writer!(&mut style.text_style.overflow)
   }
 
+  /// Returns a state writer for the white space mode of the text, whether the
+  /// white spaces are preserved and the lines wrap.
+  pub fn white_space(&mut self) -> impl StateWriter<Value = WhiteSpace> {
+    let style = self.text_style_widget();
+    part_writer!(&mut style.text_style.white_space)
+  }
+
+  /// Returns a state writer for the writing mode of the text, whether the text
+  /// is set horizontally or vertically.
+  pub fn writing_mode(&mut self) -> impl StateWriter<Value = WritingMode> {
+    let style = self.text_style_widget();
+    part_writer!(&mut style.text_style.writing_mode)
+  }
+
+  /// Returns a state writer for the text orientation, whether the characters
+  /// in the vertical lines are set upright or sideways.
+  pub fn text_orientation(&mut self) -> impl StateWriter<Value = TextOrientation> {
+    let style = self.text_style_widget();
+    part_writer!(&mut style.text_style.text_orientation)
+  }
+
   /// Returns a state writer for modifying interior padding.
   /// Controls space between the widget's border and its content.
   pub fn padding(&mut self) -> impl StateWriter<Value = EdgeInsets> {
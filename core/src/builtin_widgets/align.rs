@@ -21,6 +21,10 @@ pub enum Align {
   /// [`HAlign::Stretch`]! if direction is horizontal and [`VAlign::Stretch`]!
   /// if direction is vertical.
   Stretch,
+  /// The children are aligned by their first baseline in a horizontal layout,
+  /// the children without a baseline are aligned to the start edge. It's the
+  /// same as [`Align::Start`] in other cases.
+  Baseline,
 }
 
 /// A enum that describe how widget align to its box in x-axis.
@@ -216,7 +220,7 @@ impl From<VAlign> for Align {
 impl From<Align> for HAlign {
   fn from(h: Align) -> Self {
     match h {
-      Align::Start => HAlign::Left,
+      Align::Start | Align::Baseline => HAlign::Left,
       Align::Center => HAlign::Center,
       Align::End => HAlign::Right,
       Align::Stretch => HAlign::Stretch,
@@ -227,7 +231,7 @@ impl From<Align> for HAlign {
 impl From<Align> for VAlign {
   fn from(h: Align) -> Self {
     match h {
-      Align::Start => VAlign::Top,
+      Align::Start | Align::Baseline => VAlign::Top,
       Align::Center => VAlign::Center,
       Align::End => VAlign::Bottom,
       Align::Stretch => VAlign::Stretch,
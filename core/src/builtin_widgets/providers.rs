@@ -668,6 +668,18 @@ impl Query for ProvidersRender {
   fn queryable(&self) -> bool { true }
 }
 
+impl ProvidersRender {
+  fn with_providers<R>(
+    &self, ctx: &mut LayoutCtx, f: impl FnOnce(&dyn RenderQueryable, &mut LayoutCtx) -> R,
+  ) -> R {
+    let Self { render, providers } = self;
+    providers.setup_providers(ctx.as_mut());
+    let res = f(&**render, ctx);
+    providers.restore_providers(ctx.as_mut());
+    res
+  }
+}
+
 impl Render for ProvidersRender {
   fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
     let Self { render, providers } = self;
@@ -677,6 +689,26 @@ impl Render for ProvidersRender {
     size
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.with_providers(ctx, |r, ctx| r.min_intrinsic_width(height, ctx))
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.with_providers(ctx, |r, ctx| r.max_intrinsic_width(height, ctx))
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.with_providers(ctx, |r, ctx| r.min_intrinsic_height(width, ctx))
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.with_providers(ctx, |r, ctx| r.max_intrinsic_height(width, ctx))
+  }
+
+  fn baseline(&self, kind: BaselineKind, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.with_providers(ctx, |r, ctx| r.baseline(kind, ctx))
+  }
+
   fn visual_box(&self, ctx: &mut VisualCtx) -> Option<Rect> { self.render.visual_box(ctx) }
 
   fn paint(&self, ctx: &mut PaintingCtx) {
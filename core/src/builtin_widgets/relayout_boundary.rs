@@ -0,0 +1,143 @@
+use std::cell::Cell;
+
+use crate::prelude::*;
+
+/// A widget that stops the relayout of its descendants from spreading to its
+/// ancestors.
+///
+/// It sizes itself to the max size the parent allows, so the changes of its
+/// child never change its size, and a change deep inside it only relayouts the
+/// subtree of the `RelayoutBoundary`. Use it to wrap a large and frequently
+/// changed subtree that is placed in a bounded area.
+///
+/// If an axis of the parent clamp is unlimited, it sizes to the child in that
+/// axis and it's not a boundary anymore.
+///
+/// A widget laid out with a tight clamp is a relayout boundary too, you don't
+/// need this widget for it. But neither of them is a boundary if its parent
+/// depends on more than its size, like its baseline, see
+/// [`LayoutCtx::depend_on_child`].
+#[derive(Declare, SingleChild, Default)]
+pub struct RelayoutBoundary {
+  #[declare(skip)]
+  bounded: Cell<bool>,
+}
+
+impl Render for RelayoutBoundary {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let max = clamp.max;
+    self
+      .bounded
+      .set(max.width.is_finite() && max.height.is_finite());
+    let child = ctx
+      .perform_single_child_layout(clamp)
+      .unwrap_or_default();
+    let width = if max.width.is_finite() { max.width } else { child.width };
+    let height = if max.height.is_finite() { max.height } else { child.height };
+    clamp.clamp(Size::new(width, height))
+  }
+
+  #[inline]
+  fn size_affected_by_child(&self) -> bool { !self.bounded.get() }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use std::rc::Rc;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn stop_relayout() {
+    reset_test_env!();
+
+    let size = Stateful::new(Size::splat(10.));
+    let c_size = size.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: Size::splat(20.) }
+          @RelayoutBoundary {
+            @MockMulti {
+              @MockBox { size: pipe!(*$read(size)) }
+            }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 1]).unwrap().size, Some(Size::new(100., 100.)));
+
+    *c_size.write() = Size::splat(30.);
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_count(), 3);
+    assert_eq!(
+      wnd
+        .layout_info_by_path(&[0, 1, 0, 0])
+        .unwrap()
+        .size,
+      Some(Size::splat(30.))
+    );
+  }
+
+  #[derive(Declare, SingleChild)]
+  struct BaselineOf {
+    baseline: Rc<Cell<Option<f32>>>,
+  }
+
+  impl Render for BaselineOf {
+    fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+      let (ctx, mut children) = ctx.split_children();
+      let child = children.next().unwrap();
+      ctx.perform_child_layout(child, BoxClamp::fixed_size(Size::splat(50.)));
+      self
+        .baseline
+        .set(ctx.child_baseline(child, BaselineKind::First));
+      clamp.max
+    }
+  }
+
+  #[derive(Declare)]
+  struct BaselineLeaf {
+    baseline: f32,
+  }
+
+  impl Render for BaselineLeaf {
+    fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.min }
+
+    fn baseline(&self, _: BaselineKind, _: &mut LayoutCtx) -> Option<f32> { Some(self.baseline) }
+  }
+
+  #[test]
+  fn tight_child_baseline_follow_descendants() {
+    reset_test_env!();
+
+    let baseline = Rc::new(Cell::new(None));
+    let value = Stateful::new(10.);
+    let c_value = value.clone_writer();
+    let c_baseline = baseline.clone();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @BaselineOf {
+          baseline: c_baseline.clone(),
+          @MockBox {
+            size: Size::splat(50.),
+            @BaselineLeaf { baseline: pipe!(*$read(value)) }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    assert_eq!(baseline.get(), Some(10.));
+
+    *c_value.write() = 20.;
+    wnd.draw_frame();
+    assert_eq!(baseline.get(), Some(20.));
+  }
+}
@@ -0,0 +1,251 @@
+use ribir_algo::Resource;
+
+use crate::prelude::*;
+
+/// A widget that records its subtree into a bundle of paint commands, and
+/// reuses it in the next frames until any widget in the subtree is marked
+/// dirty.
+///
+/// The bundle is drawn as a [`PaintCommand::Bundle`], so the backend can cache
+/// it to a texture, and needn't tessellate or batch the commands of the subtree
+/// every frame. Moving, transforming, or changing the opacity of the boundary
+/// doesn't break the cache.
+///
+/// The boundary decides when to cache by itself. The subtree is only cached
+/// after it's painted unchanged for a few frames, and every time a cache is
+/// broken before it's reused for as many frames as it waited, the boundary
+/// waits twice as long before caching again. So a subtree that changes every
+/// frame is painted directly and is never recorded in vain.
+///
+/// Use it to wrap a complex subtree that seldom changes, but is painted with
+/// other frequently changed widgets.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @RepaintBoundary {
+///     @Text { text: "A complex chart" }
+///   }
+/// };
+/// ```
+#[derive(Declare, SingleChild, Default)]
+pub struct RepaintBoundary;
+
+/// The number of the unchanged frames that a subtree waits before it's cached
+/// for the first time, and the limit that it waits after its caches are
+/// broken again and again.
+const MIN_STABLE_FRAMES: usize = 2;
+const MAX_STABLE_FRAMES: usize = 64;
+
+/// The raster cache of a repaint boundary, it's kept by the widget tree.
+pub(crate) struct RasterCache {
+  /// The number of the frames that the subtree has been painted unchanged.
+  stable_frames: usize,
+  /// The number of the unchanged frames needed before caching the subtree.
+  required_frames: usize,
+  /// The number of the frames that the current bundle is reused.
+  reused_frames: usize,
+  bundle: Option<CachedBundle>,
+}
+
+struct CachedBundle {
+  bounds: Rect,
+  cmds: Resource<Box<[PaintCommand]>>,
+  /// The fill and stroke brushes inherited from the ancestors when it's
+  /// recorded.
+  brushes: (Brush, Brush),
+}
+
+impl Default for RasterCache {
+  fn default() -> Self {
+    Self { stable_frames: 0, required_frames: MIN_STABLE_FRAMES, reused_frames: 0, bundle: None }
+  }
+}
+
+impl RasterCache {
+  /// Drop the bundle because the subtree is changed, and wait longer before
+  /// caching again if the bundle didn't pay off.
+  pub(crate) fn invalidate(&mut self) {
+    if self.bundle.take().is_some() && self.reused_frames < self.required_frames {
+      self.required_frames = (self.required_frames * 2).min(MAX_STABLE_FRAMES);
+    }
+    self.stable_frames = 0;
+    self.reused_frames = 0;
+  }
+}
+
+impl Render for RepaintBoundary {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Some(child) = ctx.single_child() else { return clamp.min };
+    // The cache is out of date if the subtree is laid out again.
+    let reuse = ctx
+      .tree
+      .store
+      .layout_info(child)
+      .is_some_and(|info| info.size.is_some() && info.clamp == clamp);
+    if !reuse {
+      let id = ctx.widget_id();
+      if let Some(cache) = ctx.tree.raster_caches.get_mut().get_mut(&id) {
+        cache.invalidate();
+      }
+    }
+    ctx.perform_child_layout(child, clamp)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let id = ctx.widget_id();
+    let painter = ctx.painter();
+    let brushes = (painter.fill_brush().clone(), painter.stroke_brush().clone());
+
+    let cached = {
+      let mut caches = ctx.tree().raster_caches.borrow_mut();
+      let cache = caches.entry(id).or_default();
+      if cache
+        .bundle
+        .as_ref()
+        .is_some_and(|b| b.brushes != brushes)
+      {
+        cache.invalidate();
+      }
+      if cache.bundle.is_none() && cache.stable_frames < cache.required_frames {
+        // Not stable enough, let the framework paint the children directly.
+        cache.stable_frames += 1;
+        return;
+      }
+      if cache.bundle.is_some() {
+        cache.reused_frames += 1;
+        if cache.reused_frames >= cache.required_frames {
+          // The cache paid off, the subtree is stable again.
+          cache.required_frames = MIN_STABLE_FRAMES;
+        }
+      }
+      cache
+        .bundle
+        .as_ref()
+        .map(|b| (b.bounds, b.cmds.clone()))
+    };
+
+    let (bounds, cmds) = if let Some(cached) = cached {
+      ctx.skip_children();
+      cached
+    } else {
+      let mut bounds = Rect::from_size(ctx.box_size().unwrap_or_default());
+      let info = ctx.tree().store.layout_info(id);
+      if let Some(subtree) = info.and_then(|info| info.visual_box.subtree) {
+        bounds = bounds.union(&subtree);
+      }
+
+      let mut recorder = ctx.painter().fork_bundle(bounds);
+      ctx.paint_children_to(&mut recorder);
+      let cmds: Box<[PaintCommand]> = recorder.finish().to_vec().into();
+      let cmds = Resource::new(cmds);
+
+      let bundle = CachedBundle { bounds, cmds: cmds.clone(), brushes };
+      let mut caches = ctx.tree().raster_caches.borrow_mut();
+      caches.entry(id).or_default().bundle = Some(bundle);
+      (bounds, cmds)
+    };
+
+    if !cmds.is_empty() {
+      ctx.painter().draw_bundle_commands(bounds, cmds);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn bundles(wnd: &mut TestWindow) -> Vec<Resource<Box<[PaintCommand]>>> {
+    wnd
+      .take_last_frame()
+      .unwrap()
+      .commands
+      .into_iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Bundle { cmds, .. } => Some(cmds),
+        _ => None,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn reuse_until_dirty() {
+    reset_test_env!();
+
+    let color = Stateful::new(Color::RED);
+    let c_color = color.clone_writer();
+    let pos = Stateful::new(Point::zero());
+    let c_pos = pos.clone_writer();
+    let mut wnd = TestWindow::from_widget(fn_widget! {
+      @MockMulti {
+        @RepaintBoundary {
+          anchor: pipe!(Anchor::from_point(*$read(pos))),
+          @Container {
+            size: Size::new(20., 20.),
+            background: pipe!(*$read(color)),
+          }
+        }
+      }
+    });
+
+    let mut move_to = |x: f32| {
+      *c_pos.write() = Point::new(x, x);
+      wnd.draw_frame();
+      bundles(&mut wnd)
+    };
+    // Painted directly until it keeps unchanged for a few frames.
+    assert!(move_to(0.).is_empty());
+    assert!(move_to(1.).is_empty());
+    let first = move_to(2.);
+    assert_eq!(first.len(), 1);
+    // Moving the boundary reuses the bundle.
+    assert_eq!(move_to(3.), first);
+
+    *c_color.write() = Color::BLUE;
+    assert!(move_to(4.).is_empty());
+  }
+
+  #[test]
+  fn wait_longer_after_broken_cache() {
+    reset_test_env!();
+
+    let color = Stateful::new(Color::RED);
+    let c_color = color.clone_writer();
+    let tick = Stateful::new(0.);
+    let c_tick = tick.clone_writer();
+    let mut wnd = TestWindow::from_widget(fn_widget! {
+      @MockMulti {
+        // A sibling changes every frame to force painting.
+        @MockBox { size: pipe!(Size::splat(*$read(tick))) }
+        @RepaintBoundary {
+          @Container { size: Size::new(20., 20.), background: pipe!(*$read(color)) }
+        }
+      }
+    });
+
+    let mut frames = |n: usize| {
+      (0..n)
+        .map(|_| {
+          *c_tick.write() += 1.;
+          wnd.draw_frame();
+          bundles(&mut wnd).len()
+        })
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(frames(3), [0, 0, 1]);
+
+    // The cache is broken at once, so it waits twice as long.
+    *c_color.write() = Color::BLUE;
+    assert_eq!(frames(5), [0, 0, 0, 0, 1]);
+
+    // The cache keeps long enough, so the boundary is trusted again.
+    assert_eq!(frames(4), [1, 1, 1, 1]);
+    *c_color.write() = Color::RED;
+    assert_eq!(frames(3), [0, 0, 1]);
+  }
+}
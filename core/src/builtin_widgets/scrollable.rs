@@ -1,6 +1,9 @@
-use std::cell::Cell;
+use std::{cell::Cell, collections::VecDeque, rc::Rc};
 
-use crate::prelude::*;
+use crate::{prelude::*, ticker::FrameMsg, window::WindowId};
+
+mod physics;
+pub use physics::*;
 /// Enumerate to describe which direction allow widget to scroll.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Hash)]
 pub enum Scrollable {
@@ -17,7 +20,15 @@ pub enum Scrollable {
 }
 
 /// Helper struct for builtin scrollable field.
-#[derive(Default)]
+///
+/// The mouse wheel scrolls the content directly. The pointer drag and the
+/// scrolling gesture of a touchpad move the content by the [`ScrollPhysics`],
+/// which decides whether the content can be scrolled beyond its edges and how
+/// it keeps moving after the user releases it. A [`ScrollSnap`] can be set to
+/// make the content settle at the pages or the items.
+///
+/// Use [`ScrollableWidget::jump_to`] to scroll instantly, and
+/// [`ScrollableWidget::scroll_to`] to scroll smoothly with a transition.
 pub struct ScrollableWidget {
   pub scrollable: Scrollable,
   scroll_pos: Point,
@@ -25,8 +36,40 @@ pub struct ScrollableWidget {
   content_size: Size,
 
   view_id: Option<TrackId>,
+  physics: Rc<dyn ScrollPhysics>,
+  snap: ScrollSnap,
+  transition: Option<Rc<dyn Transition>>,
+  motion: Option<ScrollMotion>,
+  velocity: VelocityTracker,
+  pointer_drag: Option<(PointerId, Point)>,
+  /// Whether a scrolling gesture of a precise wheel device is in progress.
+  wheel_gesture: bool,
+  /// Whether the content is dragged by the user input and not released.
+  dragging: bool,
+  window_id: Option<WindowId>,
+  /// The closest ancestor scrollable widget, the user input this widget can't
+  /// consume is handed to it.
+  parent: Option<ScrollableProvider>,
+}
+
+/// The running simulations of the scroll position.
+struct ScrollMotion {
+  start_at: Instant,
+  x: Option<Box<dyn ScrollSimulation>>,
+  y: Option<Box<dyn ScrollSimulation>>,
 }
 
+/// Tracks the recent user scroll deltas to estimate the velocity when the user
+/// releases the content.
+#[derive(Default)]
+struct VelocityTracker {
+  offset: Vector,
+  samples: VecDeque<(Instant, Vector)>,
+}
+
+/// The time span of the samples to estimate the velocity.
+const VELOCITY_SAMPLE_SPAN: Duration = Duration::from_millis(100);
+
 /// The provider of `ScrollableWidget` providers the descendant widgets to use
 /// it.
 ///
@@ -44,14 +87,72 @@ impl Declare for ScrollableWidget {
   fn declarer() -> Self::Builder { FatObj::new(()) }
 }
 
+impl Default for ScrollableWidget {
+  fn default() -> Self {
+    Self {
+      scrollable: Scrollable::default(),
+      scroll_pos: Point::zero(),
+      page: Size::zero(),
+      content_size: Size::zero(),
+      view_id: None,
+      physics: default_scroll_physics(),
+      snap: ScrollSnap::None,
+      transition: None,
+      motion: None,
+      velocity: VelocityTracker::default(),
+      pointer_drag: None,
+      wheel_gesture: false,
+      dragging: false,
+      window_id: None,
+      parent: None,
+    }
+  }
+}
+
 impl<'c> ComposeChild<'c> for ScrollableWidget {
   type Child = Widget<'c>;
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
+      // The widget may be provided by its wrapper, like the `Scrollbar`, before
+      // it provides itself, that's not a parent.
+      let parent = ScrollableWidget::boxed_writer_of(BuildCtx::get())
+        .filter(|p| !std::ptr::eq(&*p.read(), &*this.read()));
       let mut view = @Viewport {
         clip_boundary: true,
         scroll_dir: distinct_pipe!($read(this).scrollable),
-        on_wheel: move |e| $write(this).scroll(-e.delta_x, -e.delta_y),
+        on_wheel: move |e| {
+          let delta = Vector::new(-e.delta_x, -e.delta_y);
+          // A zero delta scrolls nothing, only the gesture phases are kept.
+          let gesture = matches!(e.phase, WheelPhase::Started | WheelPhase::Ended);
+          if delta != Vector::zero() || gesture {
+            $write(this).wheel_by(delta, e.phase);
+          }
+          // The delta is already handed to the ancestors by the chain.
+          e.stop_propagation();
+        },
+        on_pointer_down: move |e| {
+          let mut this = $write(this);
+          this.stop();
+          if e.point_type != PointerType::Mouse {
+            this.pointer_drag = Some((e.id, e.global_pos()));
+          }
+        },
+        on_pointer_move: move |e| {
+          let drag = $read(this).pointer_drag;
+          if let Some((id, last)) = drag.filter(|(id, _)| *id == e.id) {
+            let pos = e.global_pos();
+            let mut this = $write(this);
+            this.pointer_drag = Some((id, pos));
+            this.drag_by(last - pos);
+          }
+        },
+        on_pointer_up: move |e| {
+          if $read(this).pointer_drag.is_some_and(|(id, _)| id == e.id) {
+            let mut this = $write(this);
+            this.pointer_drag = None;
+            this.release();
+          }
+        },
       };
 
       let mut child = FatObj::new(child);
@@ -69,7 +170,26 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
         }
       };
 
-      this.write().view_id = Some(view.track_id());
+      let wnd = BuildCtx::get().window();
+      {
+        let mut this = this.write();
+        this.view_id = Some(view.track_id());
+        this.window_id = Some(wnd.id());
+        this.parent = parent;
+        if this.transition.is_none() {
+          this.transition = Some(Rc::from(transitions::EASE_OUT.of(BuildCtx::get())));
+        }
+      }
+
+      let u_tick = wnd
+        .frame_tick_stream()
+        .subscribe(move |msg| {
+          if let FrameMsg::BeforeLayout(time) = msg {
+            if $read(this).motion.is_some() {
+              $write(this).advance(time);
+            }
+          }
+        });
 
       @(view) {
         on_performed_layout: move |_| {
@@ -79,6 +199,7 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
             this.set_page(view_size);
           }
         },
+        on_disposed: move |_| u_tick.unsubscribe(),
         providers: [Provider::value_of_writer(this.clone_boxed_writer(), None)],
         @ { child }
       }
@@ -133,35 +254,8 @@ impl ScrollableWidget {
   /// If Anchor.y is None, it will anchor the widget to the closest edge of the
   /// view in vertical direction, when the widget is out of the view.
   pub fn visible_content_box(&mut self, rect: Rect, anchor: Anchor) {
-    let view_size = self.scroll_view_size();
-
-    let offset_x = anchor
-      .x
-      .or_else(|| {
-        if rect.max_x() > self.scroll_pos.x + view_size.width {
-          Some(HAnchor::Right(0.0.into()))
-        } else if rect.min_x() < self.scroll_pos.x {
-          Some(HAnchor::Left(0.0.into()))
-        } else {
-          None
-        }
-      })
-      .map_or(self.scroll_pos.x, |x| rect.min_x() - x.into_pixel(rect.width(), view_size.width));
-
-    let offset_y = anchor
-      .y
-      .or_else(|| {
-        if rect.max_y() > view_size.height + self.scroll_pos.y {
-          Some(VAnchor::Bottom(0.0.into()))
-        } else if rect.min_y() < self.scroll_pos.y {
-          Some(VAnchor::Top(0.0.into()))
-        } else {
-          None
-        }
-      })
-      .map_or(self.scroll_pos.y, |y| rect.min_y() - y.into_pixel(rect.height(), view_size.height));
-
-    self.jump_to(Point::new(offset_x, offset_y));
+    let pos = self.visible_pos(rect, anchor);
+    self.jump_to(pos);
   }
 
   /// Ensure the given child is visible in the scroll view with the given anchor
@@ -171,12 +265,22 @@ impl ScrollableWidget {
   /// If Anchor.y is None, it will anchor the widget to the closest edge of the
   /// view in vertical direction, when the widget is out of the view.
   pub fn visible_widget(&mut self, child: WidgetId, anchor: Anchor, wnd: &Window) {
-    let Some(pos) = self.map_to_content(Point::zero(), child, wnd) else { return };
-    let Some(size) = wnd.widget_size(child) else { return };
-    let show_box = Rect::new(pos, size);
+    let Some(show_box) = self.widget_box(child, wnd) else { return };
     self.visible_content_box(show_box, anchor);
   }
 
+  /// Like [`ScrollableWidget::visible_content_box`], but scrolls smoothly.
+  pub fn scroll_to_content_box(&mut self, rect: Rect, anchor: Anchor) {
+    let pos = self.visible_pos(rect, anchor);
+    self.scroll_to(pos);
+  }
+
+  /// Like [`ScrollableWidget::visible_widget`], but scrolls smoothly.
+  pub fn scroll_to_widget(&mut self, child: WidgetId, anchor: Anchor, wnd: &Window) {
+    let Some(show_box) = self.widget_box(child, wnd) else { return };
+    self.scroll_to_content_box(show_box, anchor);
+  }
+
   pub fn scroll(&mut self, x: f32, y: f32) {
     let mut new = self.scroll_pos;
     if self.scrollable != Scrollable::X {
@@ -188,9 +292,183 @@ impl ScrollableWidget {
     self.jump_to(new);
   }
 
+  /// Scroll to the position instantly, and stop the running scroll motion.
   pub fn jump_to(&mut self, top_left: Point) {
+    self.stop();
+    self.clamp_to(top_left);
+  }
+
+  /// Scroll to the position smoothly with the transition of the scrollable
+  /// widget, see [`ScrollableWidget::set_transition`].
+  pub fn scroll_to(&mut self, top_left: Point) {
+    let Some(transition) = self.transition.clone() else {
+      self.jump_to(top_left);
+      return;
+    };
     let max = self.max_scrollable();
-    self.scroll_pos = top_left.clamp(Point::zero(), max.to_vector().to_point());
+    let to = top_left.clamp(Point::zero(), max);
+    let from = self.scroll_pos;
+    let simulation = |from: f32, to: f32| {
+      (from != to).then(|| {
+        Box::new(TransitionSimulation::new(from, to, transition.clone()))
+          as Box<dyn ScrollSimulation>
+      })
+    };
+    self.start_motion(simulation(from.x, to.x), simulation(from.y, to.y));
+  }
+
+  /// Move the content by the user drag. If this widget is inside another
+  /// scrollable widget, the delta that can't be consumed at the edges is
+  /// handed to the ancestors, and the rest is applied by the scroll physics, so
+  /// the content may be scrolled beyond its edges. Call
+  /// [`ScrollableWidget::release`] when the user input ends.
+  pub fn drag_by(&mut self, delta: Vector) {
+    self.dragging = true;
+    let overscrolled = self.metrics_x().overscroll() != 0. || self.metrics_y().overscroll() != 0.;
+    let rest = if overscrolled { delta } else { self.chain(delta, true) };
+    if rest != Vector::zero() {
+      self.apply_physics(rest);
+    }
+  }
+
+  /// Scroll the content by the wheel. The delta of a mouse wheel is applied
+  /// directly inside the scroll range without the physics and the momentum,
+  /// the delta that can't be consumed is handed to the ancestors.
+  ///
+  /// The scrolling gesture of a precise device, like a touchpad, is handled
+  /// like a drag: it may scroll beyond the edges, and the content keeps moving
+  /// after the gesture ends.
+  pub fn wheel_by(&mut self, delta: Vector, phase: WheelPhase) {
+    match phase {
+      WheelPhase::Started => {
+        self.stop();
+        self.velocity = VelocityTracker::default();
+        self.wheel_gesture = true;
+      }
+      WheelPhase::Moved if self.wheel_gesture => self.drag_by(delta),
+      WheelPhase::Ended if self.wheel_gesture => {
+        self.wheel_gesture = false;
+        // Not released by the descendant that chained the gesture to it.
+        if !self.is_moving() {
+          self.release();
+        }
+      }
+      // A precise delta without a gesture can't be released.
+      WheelPhase::Line | WheelPhase::Moved => {
+        self.stop();
+        self.chain(delta, false);
+      }
+      WheelPhase::Ended => {}
+    }
+  }
+
+  /// Consume the delta inside the scroll range, and hand the rest to the
+  /// ancestors, return the delta no one consumed. The consumed delta is
+  /// sampled for the velocity of the release if `track` is true.
+  fn chain(&mut self, delta: Vector, track: bool) -> Vector {
+    let rest = self.consume(delta, track);
+    match self.parent.as_ref() {
+      Some(parent) if rest != Vector::zero() => parent.write().chain(rest, track),
+      _ => rest,
+    }
+  }
+
+  fn consume(&mut self, delta: Vector, track: bool) -> Vector {
+    let mut pos = self.scroll_pos;
+    if self.scrollable != Scrollable::X {
+      pos.y = self.metrics_y().clamp(pos.y + delta.y);
+    }
+    if self.scrollable != Scrollable::Y {
+      pos.x = self.metrics_x().clamp(pos.x + delta.x);
+    }
+    let consumed = pos - self.scroll_pos;
+    if consumed != Vector::zero() {
+      self.stop();
+      self.scroll_pos = pos;
+      if track {
+        self.velocity.add(consumed, Instant::now());
+      }
+    }
+    delta - consumed
+  }
+
+  fn apply_physics(&mut self, delta: Vector) {
+    self.stop();
+    let mut pos = self.scroll_pos;
+    if self.scrollable != Scrollable::X {
+      pos.y = self
+        .physics
+        .apply_delta(self.metrics_y(), delta.y);
+    }
+    if self.scrollable != Scrollable::Y {
+      pos.x = self
+        .physics
+        .apply_delta(self.metrics_x(), delta.x);
+    }
+    self.scroll_pos = pos;
+    self.velocity.add(delta, Instant::now());
+  }
+
+  /// The user input ends, the content keeps moving by the velocity of the
+  /// recent input.
+  pub fn release(&mut self) {
+    self.dragging = false;
+    let velocity = self.velocity.velocity(Instant::now());
+    self.velocity = VelocityTracker::default();
+    self.fling(velocity);
+    // The ancestor that consumed the chained delta settles by its own velocity.
+    if let Some(parent) = self.parent.as_ref() {
+      let mut parent = parent.write();
+      if !parent.velocity.is_empty() {
+        parent.release();
+      }
+    }
+  }
+
+  /// Move the content with the `velocity` in pixels per second, it slows down
+  /// by the scroll physics and stops at a snap position if there is a
+  /// [`ScrollSnap`].
+  pub fn fling(&mut self, velocity: Vector) {
+    let x = (self.scrollable != Scrollable::Y)
+      .then(|| self.ballistic(self.metrics_x(), velocity.x))
+      .flatten();
+    let y = (self.scrollable != Scrollable::X)
+      .then(|| self.ballistic(self.metrics_y(), velocity.y))
+      .flatten();
+    self.start_motion(x, y);
+  }
+
+  /// Stop the running scroll motion.
+  pub fn stop(&mut self) {
+    if self.motion.is_some() {
+      self.set_motion(None);
+      self.sync_pos();
+    }
+  }
+
+  /// Whether the content is moving by a fling, a bounce or a smooth scroll.
+  pub fn is_moving(&self) -> bool { self.motion.is_some() }
+
+  /// Whether the content is dragged by the user input, from the
+  /// [`ScrollableWidget::drag_by`] until the [`ScrollableWidget::release`].
+  pub fn is_dragging(&self) -> bool { self.dragging }
+
+  /// Set the physics that determines how the content responds to the user
+  /// input.
+  pub fn set_physics(&mut self, physics: impl ScrollPhysics + 'static) {
+    self.physics = Rc::new(physics);
+  }
+
+  /// Set the positions that the content settles at after the user scrolled.
+  pub fn set_snap(&mut self, snap: ScrollSnap) { self.snap = snap; }
+
+  /// The positions that the content settles at after the user scrolled.
+  pub fn snap(&self) -> &ScrollSnap { &self.snap }
+
+  /// Set the transition of the smooth scroll, the default is
+  /// `transitions::EASE_OUT` of the theme.
+  pub fn set_transition(&mut self, transition: impl Transition + 'static) {
+    self.transition = Some(Rc::new(transition));
   }
 
   #[inline]
@@ -218,15 +496,126 @@ impl ScrollableWidget {
 
   pub fn get_x_scroll_rate(&self) -> f32 {
     let pos = self.scroll_pos.x;
-    if pos.is_normal() { pos / self.max_scrollable().x } else { 0. }
+    if pos.is_normal() { (pos / self.max_scrollable().x).clamp(0., 1.) } else { 0. }
   }
 
   pub fn get_y_scroll_rate(&self) -> f32 {
     let pos = self.scroll_pos.y;
-    if pos.is_normal() { pos / self.max_scrollable().y } else { 0. }
+    if pos.is_normal() { (pos / self.max_scrollable().y).clamp(0., 1.) } else { 0. }
+  }
+
+  fn visible_pos(&self, rect: Rect, anchor: Anchor) -> Point {
+    let view_size = self.scroll_view_size();
+
+    let offset_x = anchor
+      .x
+      .or_else(|| {
+        if rect.max_x() > self.scroll_pos.x + view_size.width {
+          Some(HAnchor::Right(0.0.into()))
+        } else if rect.min_x() < self.scroll_pos.x {
+          Some(HAnchor::Left(0.0.into()))
+        } else {
+          None
+        }
+      })
+      .map_or(self.scroll_pos.x, |x| rect.min_x() - x.into_pixel(rect.width(), view_size.width));
+
+    let offset_y = anchor
+      .y
+      .or_else(|| {
+        if rect.max_y() > view_size.height + self.scroll_pos.y {
+          Some(VAnchor::Bottom(0.0.into()))
+        } else if rect.min_y() < self.scroll_pos.y {
+          Some(VAnchor::Top(0.0.into()))
+        } else {
+          None
+        }
+      })
+      .map_or(self.scroll_pos.y, |y| rect.min_y() - y.into_pixel(rect.height(), view_size.height));
+
+    Point::new(offset_x, offset_y)
   }
 
-  fn sync_pos(&mut self) { self.jump_to(self.scroll_pos) }
+  fn widget_box(&self, child: WidgetId, wnd: &Window) -> Option<Rect> {
+    let pos = self.map_to_content(Point::zero(), child, wnd)?;
+    let size = wnd.widget_size(child)?;
+    Some(Rect::new(pos, size))
+  }
+
+  fn metrics_x(&self) -> ScrollMetrics {
+    ScrollMetrics {
+      pos: self.scroll_pos.x,
+      max: self.max_scrollable().x,
+      viewport: self.page.width,
+    }
+  }
+
+  fn metrics_y(&self) -> ScrollMetrics {
+    ScrollMetrics {
+      pos: self.scroll_pos.y,
+      max: self.max_scrollable().y,
+      viewport: self.page.height,
+    }
+  }
+
+  fn ballistic(&self, metrics: ScrollMetrics, velocity: f32) -> Option<Box<dyn ScrollSimulation>> {
+    if self.snap.is_none() {
+      return self
+        .physics
+        .ballistic_simulation(metrics, velocity);
+    }
+    let projected = self.physics.projected_position(metrics, velocity);
+    let target = self
+      .snap
+      .snap(projected, metrics.viewport, metrics.max);
+    ((target - metrics.pos).abs() > f32::EPSILON).then(|| {
+      self
+        .physics
+        .settle_simulation(metrics, target, velocity)
+    })
+  }
+
+  fn start_motion(
+    &mut self, x: Option<Box<dyn ScrollSimulation>>, y: Option<Box<dyn ScrollSimulation>>,
+  ) {
+    let motion =
+      (x.is_some() || y.is_some()).then(|| ScrollMotion { start_at: Instant::now(), x, y });
+    self.set_motion(motion);
+  }
+
+  fn set_motion(&mut self, motion: Option<ScrollMotion>) {
+    let wnd = self.window_id.and_then(AppCtx::get_window);
+    match (self.motion.is_some(), motion.is_some(), wnd) {
+      (false, true, Some(wnd)) => wnd.inc_running_animate(),
+      (true, false, Some(wnd)) => wnd.dec_running_animate(),
+      _ => {}
+    }
+    self.motion = motion;
+  }
+
+  fn advance(&mut self, time: Instant) {
+    let Some(motion) = self.motion.as_ref() else { return };
+    let elapsed = time.saturating_duration_since(motion.start_at);
+    let mut done = true;
+    if let Some(x) = motion.x.as_ref() {
+      self.scroll_pos.x = x.position(elapsed);
+      done &= x.is_done(elapsed);
+    }
+    if let Some(y) = motion.y.as_ref() {
+      self.scroll_pos.y = y.position(elapsed);
+      done &= y.is_done(elapsed);
+    }
+    if done {
+      self.stop();
+    }
+  }
+
+  fn clamp_to(&mut self, top_left: Point) {
+    let max = self.max_scrollable();
+    self.scroll_pos = top_left.clamp(Point::zero(), max.to_vector().to_point());
+  }
+
+  fn sync_pos(&mut self) { self.clamp_to(self.scroll_pos) }
 
   fn set_content_size(&mut self, content_size: Size) {
     self.content_size = content_size;
@@ -239,6 +628,42 @@ impl ScrollableWidget {
   }
 }
 
+impl Drop for ScrollableWidget {
+  fn drop(&mut self) { self.set_motion(None); }
+}
+
+impl VelocityTracker {
+  fn add(&mut self, delta: Vector, at: Instant) {
+    self.offset += delta;
+    self.samples.push_back((at, self.offset));
+    while self
+      .samples
+      .front()
+      .is_some_and(|(time, _)| at.saturating_duration_since(*time) > VELOCITY_SAMPLE_SPAN)
+    {
+      self.samples.pop_front();
+    }
+  }
+
+  fn is_empty(&self) -> bool { self.samples.is_empty() }
+
+  /// The velocity in pixels per second of the samples in the recent time
+  /// span.
+  fn velocity(&self, now: Instant) -> Vector {
+    let (Some((start, from)), Some((end, to))) = (self.samples.front(), self.samples.back()) else {
+      return Vector::zero();
+    };
+    let span = end
+      .saturating_duration_since(*start)
+      .as_secs_f32();
+    // The user stopped for a while before releasing.
+    if span <= 0. || now.saturating_duration_since(*end) > VELOCITY_SAMPLE_SPAN {
+      return Vector::zero();
+    }
+    (*to - *from) / span
+  }
+}
+
 #[derive(SingleChild, Declare)]
 struct Viewport {
   scroll_dir: Scrollable,
@@ -323,6 +748,185 @@ mod tests {
     test_assert(Scrollable::Both, 100., 100., 0., 0.);
   }
 
+  fn scroll_window(scroll: &Stateful<ScrollableWidget>) -> TestWindow {
+    let scroll = scroll.clone_writer();
+    let w = fn_widget! {
+      @(scroll.clone_writer()) {
+        @MockBox { size: Size::new(1000., 1000.) }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd
+  }
+
+  /// Advance the scroll motion by the frames of a simulated clock until it
+  /// finishes, so the test doesn't depend on the wall clock.
+  fn run_motion(wnd: &TestWindow, scroll: &Stateful<ScrollableWidget>) {
+    let Some(start) = scroll.read().motion.as_ref().map(|m| m.start_at) else { return };
+    for frame in 1..=600 {
+      scroll.write().advance(start + FRAME * frame);
+      if !scroll.read().is_moving() {
+        wnd.draw_frame();
+        return;
+      }
+    }
+    panic!("the scroll motion never stops");
+  }
+
+  const FRAME: Duration = Duration::from_millis(16);
+
+  #[test]
+  fn overscroll_bounce_back() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    scroll
+      .write()
+      .set_physics(BouncingScrollPhysics::default());
+    let wnd = scroll_window(&scroll);
+
+    scroll.write().drag_by(Vector::new(0., -30.));
+    wnd.draw_frame();
+    assert_eq!(scroll.read().get_scroll_pos().y, -15.);
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::new(0., 15.));
+
+    scroll.write().release();
+    assert!(scroll.read().is_moving());
+    run_motion(&wnd, &scroll);
+    assert_eq!(scroll.read().get_scroll_pos(), Point::zero());
+  }
+
+  #[test]
+  fn wheel_without_momentum() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    scroll
+      .write()
+      .set_physics(BouncingScrollPhysics::default());
+    let wnd = scroll_window(&scroll);
+    wnd.process_cursor_move(Point::new(50., 50.));
+
+    wnd.process_wheel(0., -50.);
+    wnd.run_frame_tasks();
+    assert_eq!(scroll.read().get_scroll_pos().y, 50.);
+    assert!(!scroll.read().is_moving());
+
+    // The wheel doesn't scroll beyond the edges.
+    wnd.process_wheel(0., 100.);
+    wnd.run_frame_tasks();
+    assert_eq!(scroll.read().get_scroll_pos().y, 0.);
+    assert!(!scroll.read().is_moving());
+  }
+
+  #[test]
+  fn touchpad_momentum() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    scroll
+      .write()
+      .set_physics(BouncingScrollPhysics::default());
+    let wnd = scroll_window(&scroll);
+    wnd.process_cursor_move(Point::new(50., 50.));
+
+    wnd.process_wheel_phase(0., 0., WheelPhase::Started);
+    for _ in 0..3 {
+      wnd.process_wheel_phase(0., -10., WheelPhase::Moved);
+      wnd.run_frame_tasks();
+    }
+    assert_eq!(scroll.read().get_scroll_pos().y, 30.);
+    assert!(!scroll.read().is_moving());
+
+    // The content keeps moving after the fingers are lifted.
+    wnd.process_wheel_phase(0., 0., WheelPhase::Ended);
+    wnd.run_frame_tasks();
+    assert!(scroll.read().is_moving());
+    run_motion(&wnd, &scroll);
+    assert!(scroll.read().get_scroll_pos().y > 30.);
+  }
+
+  #[test]
+  fn touchpad_overscroll_bounce_back() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    scroll
+      .write()
+      .set_physics(BouncingScrollPhysics::default());
+    let wnd = scroll_window(&scroll);
+    wnd.process_cursor_move(Point::new(50., 50.));
+
+    wnd.process_wheel_phase(0., 0., WheelPhase::Started);
+    wnd.process_wheel_phase(0., 30., WheelPhase::Moved);
+    wnd.run_frame_tasks();
+    assert!(scroll.read().get_scroll_pos().y < 0.);
+
+    wnd.process_wheel_phase(0., 0., WheelPhase::Ended);
+    wnd.run_frame_tasks();
+    run_motion(&wnd, &scroll);
+    assert_eq!(scroll.read().get_scroll_pos(), Point::zero());
+  }
+
+  #[test]
+  fn clamping_drag() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    scroll
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    let wnd = scroll_window(&scroll);
+
+    scroll.write().drag_by(Vector::new(0., -30.));
+    assert_eq!(scroll.read().get_scroll_pos().y, 0.);
+    scroll.write().release();
+    assert!(!scroll.read().is_moving());
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn fling_to_snap_position() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    {
+      let mut scroll = scroll.write();
+      scroll.set_physics(ClampingScrollPhysics::default());
+      scroll.set_snap(ScrollSnap::Interval(100.));
+    }
+    let wnd = scroll_window(&scroll);
+
+    // The fling would stop at 125 without snapping.
+    scroll.write().fling(Vector::new(0., 500.));
+    run_motion(&wnd, &scroll);
+    assert_eq!(scroll.read().get_scroll_pos(), Point::new(0., 100.));
+  }
+
+  #[test]
+  fn smooth_scroll_to() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let wnd = scroll_window(&scroll);
+
+    scroll.write().scroll_to(Point::new(0., 300.));
+    let start = scroll.read().motion.as_ref().unwrap().start_at;
+    scroll.write().advance(start + FRAME);
+    let y = scroll.read().get_scroll_pos().y;
+    assert!(y > 0. && y < 300.);
+
+    run_motion(&wnd, &scroll);
+    assert_eq!(scroll.read().get_scroll_pos(), Point::new(0., 300.));
+
+    // Jump stops the running motion.
+    scroll.write().scroll_to(Point::new(0., 600.));
+    scroll.write().jump_to(Point::new(0., 100.));
+    assert!(!scroll.read().is_moving());
+    assert_eq!(scroll.read().get_scroll_pos(), Point::new(0., 100.));
+  }
+
   #[derive(SingleChild, Declare, Clone)]
   pub struct FixedBox {
     pub size: Size,
@@ -340,6 +944,154 @@ mod tests {
     fn paint(&self, _: &mut PaintingCtx) {}
   }
 
+  #[test]
+  fn nested_scroll_chaining() {
+    reset_test_env!();
+
+    let outer = Stateful::new(ScrollableWidget::default());
+    let inner = Stateful::new(ScrollableWidget::default());
+    outer
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    inner
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    let (o, i) = (outer.clone_writer(), inner.clone_writer());
+    let w = fn_widget! {
+      @(o.clone_writer()) {
+        @MockMulti {
+          @FixedBox {
+            size: Size::new(100., 100.),
+            @(i.clone_writer()) { @MockBox { size: Size::new(100., 1000.) } }
+          }
+          @MockBox { size: Size::new(100., 500.) }
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd.process_cursor_move(Point::new(50., 50.));
+
+    wnd.process_wheel(0., -50.);
+    wnd.run_frame_tasks();
+    assert_eq!(inner.read().get_scroll_pos().y, 50.);
+    assert_eq!(outer.read().get_scroll_pos().y, 0.);
+
+    // The inner one reaches its end, and the rest scrolls the outer one.
+    wnd.process_wheel(0., -1000.);
+    wnd.run_frame_tasks();
+    assert_eq!(inner.read().get_scroll_pos().y, 900.);
+    assert_eq!(outer.read().get_scroll_pos().y, 150.);
+  }
+
+  #[test]
+  fn release_chained_drag() {
+    reset_test_env!();
+
+    let outer = Stateful::new(ScrollableWidget::default());
+    let inner = Stateful::new(ScrollableWidget::default());
+    outer
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    inner
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    let (o, i) = (outer.clone_writer(), inner.clone_writer());
+    let w = fn_widget! {
+      @(o.clone_writer()) {
+        @MockMulti {
+          @FixedBox {
+            size: Size::new(100., 100.),
+            @(i.clone_writer()) { @MockBox { size: Size::new(100., 200.) } }
+          }
+          @MockBox { size: Size::new(100., 500.) }
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    inner.write().drag_by(Vector::new(0., 150.));
+    assert_eq!(outer.read().get_scroll_pos().y, 50.);
+    assert!(!outer.read().velocity.is_empty());
+
+    // The release of the inner one is forwarded to the outer one.
+    inner.write().release();
+    assert!(outer.read().velocity.is_empty());
+  }
+
+  #[test]
+  fn nested_touchpad_momentum() {
+    reset_test_env!();
+
+    let outer = Stateful::new(ScrollableWidget::default());
+    let inner = Stateful::new(ScrollableWidget::default());
+    outer
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    inner
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    let (wheel_cnt, c_wheel_cnt) = split_value(0);
+    let (o, i) = (outer.clone_writer(), inner.clone_writer());
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_wheel: move |_| *$write(c_wheel_cnt) += 1,
+        @(o.clone_writer()) {
+          @MockMulti {
+            @FixedBox {
+              size: Size::new(100., 100.),
+              @(i.clone_writer()) { @MockBox { size: Size::new(100., 200.) } }
+            }
+            @MockBox { size: Size::new(100., 1000.) }
+          }
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd.process_cursor_move(Point::new(50., 50.));
+
+    wnd.process_wheel_phase(0., 0., WheelPhase::Started);
+    for _ in 0..4 {
+      wnd.process_wheel_phase(0., -50., WheelPhase::Moved);
+      wnd.run_frame_tasks();
+    }
+    assert_eq!(inner.read().get_scroll_pos().y, 100.);
+    assert_eq!(outer.read().get_scroll_pos().y, 100.);
+
+    // The outer one keeps the momentum of the chained gesture.
+    wnd.process_wheel_phase(0., 0., WheelPhase::Ended);
+    wnd.run_frame_tasks();
+    assert!(outer.read().is_moving());
+    // The wheel events are handled by the scrollables.
+    assert_eq!(*wheel_cnt.read(), 0);
+  }
+
+  #[test]
+  fn provided_by_wrapper_is_not_parent() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let c_scroll = scroll.clone_writer();
+    let w = fn_widget! {
+      @MockMulti {
+        providers: [Provider::value_of_writer(c_scroll.clone_writer(), None)],
+        @(c_scroll.clone_writer()) { @MockBox { size: Size::new(100., 1000.) } }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    scroll
+      .write()
+      .set_physics(ClampingScrollPhysics::default());
+    // The rest of the delta isn't handed to itself.
+    scroll.write().drag_by(Vector::new(0., 1000.));
+    assert_eq!(scroll.read().get_scroll_pos().y, 900.);
+  }
+
   #[test]
   fn scroll_content_expand() {
     reset_test_env!();
@@ -0,0 +1,484 @@
+use std::rc::Rc;
+
+use crate::prelude::*;
+
+/// The velocity in pixels per second below which a released content does not
+/// keep moving.
+pub const MIN_FLING_VELOCITY: f32 = 50.;
+
+/// The scroll extent of one axis that the physics works with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollMetrics {
+  /// The current scroll position, it's out of `0..=max` when overscrolled.
+  pub pos: f32,
+  /// The max scroll position.
+  pub max: f32,
+  /// The size of the view along the axis.
+  pub viewport: f32,
+}
+
+/// Determines how a scrollable widget responds to the user input, and how the
+/// content moves after the user releases it.
+///
+/// Set it by [`ScrollableWidget::set_physics`], the default is
+/// [`BouncingScrollPhysics`] on macOS, iOS and Android, and
+/// [`ClampingScrollPhysics`] on the other platforms.
+pub trait ScrollPhysics {
+  /// Return the new scroll position after the user moves the content by
+  /// `delta`.
+  fn apply_delta(&self, metrics: ScrollMetrics, delta: f32) -> f32;
+
+  /// Create the simulation that moves the content after the user releases it
+  /// with the `velocity` in pixels per second, or `None` if the content should
+  /// stay.
+  fn ballistic_simulation(
+    &self, metrics: ScrollMetrics, velocity: f32,
+  ) -> Option<Box<dyn ScrollSimulation>>;
+
+  /// The position where the content would stop if it's released with the
+  /// `velocity`, it's used to pick the snap position.
+  fn projected_position(&self, metrics: ScrollMetrics, velocity: f32) -> f32;
+
+  /// Create the simulation that moves the content to the `target` with the
+  /// initial `velocity`, it's used to settle at a snap position.
+  fn settle_simulation(
+    &self, metrics: ScrollMetrics, target: f32, velocity: f32,
+  ) -> Box<dyn ScrollSimulation> {
+    Box::new(SpringSimulation::new(metrics.pos, target, velocity, DEFAULT_STIFFNESS))
+  }
+}
+
+/// A motion of the scroll position along one axis.
+pub trait ScrollSimulation {
+  /// The scroll position after the `time` since the simulation started.
+  fn position(&self, time: Duration) -> f32;
+
+  /// Whether the simulation is finished at the `time`.
+  fn is_done(&self, time: Duration) -> bool;
+}
+
+/// The physics that never scrolls beyond the content, the content slows down
+/// by friction after a fling, and stops at the edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClampingScrollPhysics {
+  /// How fast the velocity decays, the velocity decays by `e^(-friction * t)`.
+  pub friction: f32,
+}
+
+/// The physics that allows scrolling beyond the content with increasing
+/// resistance, and bounces back to the edge when released.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BouncingScrollPhysics {
+  /// How fast the velocity decays, the velocity decays by `e^(-friction * t)`.
+  pub friction: f32,
+  /// How fast the content bounces back to the edge, a higher stiffness bounces
+  /// back faster.
+  pub stiffness: f32,
+}
+
+/// The positions a scrollable widget settles at after the user scrolled.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ScrollSnap {
+  /// Stop anywhere.
+  #[default]
+  None,
+  /// Stop at the multiples of the view size.
+  Page,
+  /// Stop at the multiples of the interval, for items with the same extent.
+  Interval(f32),
+  /// Stop at one of the offsets, for items with different extents.
+  Offsets(Vec<f32>),
+}
+
+const DEFAULT_FRICTION: f32 = 4.;
+const DEFAULT_STIFFNESS: f32 = 18.;
+/// The distance in pixels that close enough to the target.
+const DISTANCE_TOLERANCE: f32 = 0.5;
+/// The velocity in pixels per second that is slow enough to stop.
+const VELOCITY_TOLERANCE: f32 = 10.;
+
+pub(crate) fn default_scroll_physics() -> Rc<dyn ScrollPhysics> {
+  if cfg!(any(target_os = "macos", target_os = "ios", target_os = "android")) {
+    Rc::new(BouncingScrollPhysics::default())
+  } else {
+    Rc::new(ClampingScrollPhysics::default())
+  }
+}
+
+impl ScrollMetrics {
+  /// The distance beyond the content, negative if before the start.
+  pub fn overscroll(&self) -> f32 {
+    if self.pos < 0. {
+      self.pos
+    } else if self.pos > self.max {
+      self.pos - self.max
+    } else {
+      0.
+    }
+  }
+
+  /// Clamp the position into the scroll range.
+  pub fn clamp(&self, pos: f32) -> f32 { pos.clamp(0., self.max.max(0.)) }
+}
+
+impl Default for ClampingScrollPhysics {
+  fn default() -> Self { Self { friction: DEFAULT_FRICTION } }
+}
+
+impl Default for BouncingScrollPhysics {
+  fn default() -> Self { Self { friction: DEFAULT_FRICTION, stiffness: DEFAULT_STIFFNESS } }
+}
+
+impl ScrollPhysics for ClampingScrollPhysics {
+  fn apply_delta(&self, metrics: ScrollMetrics, delta: f32) -> f32 {
+    metrics.clamp(metrics.pos + delta)
+  }
+
+  fn ballistic_simulation(
+    &self, metrics: ScrollMetrics, velocity: f32,
+  ) -> Option<Box<dyn ScrollSimulation>> {
+    if metrics.overscroll() != 0. {
+      let target = metrics.clamp(metrics.pos);
+      Some(Box::new(SpringSimulation::new(metrics.pos, target, velocity, DEFAULT_STIFFNESS)))
+    } else if velocity.abs() < MIN_FLING_VELOCITY {
+      None
+    } else {
+      let friction = FrictionSimulation::new(metrics.pos, velocity, self.friction);
+      Some(Box::new(ClampedSimulation { simulation: friction, min: 0., max: metrics.max.max(0.) }))
+    }
+  }
+
+  fn projected_position(&self, metrics: ScrollMetrics, velocity: f32) -> f32 {
+    let end = FrictionSimulation::new(metrics.pos, velocity, self.friction).end_position();
+    metrics.clamp(end)
+  }
+}
+
+impl ScrollPhysics for BouncingScrollPhysics {
+  fn apply_delta(&self, metrics: ScrollMetrics, delta: f32) -> f32 {
+    let new = metrics.pos + delta;
+    let over = metrics.overscroll();
+    let leaving = if delta < 0. { new < 0. } else { new > metrics.max };
+    if !leaving || (over != 0. && over.signum() != delta.signum()) {
+      return new;
+    }
+
+    // The part inside the range moves freely, the part beyond the edge moves
+    // with a resistance that grows with the overscroll distance.
+    let bound = if delta < 0. { 0. } else { metrics.max.max(0.) };
+    let rest = new - bound - over;
+    let rate = (1. - over.abs() / metrics.viewport.max(1.)).clamp(0., 1.);
+    bound + over + rest * 0.5 * rate * rate
+  }
+
+  fn ballistic_simulation(
+    &self, metrics: ScrollMetrics, velocity: f32,
+  ) -> Option<Box<dyn ScrollSimulation>> {
+    if metrics.overscroll() != 0. {
+      let target = metrics.clamp(metrics.pos);
+      Some(Box::new(SpringSimulation::new(metrics.pos, target, velocity, self.stiffness)))
+    } else if velocity.abs() < MIN_FLING_VELOCITY {
+      None
+    } else {
+      Some(Box::new(BounceSimulation::new(
+        FrictionSimulation::new(metrics.pos, velocity, self.friction),
+        metrics.max.max(0.),
+        self.stiffness,
+      )))
+    }
+  }
+
+  fn projected_position(&self, metrics: ScrollMetrics, velocity: f32) -> f32 {
+    let end = FrictionSimulation::new(metrics.pos, velocity, self.friction).end_position();
+    metrics.clamp(end)
+  }
+
+  fn settle_simulation(
+    &self, metrics: ScrollMetrics, target: f32, velocity: f32,
+  ) -> Box<dyn ScrollSimulation> {
+    Box::new(SpringSimulation::new(metrics.pos, target, velocity, self.stiffness))
+  }
+}
+
+impl ScrollSnap {
+  /// Return the snap position closest to the `pos`.
+  pub fn snap(&self, pos: f32, viewport: f32, max: f32) -> f32 {
+    let max = max.max(0.);
+    let snapped = match self {
+      ScrollSnap::None => pos,
+      ScrollSnap::Page => snap_to_interval(pos, viewport, max),
+      ScrollSnap::Interval(interval) => snap_to_interval(pos, *interval, max),
+      ScrollSnap::Offsets(offsets) => offsets
+        .iter()
+        .copied()
+        .chain([0., max])
+        .min_by(|a, b| (a - pos).abs().total_cmp(&(b - pos).abs()))
+        .unwrap_or(pos),
+    };
+    snapped.clamp(0., max)
+  }
+
+  /// Whether the scrollable widget snaps.
+  pub fn is_none(&self) -> bool { matches!(self, ScrollSnap::None) }
+}
+
+fn snap_to_interval(pos: f32, interval: f32, max: f32) -> f32 {
+  if interval <= 0. {
+    return pos;
+  }
+  let snapped = (pos / interval).round() * interval;
+  // The end of the content is always a snap position, even if it's not a
+  // multiple of the interval.
+  if (max - pos).abs() < (snapped - pos).abs() { max } else { snapped }
+}
+
+/// A motion that slows down by friction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrictionSimulation {
+  start: f32,
+  velocity: f32,
+  friction: f32,
+}
+
+impl FrictionSimulation {
+  pub fn new(start: f32, velocity: f32, friction: f32) -> Self {
+    Self { start, velocity, friction: friction.max(f32::EPSILON) }
+  }
+
+  pub fn velocity(&self, time: Duration) -> f32 {
+    self.velocity * (-self.friction * time.as_secs_f32()).exp()
+  }
+
+  /// The position where the motion stops.
+  pub fn end_position(&self) -> f32 { self.start + self.velocity / self.friction }
+
+  /// The time when the motion reaches the `pos`, or `None` if it never does.
+  pub fn time_at(&self, pos: f32) -> Option<Duration> {
+    let rate = 1. - (pos - self.start) * self.friction / self.velocity;
+    (rate > 0. && rate <= 1.).then(|| Duration::from_secs_f32(-rate.ln() / self.friction))
+  }
+}
+
+impl ScrollSimulation for FrictionSimulation {
+  fn position(&self, time: Duration) -> f32 {
+    let decay = 1. - (-self.friction * time.as_secs_f32()).exp();
+    self.start + self.velocity * decay / self.friction
+  }
+
+  fn is_done(&self, time: Duration) -> bool {
+    // Stop when the remaining distance is small enough.
+    (self.velocity(time) / self.friction).abs() < DISTANCE_TOLERANCE
+  }
+}
+
+/// A critically damped spring that moves to the target without oscillation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringSimulation {
+  target: f32,
+  c1: f32,
+  c2: f32,
+  stiffness: f32,
+}
+
+impl SpringSimulation {
+  pub fn new(start: f32, target: f32, velocity: f32, stiffness: f32) -> Self {
+    let c1 = start - target;
+    Self { target, c1, c2: velocity + stiffness * c1, stiffness }
+  }
+
+  pub fn velocity(&self, time: Duration) -> f32 {
+    let t = time.as_secs_f32();
+    let Self { c1, c2, stiffness, .. } = *self;
+    (c2 - stiffness * (c1 + c2 * t)) * (-stiffness * t).exp()
+  }
+}
+
+impl ScrollSimulation for SpringSimulation {
+  fn position(&self, time: Duration) -> f32 {
+    if self.is_done(time) {
+      return self.target;
+    }
+    let t = time.as_secs_f32();
+    self.target + (self.c1 + self.c2 * t) * (-self.stiffness * t).exp()
+  }
+
+  fn is_done(&self, time: Duration) -> bool {
+    let t = time.as_secs_f32();
+    let offset = (self.c1 + self.c2 * t) * (-self.stiffness * t).exp();
+    offset.abs() < DISTANCE_TOLERANCE && self.velocity(time).abs() < VELOCITY_TOLERANCE
+  }
+}
+
+/// A friction motion that stops at the edges.
+struct ClampedSimulation {
+  simulation: FrictionSimulation,
+  min: f32,
+  max: f32,
+}
+
+impl ScrollSimulation for ClampedSimulation {
+  fn position(&self, time: Duration) -> f32 {
+    self
+      .simulation
+      .position(time)
+      .clamp(self.min, self.max)
+  }
+
+  fn is_done(&self, time: Duration) -> bool {
+    let pos = self.simulation.position(time);
+    self.simulation.is_done(time) || pos <= self.min || pos >= self.max
+  }
+}
+
+/// A friction motion that bounces back by a spring when it goes beyond the
+/// edges.
+struct BounceSimulation {
+  friction: FrictionSimulation,
+  bounce: Option<(Duration, SpringSimulation)>,
+}
+
+impl BounceSimulation {
+  fn new(friction: FrictionSimulation, max: f32, stiffness: f32) -> Self {
+    let end = friction.end_position();
+    let edge = if end < 0. {
+      Some(0.)
+    } else if end > max {
+      Some(max)
+    } else {
+      None
+    };
+    let bounce = edge.and_then(|edge| {
+      let at = friction.time_at(edge)?;
+      let velocity = friction.velocity(at);
+      Some((at, SpringSimulation::new(edge, edge, velocity, stiffness)))
+    });
+    Self { friction, bounce }
+  }
+}
+
+impl ScrollSimulation for BounceSimulation {
+  fn position(&self, time: Duration) -> f32 {
+    match &self.bounce {
+      Some((at, spring)) if time >= *at => spring.position(time - *at),
+      _ => self.friction.position(time),
+    }
+  }
+
+  fn is_done(&self, time: Duration) -> bool {
+    match &self.bounce {
+      Some((at, spring)) => time >= *at && spring.is_done(time - *at),
+      None => self.friction.is_done(time),
+    }
+  }
+}
+
+/// A motion to the target that follows a transition, used by the animated
+/// scroll.
+pub struct TransitionSimulation {
+  from: f32,
+  to: f32,
+  transition: Rc<dyn Transition>,
+}
+
+impl TransitionSimulation {
+  pub fn new(from: f32, to: f32, transition: Rc<dyn Transition>) -> Self {
+    Self { from, to, transition }
+  }
+}
+
+impl ScrollSimulation for TransitionSimulation {
+  fn position(&self, time: Duration) -> f32 {
+    let rate = self.transition.rate_of_change(time).value();
+    self.from + (self.to - self.from) * rate
+  }
+
+  fn is_done(&self, time: Duration) -> bool {
+    matches!(self.transition.rate_of_change(time), AnimateProgress::Finish)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SEC: Duration = Duration::from_secs(1);
+
+  fn metrics(pos: f32) -> ScrollMetrics { ScrollMetrics { pos, max: 1000., viewport: 100. } }
+
+  #[test]
+  fn clamping_physics() {
+    let physics = ClampingScrollPhysics::default();
+    assert_eq!(physics.apply_delta(metrics(10.), -50.), 0.);
+    assert_eq!(physics.apply_delta(metrics(990.), 50.), 1000.);
+    assert!(
+      physics
+        .ballistic_simulation(metrics(100.), 10.)
+        .is_none()
+    );
+
+    let fling = physics
+      .ballistic_simulation(metrics(900.), 2000.)
+      .unwrap();
+    assert!(fling.position(SEC / 10) > 900.);
+    assert_eq!(fling.position(SEC * 10), 1000.);
+    assert!(fling.is_done(SEC * 10));
+  }
+
+  #[test]
+  fn friction_slows_down() {
+    let friction = FrictionSimulation::new(0., 1000., 4.);
+    assert_eq!(friction.end_position(), 250.);
+    let (a, b) = (friction.position(SEC / 10), friction.position(SEC / 5));
+    assert!(a > 0. && b - a < a);
+    assert!(friction.is_done(SEC * 3));
+    assert!((friction.position(SEC * 3) - 250.).abs() < 1.);
+
+    let at = friction.time_at(200.).unwrap();
+    assert!((friction.position(at) - 200.).abs() < 0.01);
+    assert!(friction.time_at(300.).is_none());
+  }
+
+  #[test]
+  fn rubber_band_overscroll() {
+    let physics = BouncingScrollPhysics::default();
+    assert_eq!(physics.apply_delta(metrics(500.), 10.), 510.);
+    // Only half of the delta beyond the edge applies.
+    assert_eq!(physics.apply_delta(metrics(10.), -30.), -10.);
+    // The resistance grows with the overscroll distance.
+    let far = physics.apply_delta(metrics(-50.), -20.);
+    assert!(far < -50. && far > -55.);
+    // Moving back is not resisted.
+    assert_eq!(physics.apply_delta(metrics(-50.), 20.), -30.);
+  }
+
+  #[test]
+  fn bounce_back() {
+    let physics = BouncingScrollPhysics::default();
+    let back = physics
+      .ballistic_simulation(metrics(-40.), 0.)
+      .unwrap();
+    assert!(back.position(SEC / 10) > -40.);
+    assert!(back.is_done(SEC * 2));
+    assert_eq!(back.position(SEC * 2), 0.);
+
+    let fling = physics
+      .ballistic_simulation(metrics(950.), 2000.)
+      .unwrap();
+    let max = (0..100)
+      .map(|i| fling.position(SEC * i / 50))
+      .fold(f32::MIN, f32::max);
+    assert!(max > 1000.);
+    assert!(fling.is_done(SEC * 5));
+    assert_eq!(fling.position(SEC * 5), 1000.);
+  }
+
+  #[test]
+  fn snap_positions() {
+    assert_eq!(ScrollSnap::None.snap(123., 100., 1000.), 123.);
+    assert_eq!(ScrollSnap::Page.snap(160., 100., 1000.), 200.);
+    assert_eq!(ScrollSnap::Interval(48.).snap(70., 100., 1000.), 48.);
+    assert_eq!(ScrollSnap::Interval(48.).snap(990., 100., 1000.), 1000.);
+    assert_eq!(ScrollSnap::Offsets(vec![0., 120., 300.]).snap(200., 100., 1000.), 120.);
+    assert_eq!(ScrollSnap::Page.snap(-30., 100., 1000.), 0.);
+  }
+}
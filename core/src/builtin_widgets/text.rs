@@ -1,7 +1,6 @@
 use std::cell::{Ref, RefCell};
 
 use font_db::GlyphBaseline;
-use typography::PlaceLineDirection;
 
 use crate::prelude::*;
 
@@ -28,6 +27,39 @@ pub struct Text {
   glyphs: RefCell<Option<VisualGlyphs>>,
 }
 
+/// A provider that collects the [`Text`] widgets laid out in its descendants.
+///
+/// Every descendant `Text` records its content and glyphs after layout, so the
+/// ancestor can work across multiple texts, such as a selection spanning them.
+#[derive(Clone, Default)]
+pub struct TextCollector(Sc<RefCell<Vec<CollectedText>>>);
+
+/// A [`Text`] recorded by the [`TextCollector`].
+#[derive(Clone)]
+pub struct CollectedText {
+  pub id: WidgetId,
+  pub text: CowArc<str>,
+  /// The glyphs of the last layout, in the coordinate of the `Text` widget.
+  pub glyphs: VisualGlyphs,
+}
+
+impl TextCollector {
+  /// The texts recorded, in the order of their first layout.
+  pub fn texts(&self) -> Ref<'_, [CollectedText]> { Ref::map(self.0.borrow(), |v| v.as_slice()) }
+
+  /// Remove the texts that `f` returns false, e.g. the disposed ones.
+  pub fn retain(&self, f: impl FnMut(&CollectedText) -> bool) { self.0.borrow_mut().retain(f) }
+
+  fn collect(&self, id: WidgetId, text: &CowArc<str>, glyphs: &VisualGlyphs) {
+    let mut texts = self.0.borrow_mut();
+    let collected = CollectedText { id, text: text.clone(), glyphs: glyphs.clone() };
+    match texts.iter_mut().find(|t| t.id == id) {
+      Some(t) => *t = collected,
+      None => texts.push(collected),
+    }
+  }
+}
+
 pub fn text_glyph(
   text: Substr, text_style: &TextStyle, text_align: TextAlign, bounds: Size,
 ) -> VisualGlyphs {
@@ -39,7 +71,7 @@ pub fn text_glyph(
       bounds,
       text_align,
       GlyphBaseline::Middle,
-      PlaceLineDirection::TopToBottom,
+      text_style.writing_mode.line_dir(),
     )
 }
 
@@ -67,14 +99,67 @@ impl Render for Text {
     let mut glyphs = text_glyph(self.text.substr(..), &style, text_align, clamp.max);
     let mut size = glyphs.visual_rect().size;
     if text_align != TextAlign::Start {
-      size.width = clamp.container_width(size.width);
+      if style.writing_mode.is_vertical() {
+        size.height = clamp.container_height(size.height);
+      } else {
+        size.width = clamp.container_width(size.width);
+      }
       glyphs.align(Rect::from_size(size));
     }
 
+    if let Some(collector) = Provider::of::<TextCollector>(ctx) {
+      collector.collect(ctx.widget_id(), &self.text, &glyphs);
+    }
     *self.glyphs.borrow_mut() = Some(glyphs);
     clamp.clamp(size)
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    if Self::is_vertical(ctx) {
+      // The vertical lines can't be narrower than wrapped by the height.
+      self.max_intrinsic_width(height, ctx)
+    } else {
+      // Every word is in its own line, the longest one is the width.
+      self
+        .measure(Size::new(0., f32::INFINITY), ctx)
+        .width
+    }
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    let height = if Self::is_vertical(ctx) { height } else { f32::INFINITY };
+    self
+      .measure(Size::new(f32::INFINITY, height), ctx)
+      .width
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    if Self::is_vertical(ctx) {
+      // Every word is in its own vertical line, the longest one is the height.
+      self
+        .measure(Size::new(f32::INFINITY, 0.), ctx)
+        .height
+    } else {
+      self.max_intrinsic_height(width, ctx)
+    }
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let width = if Self::is_vertical(ctx) { f32::INFINITY } else { width };
+    self
+      .measure(Size::new(width, f32::INFINITY), ctx)
+      .height
+  }
+
+  fn baseline(&self, kind: BaselineKind, _: &mut LayoutCtx) -> Option<f32> {
+    let glyphs = self.glyphs()?;
+    let line = match kind {
+      BaselineKind::First => 0,
+      BaselineKind::Last => glyphs.line_count().checked_sub(1)?,
+    };
+    glyphs.line_baseline(line)
+  }
+
   fn visual_box(&self, _: &mut VisualCtx) -> Option<Rect> {
     Some(
       self
@@ -101,6 +186,20 @@ impl Text {
   pub fn new(text: impl Into<CowArc<str>>) -> Self {
     Self { text: text.into(), glyphs: Default::default() }
   }
+  /// The size of the text laid out in the bounds, without keeping the glyphs.
+  fn measure(&self, bounds: Size, ctx: &mut LayoutCtx) -> Size {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let text_align = Provider::of::<TextAlign>(ctx).map_or(TextAlign::Start, |t| *t);
+    text_glyph(self.text.substr(..), &style, text_align, bounds)
+      .visual_rect()
+      .size
+  }
+
+  /// Whether the lines are vertical, they wrap by the height then.
+  fn is_vertical(ctx: &mut LayoutCtx) -> bool {
+    Provider::of::<TextStyle>(ctx).is_some_and(|s| s.writing_mode.is_vertical())
+  }
+
   pub fn glyphs(&self) -> Option<Ref<VisualGlyphs>> {
     Ref::filter_map(self.glyphs.borrow(), |v| v.as_ref()).ok()
   }
@@ -136,6 +235,8 @@ define_text_with_theme_style!(H6, title_small);
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
+  use std::{cell::Cell, rc::Rc};
+
   use ribir::{core::test_helper::*, material as ribir_material, prelude::*};
   use ribir_dev_helper::*;
 
@@ -193,4 +294,169 @@ mod tests {
     .with_wnd_size(WND_SIZE)
     .with_comparison(0.0001)
   );
+
+  #[test]
+  fn text_intrinsic_width() {
+    reset_test_env!();
+
+    #[derive(SingleChild)]
+    struct Measure(Rc<Cell<(f32, f32)>>);
+
+    impl Render for Measure {
+      fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+        let child = ctx.assert_single_child();
+        let min = ctx.child_intrinsic_size(child, IntrinsicSize::MinWidth, f32::INFINITY);
+        let max = ctx.child_intrinsic_size(child, IntrinsicSize::MaxWidth, f32::INFINITY);
+        self.0.set((min, max));
+        ctx.perform_child_layout(child, clamp)
+      }
+    }
+
+    let widths = Rc::new(Cell::new((0., 0.)));
+    let w = widths.clone();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @(Measure(w.clone())) { @Text {
+          text: "Hello ribir world!",
+          text_overflow: TextOverflow::AutoWrap,
+        } }
+    });
+    wnd.draw_frame();
+
+    let (min, max) = widths.get();
+    assert!(0. < min && min < max);
+    // The text is laid out again after it's measured.
+    assert_eq!(
+      wnd
+        .layout_info_by_path(&[0, 0])
+        .unwrap()
+        .size
+        .unwrap()
+        .width,
+      max
+    );
+  }
+
+  #[test]
+  fn text_baseline() {
+    reset_test_env!();
+
+    #[derive(SingleChild)]
+    struct Baselines(Rc<Cell<(f32, f32)>>);
+
+    impl Render for Baselines {
+      fn perform_layout(&self, _: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+        let child = ctx.assert_single_child();
+        let size = ctx.perform_child_layout(child, BoxClamp::max_size(Size::new(60., 500.)));
+        let first = ctx.child_baseline(child, BaselineKind::First);
+        let last = ctx.child_baseline(child, BaselineKind::Last);
+        self.0.set((first.unwrap(), last.unwrap()));
+        size
+      }
+    }
+
+    let baselines = Rc::new(Cell::new((0., 0.)));
+    let b = baselines.clone();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @(Baselines(b.clone())) {
+        @Text {
+          text: "Hello ribir world!",
+          font_size: 16.,
+          text_line_height: 20.,
+          text_overflow: TextOverflow::AutoWrap,
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let (first, last) = baselines.get();
+    assert!(0. < first && first < 20.);
+    // The last line is below the first one by the line height.
+    let lines = ((last - first) / 20.).round();
+    assert!(lines >= 1.);
+    assert!((last - first - lines * 20.).abs() < 0.1);
+  }
+
+  #[test]
+  fn vertical_text() {
+    reset_test_env!();
+
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Text {
+        text: "Hello\nribir",
+        text_line_height: 20.,
+        writing_mode: WritingMode::VerticalRl,
+      }
+    });
+    wnd.draw_frame();
+
+    let size = wnd
+      .layout_info_by_path(&[0])
+      .unwrap()
+      .size
+      .unwrap();
+    // Two vertical lines, each one is as wide as the line height.
+    assert!((size.width - 40.).abs() < 0.01);
+    assert!(size.height > size.width);
+  }
+
+  #[test]
+  fn vertical_text_intrinsic_height() {
+    reset_test_env!();
+
+    #[derive(SingleChild)]
+    struct Measure(Rc<Cell<(f32, f32, f32)>>);
+
+    impl Render for Measure {
+      fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+        let child = ctx.assert_single_child();
+        let min = ctx.child_intrinsic_size(child, IntrinsicSize::MinHeight, f32::INFINITY);
+        let max = ctx.child_intrinsic_size(child, IntrinsicSize::MaxHeight, f32::INFINITY);
+        let width = ctx.child_intrinsic_size(child, IntrinsicSize::MaxWidth, max);
+        self.0.set((min, max, width));
+        ctx.perform_child_layout(child, clamp)
+      }
+    }
+
+    let sizes = Rc::new(Cell::new((0., 0., 0.)));
+    let s = sizes.clone();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @(Measure(s.clone())) {
+        @Text {
+          text: "Hello ribir world!",
+          text_line_height: 20.,
+          text_overflow: TextOverflow::AutoWrap,
+          writing_mode: WritingMode::VerticalRl,
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let (min, max, width) = sizes.get();
+    assert!(0. < min && min < max);
+    // All the text is in one vertical line if it's as high as it likes.
+    assert!((width - 20.).abs() < 0.01);
+  }
+
+  #[test]
+  fn vertical_text_orientation() {
+    reset_test_env!();
+
+    let orientation = Stateful::new(TextOrientation::Sideways);
+    let o = orientation.clone_watcher();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Text {
+        text: "Hello",
+        writing_mode: WritingMode::VerticalRl,
+        text_orientation: pipe!(*$read(o)),
+      }
+    });
+    wnd.draw_frame();
+    let height = |wnd: &TestWindow| wnd.layout_info_by_path(&[0]).unwrap().size.unwrap().height;
+    let sideways = height(&wnd);
+
+    *orientation.write() = TextOrientation::Upright;
+    wnd.draw_frame();
+    // Every upright Latin character takes a full em of the line.
+    assert!(height(&wnd) > sideways);
+  }
 }
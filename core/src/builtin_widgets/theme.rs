@@ -220,7 +220,14 @@ fn typography_theme() -> TypographyTheme {
     };
     let overflow = TextOverflow::Overflow;
     TextTheme {
-      text: TextStyle { line_height, font_size, letter_space, font_face, overflow },
+      text: TextStyle {
+        line_height,
+        font_size,
+        letter_space,
+        font_face,
+        overflow,
+        ..<_>::default()
+      },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
         decoration_color: Color::BLACK.with_alpha(0.87).into(),
@@ -1,10 +1,11 @@
+use ahash::{HashMap, HashMapExt};
 use ribir_geom::{Point, Size};
 
 use super::{WidgetCtx, WidgetCtxImpl};
 use crate::{
   context::VisualCtx,
   prelude::ProviderCtx,
-  widget::{BoxClamp, VisualBox, WidgetTree},
+  widget::{BaselineKind, BoxClamp, IntrinsicSize, VisualBox, WidgetTree},
   widget_tree::WidgetId,
 };
 
@@ -20,6 +21,17 @@ pub struct LayoutCtx<'a> {
   pub(crate) tree: &'a mut WidgetTree,
   pub(crate) provider_ctx: ProviderCtx,
   pub(crate) laid_out_queue: &'a mut Vec<WidgetId>,
+  /// The layout results of the running intrinsic measuring, they're discarded
+  /// when the measuring finishes, so measuring never changes the real layout.
+  measuring: Option<HashMap<WidgetId, MeasuredLayout>>,
+}
+
+/// The layout result of a widget during the intrinsic measuring.
+#[derive(Default)]
+pub(crate) struct MeasuredLayout {
+  pub(crate) clamp: Option<BoxClamp>,
+  pub(crate) size: Option<Size>,
+  pub(crate) pos: Point,
 }
 
 impl<'a> WidgetCtxImpl for LayoutCtx<'a> {
@@ -28,6 +40,8 @@ impl<'a> WidgetCtxImpl for LayoutCtx<'a> {
 
   #[inline]
   fn tree(&self) -> &WidgetTree { self.tree }
+
+  fn measured(&self, id: WidgetId) -> Option<&MeasuredLayout> { self.measuring.as_ref()?.get(&id) }
 }
 
 impl<'a> LayoutCtx<'a> {
@@ -39,7 +53,7 @@ impl<'a> LayoutCtx<'a> {
     } else {
       ProviderCtx::default()
     };
-    Self { id, tree, provider_ctx, laid_out_queue }
+    Self { id, tree, provider_ctx, laid_out_queue, measuring: None }
   }
 
   /// Perform layout of the widget of the context and return its size.
@@ -56,9 +70,18 @@ impl<'a> LayoutCtx<'a> {
         debug_assert!(clamp.min.is_finite());
         let size = id.assert_get(tree2).perform_layout(clamp, self);
         debug_assert!(size.is_finite());
+        if let Some(measuring) = self.measuring.as_mut() {
+          let info = measuring.entry(id).or_default();
+          info.clamp = Some(clamp);
+          info.size = Some(size);
+          self.provider_ctx.pop_providers_for(id);
+          return size;
+        }
+
         let info = self.tree.store.layout_info_or_default(id);
         info.clamp = clamp;
         info.size = Some(size);
+        info.depended_by_parent = false;
 
         {
           VisualCtx::from_layout_ctx(self).update_visual_box();
@@ -88,22 +111,123 @@ impl<'a> LayoutCtx<'a> {
       })
   }
 
+  /// Return the intrinsic size of the `child` without performing its layout,
+  /// the `extent` is the size of the other axis, it may be infinite.
+  ///
+  /// The result is cached until the `child` or its descendants are marked
+  /// dirty. Measuring doesn't record any layout result, the widgets laid out
+  /// by the measuring are laid out again by the real layout.
+  pub fn child_intrinsic_size(&mut self, child: WidgetId, kind: IntrinsicSize, extent: f32) -> f32 {
+    if let Some(value) = self
+      .tree
+      .store
+      .layout_info(child)
+      .and_then(|info| info.intrinsic.get(kind, extent))
+    {
+      return value;
+    }
+
+    // Safety: the `tree` just use to get the widget of `child`, and `tree2` not
+    // drop or modify it during measuring.
+    let tree2 = unsafe { &*(self.tree as *mut WidgetTree) };
+    let render = child.assert_get(tree2);
+    let outermost = self.measuring.is_none();
+    if outermost {
+      self.measuring = Some(HashMap::new());
+    }
+    let id = std::mem::replace(&mut self.id, child);
+    let value = match kind {
+      IntrinsicSize::MinWidth => render.min_intrinsic_width(extent, self),
+      IntrinsicSize::MaxWidth => render.max_intrinsic_width(extent, self),
+      IntrinsicSize::MinHeight => render.min_intrinsic_height(extent, self),
+      IntrinsicSize::MaxHeight => render.max_intrinsic_height(extent, self),
+    };
+    self.provider_ctx.pop_providers_for(child);
+    self.id = id;
+
+    if outermost {
+      let measured = self.measuring.take().unwrap_or_default();
+      for (id, measured) in measured {
+        // The widget may keep the state of the measuring layout, so its real
+        // layout can't be reused.
+        if measured.size.is_some() {
+          if let Some(info) = self.tree.store.get_mut(&id) {
+            info.size = None;
+          }
+        }
+      }
+    }
+
+    let info = self.tree.store.layout_info_or_default(child);
+    info.intrinsic.insert(kind, extent, value);
+    value
+  }
+
+  /// Return the distance from the top of the `child` to its `kind` baseline,
+  /// the `child` must be laid out before querying its baseline. Return `None`
+  /// if the `child` has no baseline.
+  pub fn child_baseline(&mut self, child: WidgetId, kind: BaselineKind) -> Option<f32> {
+    self.depend_on_child(child);
+    // Safety: the `tree` just use to get the widget of `child`, and `tree2` not
+    // drop or modify it during querying.
+    let tree2 = unsafe { &*(self.tree as *mut WidgetTree) };
+    let render = child.assert_get(tree2);
+    let id = std::mem::replace(&mut self.id, child);
+    let baseline = render.baseline(kind, self);
+    self.provider_ctx.pop_providers_for(child);
+    self.id = id;
+
+    // The content of the child may be moved by its transform, like the padding.
+    baseline.map(|y| match render.get_transform() {
+      Some(t) => t.transform_point(Point::new(0., y)).y,
+      None => y,
+    })
+  }
+
+  /// Tell the layout that this widget reads more than the size of the laid
+  /// out `child`, e.g. its baseline or its painting data. So the `child`
+  /// isn't a relayout boundary even if it's laid out with a tight clamp, and
+  /// the changes of its descendants relayout this widget too.
+  ///
+  /// The [`LayoutCtx::child_baseline`] calls it by itself.
+  pub fn depend_on_child(&mut self, child: WidgetId) {
+    if self.measuring.is_none() {
+      self
+        .tree
+        .store
+        .layout_info_or_default(child)
+        .depended_by_parent = true;
+    }
+  }
+
+  /// Return the baseline of the first child that has one, or the last child
+  /// for the [`BaselineKind::Last`], relative to the top of this widget.
+  pub fn children_baseline(&mut self, kind: BaselineKind) -> Option<f32> {
+    let (ctx, children) = self.split_children();
+    let mut children: Vec<_> = children.collect();
+    if kind == BaselineKind::Last {
+      children.reverse();
+    }
+    children.into_iter().find_map(|c| {
+      let baseline = ctx.child_baseline(c, kind)?;
+      Some(ctx.position(c).unwrap_or_default().y + baseline)
+    })
+  }
+
   /// Adjust the position of the widget where it should be placed relative to
   /// its parent.
   #[inline]
   pub fn update_position(&mut self, child: WidgetId, pos: Point) {
-    self.tree.store.layout_info_or_default(child).pos = pos;
+    if let Some(measuring) = self.measuring.as_mut() {
+      measuring.entry(child).or_default().pos = pos;
+    } else {
+      self.tree.store.layout_info_or_default(child).pos = pos;
+    }
   }
 
   /// Return the position of the widget relative to its parent.
   #[inline]
-  pub fn position(&mut self, child: WidgetId) -> Option<Point> {
-    self
-      .tree
-      .store
-      .layout_info(child)
-      .map(|info| info.pos)
-  }
+  pub fn position(&mut self, child: WidgetId) -> Option<Point> { self.widget_box_pos(child) }
 
   /// Adjust the size of the layout widget. Use this method to directly modify
   /// the size of a widget. In most cases, it is unnecessary to call this
@@ -111,7 +235,11 @@ impl<'a> LayoutCtx<'a> {
   /// Only use this method if you are certain of its effects.
   #[inline]
   pub fn update_size(&mut self, child: WidgetId, size: Size) {
-    self.tree.store.layout_info_or_default(child).size = Some(size);
+    if let Some(measuring) = self.measuring.as_mut() {
+      measuring.entry(child).or_default().size = Some(size);
+    } else {
+      self.tree.store.layout_info_or_default(child).size = Some(size);
+    }
   }
 
   /// Split a children iterator from the context, returning a tuple of `&mut
@@ -151,10 +279,16 @@ impl<'a> LayoutCtx<'a> {
   #[inline]
   pub fn force_child_relayout(&mut self, child: WidgetId) -> bool {
     assert_eq!(child.parent(self.tree), Some(self.id));
+    if let Some(measuring) = self.measuring.as_mut() {
+      return measuring.remove(&child).is_some();
+    }
     self.tree.store.force_layout(child).is_some()
   }
 
   fn get_calculated_size(&self, child: WidgetId, clamp: BoxClamp) -> Option<Size> {
+    if let Some(info) = self.measured(child) {
+      return if info.clamp == Some(clamp) { info.size } else { None };
+    }
     let info = self.tree.store.layout_info(child)?;
     if info.clamp == clamp { info.size } else { None }
   }
@@ -1,6 +1,6 @@
 use ribir_geom::Transform;
 
-use super::WidgetCtxImpl;
+use super::{WidgetCtx, WidgetCtxImpl};
 use crate::{
   prelude::{Painter, ProviderCtx, WidgetId},
   widget::WidgetTree,
@@ -18,6 +18,9 @@ pub struct PaintingCtx<'a> {
   /// text requires translation, but the background should not.
   /// The `Background` should utilize a `box_painter` that applies this matrix.
   box_offset: Transform,
+  /// The children of the widget are painted by itself, the framework should
+  /// not paint them again.
+  skip_children: bool,
 }
 
 impl<'a> WidgetCtxImpl for PaintingCtx<'a> {
@@ -37,7 +40,7 @@ impl<'a> PaintingCtx<'a> {
     };
 
     let box_offset = Transform::identity();
-    Self { id, tree, painter, provider_ctx, box_offset }
+    Self { id, tree, painter, provider_ctx, box_offset, skip_children: false }
   }
 
   /// Called by the framework when the painting widget is finished.
@@ -47,9 +50,81 @@ impl<'a> PaintingCtx<'a> {
   #[inline]
   pub(crate) fn switch_to(&mut self, id: WidgetId) {
     self.box_offset = Transform::identity();
+    self.skip_children = false;
     self.id = id;
   }
 
+  /// Paint the subtree of the current widget.
+  pub(crate) fn paint_subtree(&mut self) {
+    let tree = self.tree;
+    let root = self.id();
+    let mut painting = vec![];
+    loop {
+      let id = self.id();
+      if !self.painter().is_transparent() {
+        if let Some(layout_box) = self.box_rect() {
+          painting.push(id);
+          let render = id.assert_get(tree);
+          self
+            .painter()
+            .save()
+            .translate(layout_box.min_x(), layout_box.min_y());
+          render.paint(self);
+
+          let first_child = if self.skip_children { None } else { id.first_child(tree) };
+          if let Some(c) = first_child {
+            self.switch_to(c);
+            continue;
+          }
+        };
+      }
+
+      while let Some(painting) = painting.pop() {
+        self.painter().restore();
+        self.switch_to(painting);
+        self.finish();
+
+        if painting == root {
+          break;
+        }
+        if let Some(sibling) = painting.next_sibling(tree) {
+          self.switch_to(sibling);
+          break;
+        }
+      }
+
+      if painting.is_empty() {
+        break;
+      }
+    }
+  }
+
+  /// Paint the children of the current widget to the `painter` instead of the
+  /// painter of the context, and the framework will not paint them again. It's
+  /// used to record the children, for example, to a cached bundle.
+  pub(crate) fn paint_children_to(&mut self, painter: &mut Painter) {
+    self.skip_children = true;
+    let tree = self.tree;
+    let mut ctx = PaintingCtx {
+      id: self.id,
+      tree,
+      painter,
+      provider_ctx: std::mem::take(&mut self.provider_ctx),
+      box_offset: Transform::identity(),
+      skip_children: false,
+    };
+    for c in self.id.children(tree) {
+      ctx.switch_to(c);
+      ctx.paint_subtree();
+    }
+    self.provider_ctx = ctx.provider_ctx;
+  }
+
+  /// Tell the framework that the children of the current widget are painted by
+  /// itself, for example, from a cached bundle.
+  #[inline]
+  pub(crate) fn skip_children(&mut self) { self.skip_children = true; }
+
   /// Apply a transformation apply only for the content but should not effect
   /// the box widget painter.
   pub fn content_only_transform_apply(&mut self, matrix: &Transform) {
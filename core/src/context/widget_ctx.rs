@@ -4,6 +4,7 @@ use ribir_algo::Sc;
 use ribir_geom::{Point, Rect, Size};
 
 use crate::{
+  context::layout_ctx::MeasuredLayout,
   prelude::ProviderCtx,
   query::QueryRef,
   state::WriteRef,
@@ -102,6 +103,10 @@ pub(crate) trait WidgetCtxImpl {
 
   fn tree(&self) -> &WidgetTree;
 
+  /// The layout of the widget in the running intrinsic measuring, it's read
+  /// before the layout in the tree.
+  fn measured(&self, _: WidgetId) -> Option<&MeasuredLayout> { None }
+
   fn split_tree(&mut self) -> (&mut Self, &WidgetTree) {
     // Safety: The widget tree remains read-only throughout the entire hit testing
     // process.
@@ -146,6 +151,9 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
   fn box_size(&self) -> Option<Size> { self.widget_box_size(self.id()) }
 
   fn layout_clamp(&self) -> Option<BoxClamp> {
+    if let Some(clamp) = self.measured(self.id()).and_then(|m| m.clamp) {
+      return Some(clamp);
+    }
     self
       .tree()
       .store
@@ -160,6 +168,9 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
   }
 
   fn widget_box_size(&self, wid: WidgetId) -> Option<Size> {
+    if let Some(size) = self.measured(wid).and_then(|m| m.size) {
+      return Some(size);
+    }
     self
       .tree()
       .layout_info(wid)
@@ -167,14 +178,15 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
   }
 
   fn widget_box_pos(&self, wid: WidgetId) -> Option<Point> {
+    if let Some(m) = self.measured(wid) {
+      return Some(m.pos);
+    }
     self.tree().layout_info(wid).map(|info| info.pos)
   }
 
   fn widget_box_rect(&self, wid: WidgetId) -> Option<Rect> {
-    self
-      .tree()
-      .layout_info(wid)
-      .and_then(|info| info.size.map(|size| Rect::new(info.pos, size)))
+    let size = self.widget_box_size(wid)?;
+    Some(Rect::new(self.widget_box_pos(wid)?, size))
   }
 
   fn map_to_global(&self, pos: Point) -> Point { self.tree().map_to_global(pos, self.id()) }
@@ -102,7 +102,10 @@ struct FrameHandle {
 impl FrameHandle {
   fn new(wnd: Sc<Window>, force_draw: bool) -> Self {
     let wnd_id = wnd.id();
-    let this = Self { wnd, events: vec![], need_redraw: force_draw, has_data_changed: false };
+    let mut this = Self { wnd, events: vec![], need_redraw: false, has_data_changed: false };
+    if force_draw {
+      this.force_redraw();
+    }
 
     let mut ticker = this.wnd.frame_ticker.clone();
     ticker.next(FrameMsg::NewFrame(Instant::now()));
@@ -119,11 +122,20 @@ impl FrameHandle {
     }
   }
 
+  /// Redraw the whole window even if nothing changed, the content of the
+  /// window may be lost.
+  fn force_redraw(&mut self) {
+    self.need_redraw = true;
+    self.wnd.tree().damage_all();
+  }
+
   fn on_framework_event(mut self, event: FrameworkEvent) -> (Vec<RibirEvent>, EventLoopHandle) {
     match event {
       FrameworkEvent::NewFrame { wnd_id, force_redraw } => {
         if wnd_id == self.wnd.id() {
-          self.need_redraw |= force_redraw;
+          if force_redraw {
+            self.force_redraw();
+          }
         } else {
           self.events.push(event.into());
         }
@@ -169,7 +181,9 @@ impl FrameHandle {
         if wnd_id != self.wnd.id() {
           self.events.push(event.into());
         } else {
-          self.need_redraw |= force;
+          if force {
+            self.force_redraw();
+          }
         }
       }
       _ => self.events.push(event.into()),
@@ -221,10 +235,7 @@ impl IdleHandle {
       }
       UiEvent::ModifiersChanged { wnd_id, state } => {
         if let Some(wnd) = AppCtx::get_window(wnd_id) {
-          wnd
-            .dispatcher
-            .borrow_mut()
-            .modifiers_changed(state);
+          wnd.process_modifiers_changed(state);
         }
       }
       UiEvent::ReceiveChars { wnd_id, chars } => {
@@ -238,9 +249,9 @@ impl IdleHandle {
           wnd.process_cursor_leave();
         }
       }
-      UiEvent::MouseWheel { wnd_id, delta_x, delta_y } => {
+      UiEvent::MouseWheel { wnd_id, delta_x, delta_y, phase } => {
         if let Some(wnd) = AppCtx::get_window(wnd_id) {
-          wnd.process_wheel(delta_x, delta_y);
+          wnd.process_wheel_phase(delta_x, delta_y, phase);
         }
       }
       UiEvent::CursorMoved { wnd_id, pos } => {
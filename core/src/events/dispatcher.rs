@@ -196,11 +196,11 @@ impl Dispatcher {
     self.info.mouse_buttons_mut().remove(button);
   }
 
-  pub fn dispatch_wheel(&mut self, delta_x: f32, delta_y: f32) {
+  pub fn dispatch_wheel(&mut self, delta_x: f32, delta_y: f32, phase: WheelPhase) {
     if let Some(id) = self.hit_widget() {
       self
         .window()
-        .add_delay_event(DelayEvent::Wheel { id, delta_x, delta_y });
+        .add_delay_event(DelayEvent::Wheel { id, delta_x, delta_y, phase });
     }
   }
 
@@ -4,15 +4,31 @@ use crate::{impl_common_event_deref, prelude::*};
 pub struct WheelEvent {
   pub delta_x: f32,
   pub delta_y: f32,
+  pub phase: WheelPhase,
   pub common: CommonEvent,
 }
 
+/// Where the wheel delta comes from, and the phase of the scrolling gesture of
+/// a precise device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WheelPhase {
+  /// The delta of a notched mouse wheel, in lines converted to pixels.
+  #[default]
+  Line,
+  /// The user starts scrolling on a precise device, like a touchpad.
+  Started,
+  /// The delta in pixels of a precise device.
+  Moved,
+  /// The user lifts the fingers from the precise device.
+  Ended,
+}
+
 impl_common_event_deref!(WheelEvent);
 
 impl WheelEvent {
   #[inline]
-  pub fn new(delta_x: f32, delta_y: f32, id: WidgetId, wnd: &Window) -> Self {
-    Self { delta_x, delta_y, common: CommonEvent::new(id, wnd.tree) }
+  pub fn new(delta_x: f32, delta_y: f32, phase: WheelPhase, id: WidgetId, wnd: &Window) -> Self {
+    Self { delta_x, delta_y, phase, common: CommonEvent::new(id, wnd.tree) }
   }
 }
 
@@ -70,7 +70,7 @@ pub mod prelude {
     ticker::{Duration, Instant},
     widget::*,
     widget_children::*,
-    widget_tree::{BoxClamp, DirtyPhase, LayoutInfo, TrackId, WidgetId},
+    widget_tree::{BaselineKind, BoxClamp, DirtyPhase, IntrinsicSize, LayoutInfo, TrackId, WidgetId},
     window::Window,
   };
   pub use crate::*;
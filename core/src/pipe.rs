@@ -537,6 +537,38 @@ impl Render for PipeNode {
     self.as_ref().data.perform_layout(clamp, ctx)
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .min_intrinsic_width(height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .max_intrinsic_width(height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .min_intrinsic_height(width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .max_intrinsic_height(width, ctx)
+  }
+
+  fn baseline(&self, kind: BaselineKind, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.as_ref().data.baseline(kind, ctx)
+  }
+
   fn visual_box(&self, ctx: &mut VisualCtx) -> Option<Rect> { self.as_ref().data.visual_box(ctx) }
 
   fn paint(&self, ctx: &mut PaintingCtx) { self.as_ref().data.paint(ctx) }
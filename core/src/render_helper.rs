@@ -36,6 +36,31 @@ where
     self.proxy().perform_layout(clamp, ctx)
   }
 
+  #[inline]
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.proxy().min_intrinsic_width(height, ctx)
+  }
+
+  #[inline]
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.proxy().max_intrinsic_width(height, ctx)
+  }
+
+  #[inline]
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.proxy().min_intrinsic_height(width, ctx)
+  }
+
+  #[inline]
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.proxy().max_intrinsic_height(width, ctx)
+  }
+
+  #[inline]
+  fn baseline(&self, kind: BaselineKind, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.proxy().baseline(kind, ctx)
+  }
+
   #[inline]
   fn visual_box(&self, ctx: &mut VisualCtx) -> Option<Rect> { self.proxy().visual_box(ctx) }
 
@@ -16,6 +16,8 @@ use crate::{
 pub struct Frame {
   pub commands: Vec<PaintCommand>,
   pub viewport: Rect,
+  /// The area changed since the last frame.
+  pub damage: Rect,
   pub surface: Color,
 }
 
@@ -110,6 +112,42 @@ impl TestWindow {
       .take()
   }
 
+  /// Take the commands of the last capture, you can render it to an image by
+  /// a backend.
+  pub fn take_last_capture(&mut self) -> Option<Frame> {
+    self
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .last_capture
+      .take()
+  }
+
+  /// Set the function that renders the captures to images, the captures are
+  /// resolved to `None` without it.
+  pub fn set_capture_render(&self, render: CaptureRender) {
+    self
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .capture_render = Some(render);
+  }
+
+  /// Set the device pixel ratio of the window, it's 1 by default.
+  pub fn set_device_pixel_ratio(&self, ratio: f32) {
+    self
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .device_pixel_ratio = ratio;
+  }
+
   pub fn content_count(&self) -> usize {
     let tree = self.0.tree();
     let root = tree.root();
@@ -124,6 +162,11 @@ impl TestWindow {
     AppCtx::new_test_frame(self);
   }
 
+  /// The number of the layouts performed in the last frame that has dirty
+  /// widgets, a widget laid out twice is counted twice. Use it to assert how
+  /// many widgets are affected by a change.
+  pub fn layout_count(&self) -> usize { self.0.layout_count() }
+
   pub fn fmt_tree(&self) -> String { self.tree().display_tree(self.tree().root()) }
 
   pub fn request_resize(&self, size: Size) {
@@ -143,17 +186,29 @@ impl std::ops::Deref for TestWindow {
   fn deref(&self) -> &Self::Target { &self.0 }
 }
 
+/// Render the commands to an image of the size in pixels, filled with the
+/// surface color before drawing.
+pub type CaptureRender = fn(Size, Color, &[PaintCommand]) -> Option<PixelImage>;
+
 pub struct TestShellWindow {
   pub cursor: CursorIcon,
   pub id: WindowId,
   pub surface_color: Color,
   pub last_frame: Option<Frame>,
+  /// The last frame requested by `ShellWindow::capture`.
+  pub last_capture: Option<Frame>,
+  /// Render the captures to images, a test can set it to render by a real
+  /// backend.
+  pub capture_render: Option<CaptureRender>,
+  pub device_pixel_ratio: f32,
   pub size: Size,
 }
 
 impl ShellWindow for TestShellWindow {
   fn inner_size(&self) -> Size { self.size }
 
+  fn device_pixel_ratio(&self) -> f32 { self.device_pixel_ratio }
+
   fn request_resize(&mut self, size: Size) { self.on_resize(size); }
 
   fn set_min_size(&mut self, _: Size) {}
@@ -191,10 +246,27 @@ impl ShellWindow for TestShellWindow {
   fn as_any_mut(&mut self) -> &mut dyn Any { self }
 
   fn draw_commands(
-    &mut self, _wnd_size: Size, viewport: Rect, surface_color: Color, commands: &[PaintCommand],
+    &mut self, _wnd_size: Size, viewport: Rect, damage: Rect, surface_color: Color,
+    commands: &[PaintCommand],
   ) {
     self.last_frame =
-      Some(Frame { commands: commands.to_owned(), viewport, surface: surface_color });
+      Some(Frame { commands: commands.to_owned(), viewport, damage, surface: surface_color });
+  }
+
+  fn capture(
+    &mut self, size: Size, surface_color: Color, commands: &[PaintCommand],
+  ) -> scheduler::BoxFuture<'static, Option<PixelImage>> {
+    let viewport = Rect::from_size(size);
+    self.last_capture = Some(Frame {
+      commands: commands.to_owned(),
+      viewport,
+      damage: viewport,
+      surface: surface_color,
+    });
+    let img = self
+      .capture_render
+      .and_then(|render| render(size, surface_color, commands));
+    Box::pin(async move { img })
   }
 
   fn request_draw(&self) {}
@@ -211,6 +283,9 @@ impl TestShellWindow {
       cursor: CursorIcon::Default,
       id: ID.fetch_add(1, Ordering::Relaxed).into(),
       last_frame: None,
+      last_capture: None,
+      capture_render: None,
+      device_pixel_ratio: 1.,
       surface_color: Color::WHITE,
       size,
     }
@@ -46,6 +46,53 @@ pub trait Render: 'static {
   ///    - Fall back to clamp limits for invalid calculations
   fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size;
 
+  /// The smallest width the widget can have without failing to paint its
+  /// content, if its height is `height`. The `height` may be infinite.
+  ///
+  /// Parents query it by `LayoutCtx::child_intrinsic_size` before they lay out
+  /// the child. The default implementation is the same as the
+  /// [`Render::max_intrinsic_width`], override it if the widget can be
+  /// narrower, like the text that wraps its lines.
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.max_intrinsic_width(height, ctx)
+  }
+
+  /// The width the widget would like to have if it's unlimited, if its height
+  /// is `height`. The `height` may be infinite.
+  ///
+  /// The default implementation performs the layout with an unlimited width,
+  /// override it if the widget can compute it cheaper.
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    IntrinsicSize::MaxWidth.measure(self, height, ctx)
+  }
+
+  /// The smallest height the widget can have without failing to paint its
+  /// content, if its width is `width`. The `width` may be infinite.
+  ///
+  /// The default implementation is the same as the
+  /// [`Render::max_intrinsic_height`].
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.max_intrinsic_height(width, ctx)
+  }
+
+  /// The height the widget would like to have if it's unlimited, if its width
+  /// is `width`. The `width` may be infinite.
+  ///
+  /// The default implementation performs the layout with an unlimited height.
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    IntrinsicSize::MaxHeight.measure(self, width, ctx)
+  }
+
+  /// The distance from the top of the widget to its `kind` baseline, it's
+  /// queried by `LayoutCtx::child_baseline` after the widget is laid out.
+  /// Return `None` if the widget has no baseline.
+  ///
+  /// The default implementation is the baseline of the first child that has
+  /// one, or the last child for the [`BaselineKind::Last`].
+  fn baseline(&self, kind: BaselineKind, ctx: &mut LayoutCtx) -> Option<f32> {
+    ctx.children_baseline(kind)
+  }
+
   /// Custom painting implementation
   ///
   /// Use `PaintingCtx::painter` for drawing operations. Child widgets are
@@ -97,6 +144,18 @@ pub trait Render: 'static {
   fn get_transform(&self) -> Option<Transform> { None }
 }
 
+impl IntrinsicSize {
+  /// Measure the intrinsic size by performing the layout of the `render` with
+  /// the [`IntrinsicSize::measure_clamp`], it's the default way a `Render`
+  /// computes its intrinsic sizes. The layout in measuring isn't recorded, it
+  /// neither changes the layout results nor fires the layout events.
+  pub fn measure<R: Render + ?Sized>(self, render: &R, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    let size = render.perform_layout(self.measure_clamp(extent), ctx);
+    let value = self.axis_of(size);
+    if value.is_finite() { value } else { 0. }
+  }
+}
+
 /// Result of a hit testing operation
 ///
 /// Contains both the hit status and child hit testing policy:
@@ -1,4 +1,9 @@
-use std::{cell::RefCell, cmp::Reverse, collections::BTreeSet, mem::MaybeUninit};
+use std::{
+  cell::{Cell, RefCell},
+  cmp::Reverse,
+  collections::BTreeSet,
+  mem::MaybeUninit,
+};
 
 pub mod widget_id;
 use indextree::Arena;
@@ -32,6 +37,12 @@ pub(crate) struct WidgetTree {
   pub(crate) store: LayoutStore,
   pub(crate) dirty_set: DirtySet,
   pub(crate) dummy_id: WidgetId,
+  /// The raster caches of the repaint boundaries, a cache is removed when any
+  /// widget in the boundary is marked dirty.
+  pub(crate) raster_caches: RefCell<ahash::HashMap<WidgetId, RasterCache>>,
+  /// The window area changed since the last frame, `None` means the whole
+  /// window.
+  pub(crate) damage: Cell<Option<Rect>>,
 }
 
 /// A tool that help you to mark a widget as dirty
@@ -91,6 +102,13 @@ impl WidgetTree {
       let Some((mut needs_layout, mut needs_paint)) = self.layout_list() else {
         break;
       };
+      let damaged = self.damage.get().is_some().then(|| {
+        needs_layout
+          .iter()
+          .chain(&needs_paint)
+          .copied()
+          .collect::<Vec<_>>()
+      });
       let mut visual_roots = BTreeSet::new();
       while let Some(wid) = needs_layout.pop() {
         if wid.is_dropped(self) {
@@ -134,6 +152,14 @@ impl WidgetTree {
           }
         }
       }
+
+      // The new area of the changed widgets, their old area is collected before
+      // the layout information is cleared.
+      for wid in damaged.into_iter().flatten() {
+        if !wid.is_dropped(self) {
+          self.add_damage(wid, self.store.layout_box_size(wid));
+        }
+      }
     }
   }
 
@@ -185,12 +211,17 @@ impl WidgetTree {
 
     let mut needs_layout = vec![];
     let mut needs_paint = vec![];
+    // The widgets whose intrinsic sizes were queried by their parent.
+    let mut intrinsic_queried = ahash::HashSet::default();
 
     for (id, dirty) in self.dirty_set.borrow_mut().drain() {
       if id.is_dropped(self) {
         continue;
       }
+      self.invalidate_raster_caches(id);
+      let mut old_size = self.store.layout_box_size(id);
       if dirty == DirtyPhase::Paint {
+        self.add_damage(id, old_size);
         needs_paint.push(id);
         continue;
       }
@@ -199,15 +230,26 @@ impl WidgetTree {
         for w in id.0.descendants(&self.arena).map(WidgetId) {
           if let Some(info) = self.store.get_mut(&w) {
             info.size.take();
+            info.intrinsic.clear();
           }
         }
       } else if let Some(info) = self.store.get_mut(&id) {
         info.size.take();
       }
 
+      // The intrinsic sizes of all the ancestors may depend on this widget.
+      for p in id.0.ancestors(&self.arena).map(WidgetId) {
+        if let Some(info) = self.store.get_mut(&p) {
+          if !info.intrinsic.is_empty() {
+            intrinsic_queried.insert(p);
+            info.intrinsic.clear();
+          }
+        }
+      }
+
       let mut relayout_root = id;
       // All ancestors of this render widget should relayout until the one which only
-      // sized by parent.
+      // sized by parent or is a relayout boundary.
       for p in id.0.ancestors(&self.arena).skip(1).map(WidgetId) {
         // The first one may be a pipe that is newly generated. Otherwise, if there
         // isn't layout information, it indicates that the ancestor marked for relayout
@@ -218,13 +260,16 @@ impl WidgetTree {
 
         relayout_root = p;
         if let Some(info) = self.store.get_mut(&p) {
-          info.size.take();
+          old_size = info.size.take();
         }
 
-        if !p.assert_get(self).size_affected_by_child() {
+        let bounded = !p.assert_get(self).size_affected_by_child()
+          || (self.is_relayout_boundary(p) && !intrinsic_queried.contains(&p));
+        if bounded && !self.is_depended_by_parent(p) {
           break;
         }
       }
+      self.add_damage(relayout_root, old_size);
       needs_layout.push(relayout_root);
     }
 
@@ -233,6 +278,70 @@ impl WidgetTree {
     Some((needs_layout, needs_paint))
   }
 
+  /// Invalidate the raster caches of the repaint boundaries that contain `id`.
+  /// The cache of `id` itself is kept, because it only caches the descendants.
+  fn invalidate_raster_caches(&self, id: WidgetId) {
+    let mut caches = self.raster_caches.borrow_mut();
+    if !caches.is_empty() {
+      for p in id.0.ancestors(&self.arena).skip(1) {
+        if let Some(cache) = caches.get_mut(&WidgetId(p)) {
+          cache.invalidate();
+        }
+      }
+    }
+  }
+
+  /// Add the window area that `id` occupies to the damage region. The area is
+  /// the box of the widget with the `size`, and its visual box.
+  fn add_damage(&self, id: WidgetId, size: Option<Size>) {
+    let Some(damage) = self.damage.get() else { return };
+    let visual = self
+      .store
+      .layout_info(id)
+      .and_then(|info| info.visual_box.bounds_rect());
+    let local = match (size, visual) {
+      (Some(size), Some(visual)) => Rect::from_size(size).union(&visual),
+      (Some(size), None) => Rect::from_size(size),
+      (None, Some(visual)) => visual,
+      (None, None) => return,
+    };
+    if local.is_empty() {
+      return;
+    }
+
+    let corners = rect_corners(&local).map(|p| self.map_to_global(p, id));
+    // Inflate a pixel to cover the anti-aliasing of the edges.
+    let global = Rect::from_points(corners).inflate(1., 1.);
+    self.damage.set(Some(damage.union(&global)));
+  }
+
+  /// Take the damage region accumulated since the last frame, `None` means
+  /// the whole window need to redraw.
+  pub(crate) fn take_damage(&self) -> Option<Rect> { self.damage.replace(Some(Rect::zero())) }
+
+  /// Mark the whole window as damaged.
+  pub(crate) fn damage_all(&self) { self.damage.set(None); }
+
+  /// A widget laid out with a tight clamp can't change its size, so the changes
+  /// of its descendants needn't relayout its parent, unless the parent queried
+  /// its intrinsic sizes. The widget itself isn't a boundary when it's dirty,
+  /// because its parent may read its data to lay out, e.g. `Expanded`.
+  fn is_relayout_boundary(&self, id: WidgetId) -> bool {
+    self
+      .store
+      .layout_info(id)
+      .is_some_and(|info| info.clamp.min == info.clamp.max)
+  }
+
+  /// The parent reads more than the size of the widget, like its baseline, so
+  /// the widget never stops the relayout.
+  fn is_depended_by_parent(&self, id: WidgetId) -> bool {
+    self
+      .store
+      .layout_info(id)
+      .is_some_and(|info| info.depended_by_parent)
+  }
+
   pub fn detach(&mut self, id: WidgetId) {
     if self.root() == id {
       let root = self.root();
@@ -249,8 +358,10 @@ impl WidgetTree {
   pub(crate) fn remove_subtree(&mut self, id: WidgetId) {
     assert_ne!(id, self.root(), "You should detach the root widget before remove it.");
 
+    let caches = self.raster_caches.get_mut();
     id.0.descendants(&self.arena).for_each(|id| {
       self.store.remove(WidgetId(id));
+      caches.remove(&WidgetId(id));
     });
     id.0.remove_subtree(&mut self.arena);
   }
@@ -281,7 +392,16 @@ impl WidgetTree {
     let dummy_id = new_node(&mut arena, Box::new(PureRender(Void)));
     dummy_id.0.remove(&mut arena);
 
-    Self { root, dummy_id, wnd_id, arena, store: <_>::default(), dirty_set: <_>::default() }
+    Self {
+      root,
+      dummy_id,
+      wnd_id,
+      arena,
+      store: <_>::default(),
+      dirty_set: <_>::default(),
+      raster_caches: <_>::default(),
+      damage: Cell::new(None),
+    }
   }
 
   pub fn disposed(&mut self) {
@@ -582,6 +702,44 @@ mod tests {
     assert_eq!(c_paint_cnt.read().paint_cnt.get(), 2);
   }
 
+  #[test]
+  fn tight_clamp_stops_relayout() {
+    reset_test_env!();
+
+    let size = Stateful::new(Size::splat(10.));
+    let c_size = size.clone_writer();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @MockMulti {
+        @MockBox { size: Size::splat(20.) }
+        @FixedSizeBox {
+          size: Size::splat(50.),
+          @MockMulti {
+            @MockBox { size: Size::splat(5.) }
+            // The tight clamp from its parent makes it a relayout boundary.
+            @UnconstrainedBox {
+              @MockMulti {
+                @MockBox { size: pipe!(*$read(size)) }
+              }
+            }
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    *c_size.write() = Size::splat(30.);
+    wnd.draw_frame();
+    // Only the boundary and the widgets below it are laid out.
+    assert_eq!(wnd.layout_count(), 3);
+    assert_eq!(
+      wnd
+        .layout_info_by_path(&[0, 1, 0, 1, 0, 0])
+        .unwrap()
+        .size,
+      Some(Size::splat(30.))
+    );
+  }
+
   #[derive(Declare, SingleChild)]
   pub struct FixedSizeBox {
     /// The specified size of the box.
@@ -632,6 +790,35 @@ mod tests {
     }
     .r_into()
   }
+
+  #[test]
+  fn damage_changed_widget() {
+    reset_test_env!();
+
+    let color = Stateful::new(Color::RED);
+    let c_color = color.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: Size::new(10., 10.) }
+          @Container {
+            size: Size::new(20., 20.),
+            background: pipe!(*$read(color)),
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+    assert_eq!(frame.damage, frame.viewport);
+
+    *c_color.write() = Color::BLUE;
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+    assert_eq!(frame.damage, Rect::new(Point::new(9., 0.), Size::new(22., 21.)));
+  }
+
   widget_layout_test!(
     visual_overflow,
     WidgetTester::new(visual_overflow()).with_wnd_size(Size::new(500., 500.)),
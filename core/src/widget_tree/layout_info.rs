@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use ribir_geom::{Rect, ZERO_SIZE};
+use smallvec::SmallVec;
 
 use super::{Lerp, WidgetId, WidgetTree};
 use crate::prelude::{INFINITY_SIZE, Point, Size};
@@ -202,8 +203,42 @@ pub struct LayoutInfo {
 
   /// the visual box of the render object
   pub visual_box: VisualBox,
+
+  /// The intrinsic sizes queried by the parent, they are kept until the render
+  /// object or its descendants are marked dirty.
+  pub intrinsic: IntrinsicCache,
+
+  /// The parent reads more than the size of the render object in its last
+  /// layout, e.g. the baseline, so it's never a relayout boundary.
+  pub(crate) depended_by_parent: bool,
+}
+
+/// The kinds of the intrinsic sizes of a render object, see
+/// [`Render::min_intrinsic_width`](crate::prelude::Render::min_intrinsic_width)
+/// and its siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntrinsicSize {
+  MinWidth,
+  MaxWidth,
+  MinHeight,
+  MaxHeight,
+}
+
+/// Which baseline of a render object to query, see
+/// [`Render::baseline`](crate::prelude::Render::baseline).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BaselineKind {
+  /// The baseline of the first line of the text.
+  First,
+  /// The baseline of the last line of the text.
+  Last,
 }
 
+/// The cache of the intrinsic sizes of a render object, keyed by the kind and
+/// the extent of the other axis.
+#[derive(Debug, Default, Clone)]
+pub struct IntrinsicCache(SmallVec<[(IntrinsicSize, u32, f32); 2]>);
+
 /// Store the render object's place relative to parent coordinate and the
 /// clamp passed from parent.
 #[derive(Default)]
@@ -316,6 +351,56 @@ impl BoxClamp {
   }
 }
 
+impl IntrinsicSize {
+  /// The clamp to measure the intrinsic size by performing the layout, the
+  /// `extent` is the size of the other axis, and it's unlimited if it's
+  /// infinite.
+  pub fn measure_clamp(self, extent: f32) -> BoxClamp {
+    let fixed = if extent.is_finite() { extent } else { 0. };
+    match self {
+      IntrinsicSize::MinWidth | IntrinsicSize::MaxWidth => {
+        BoxClamp { min: Size::new(0., fixed), max: Size::new(f32::INFINITY, extent) }
+      }
+      IntrinsicSize::MinHeight | IntrinsicSize::MaxHeight => {
+        BoxClamp { min: Size::new(fixed, 0.), max: Size::new(extent, f32::INFINITY) }
+      }
+    }
+  }
+
+  /// Return the measured axis of the size.
+  pub fn axis_of(self, size: Size) -> f32 {
+    match self {
+      IntrinsicSize::MinWidth | IntrinsicSize::MaxWidth => size.width,
+      IntrinsicSize::MinHeight | IntrinsicSize::MaxHeight => size.height,
+    }
+  }
+}
+
+impl IntrinsicCache {
+  const CAPACITY: usize = 8;
+
+  pub fn get(&self, kind: IntrinsicSize, extent: f32) -> Option<f32> {
+    self
+      .0
+      .iter()
+      .find(|(k, e, _)| *k == kind && *e == extent.to_bits())
+      .map(|(.., v)| *v)
+  }
+
+  pub(crate) fn insert(&mut self, kind: IntrinsicSize, extent: f32, value: f32) {
+    // A widget is usually measured by a few extents, drop the oldest one when
+    // the parent keeps asking with new extents, like during a resizing.
+    if self.0.len() >= Self::CAPACITY {
+      self.0.remove(0);
+    }
+    self.0.push((kind, extent.to_bits(), value));
+  }
+
+  pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+  pub(crate) fn clear(&mut self) { self.0.clear(); }
+}
+
 impl Default for BoxClamp {
   fn default() -> Self {
     Self { min: Size::new(0., 0.), max: Size::new(f32::INFINITY, f32::INFINITY) }
@@ -355,6 +440,42 @@ mod tests {
     fn paint(&self, _: &mut PaintingCtx) {}
   }
 
+  #[test]
+  fn measuring_not_record_layout() {
+    reset_test_env!();
+
+    #[derive(SingleChild, Declare)]
+    struct Measure;
+
+    impl Render for Measure {
+      fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+        let child = ctx.assert_single_child();
+        let width = ctx.child_intrinsic_size(child, IntrinsicSize::MaxWidth, 30.);
+        assert_eq!(width, 50.);
+        ctx.perform_child_layout(child, BoxClamp { min: Size::zero(), max: clamp.max })
+      }
+    }
+
+    let (sizes, w_sizes) = split_value(vec![]);
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Measure {
+        @MockBox {
+          size: Size::new(50., 50.),
+          @MockBox {
+            size: Size::new(20., 20.),
+            on_performed_layout: move |e| $write(w_sizes).push(e.box_size().unwrap()),
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    // Only the real layout is recorded, not the measuring one.
+    assert_eq!(*sizes.read(), [Size::new(20., 20.)]);
+    let size = wnd.layout_info_by_path(&[0, 0, 0]).unwrap().size;
+    assert_eq!(size, Some(Size::new(20., 20.)));
+  }
+
   #[test]
   fn fix_incorrect_relayout_root() {
     reset_test_env!();
@@ -277,42 +277,7 @@ impl WidgetId {
   }
 
   pub(crate) fn paint_subtree(self, tree: &WidgetTree, painter: &mut Painter) {
-    let mut ctx = PaintingCtx::new(self, tree, painter);
-    let mut painting = vec![];
-    loop {
-      let id = ctx.id();
-      if !ctx.painter().is_transparent() {
-        if let Some(layout_box) = ctx.box_rect() {
-          painting.push(id);
-          let render = id.assert_get(tree);
-          ctx
-            .painter()
-            .save()
-            .translate(layout_box.min_x(), layout_box.min_y());
-          render.paint(&mut ctx);
-
-          if let Some(c) = id.first_child(tree) {
-            ctx.switch_to(c);
-            continue;
-          }
-        };
-      }
-
-      while let Some(painting) = painting.pop() {
-        ctx.painter().restore();
-        ctx.switch_to(painting);
-        ctx.finish();
-
-        if let Some(sibling) = painting.next_sibling(tree) {
-          ctx.switch_to(sibling);
-          break;
-        }
-      }
-
-      if painting.is_empty() {
-        break;
-      }
-    }
+    PaintingCtx::new(self, tree, painter).paint_subtree();
   }
 }
 
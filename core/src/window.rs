@@ -128,6 +128,7 @@ pub enum UiEvent {
     wnd_id: WindowId,
     delta_x: f32,
     delta_y: f32,
+    phase: WheelPhase,
   },
   KeyBoard {
     wnd_id: WindowId,
@@ -208,6 +209,9 @@ pub struct Window {
   pub(crate) delay_drop_widgets: RefCell<Vec<(Option<WidgetId>, TrackId)>>,
 
   flags: Cell<WindowFlags>,
+  /// The number of the layouts performed in the last layout pass that has
+  /// dirty widgets.
+  layout_count: Cell<usize>,
 }
 
 bitflags! {
@@ -239,6 +243,8 @@ pub type BoxShell = Box<dyn Shell + Send>;
 pub trait ShellWindow {
   fn id(&self) -> WindowId;
   fn inner_size(&self) -> Size;
+  /// The number of the device pixels of a logical pixel of the window.
+  fn device_pixel_ratio(&self) -> f32;
   fn set_ime_cursor_area(&mut self, rect: &Rect);
   fn set_ime_allowed(&mut self, allowed: bool);
 
@@ -263,9 +269,20 @@ pub trait ShellWindow {
 
   fn request_draw(&self);
 
+  /// Draw the `commands` of a full frame, but only the `damage` area changed
+  /// since the last frame, the shell can keep the other area of the last
+  /// frame and only redraw the `damage` area.
   fn draw_commands(
-    &mut self, wnd_size: Size, viewport: Rect, surface_color: Color, commands: &[PaintCommand],
+    &mut self, wnd_size: Size, viewport: Rect, damage: Rect, surface_color: Color,
+    commands: &[PaintCommand],
   );
+
+  /// Render the `commands` to an offscreen image of `size` pixels, the image is
+  /// filled with the `surface_color` before drawing. Resolve to `None` if the
+  /// shell doesn't support it.
+  fn capture(
+    &mut self, size: Size, surface_color: Color, commands: &[PaintCommand],
+  ) -> BoxFuture<'static, Option<PixelImage>>;
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -286,6 +303,13 @@ impl Window {
       .dispatch_keyboard_input(physical_key, key, is_repeat, location, state);
   }
 
+  pub fn process_modifiers_changed(&self, modifiers: ModifiersState) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .modifiers_changed(modifiers);
+  }
+
   pub fn process_receive_chars(&self, chars: CowArc<str>) {
     self
       .dispatcher
@@ -294,10 +318,14 @@ impl Window {
   }
 
   pub fn process_wheel(&self, delta_x: f32, delta_y: f32) {
+    self.process_wheel_phase(delta_x, delta_y, WheelPhase::Line);
+  }
+
+  pub fn process_wheel_phase(&self, delta_x: f32, delta_y: f32, phase: WheelPhase) {
     self
       .dispatcher
       .borrow_mut()
-      .dispatch_wheel(delta_x, delta_y);
+      .dispatch_wheel(delta_x, delta_y, phase);
   }
 
   pub fn process_cursor_move(&self, position: Point) {
@@ -399,10 +427,16 @@ impl Window {
     let wnd_size = wnd_size.unwrap_or_else(|| self.size());
     let draw = !wnd_size.is_empty() && !self.tree().is_dirty();
     if draw {
-      let root = self.tree().root();
-      let surface = {
-        let _guard = BuildCtx::init_for(root, self.tree);
-        Palette::of(BuildCtx::get()).surface()
+      let surface = self.surface_color();
+
+      let viewport = Rect::from_size(wnd_size);
+      // The delay drop widgets are not tracked by the tree, redraw the whole
+      // window if there are any.
+      let damage = match self.tree().take_damage() {
+        Some(damage) if self.delay_drop_widgets.borrow().is_empty() => {
+          damage.intersection(&viewport).unwrap_or_default()
+        }
+        _ => viewport,
       };
 
       self.tree().draw();
@@ -413,22 +447,83 @@ impl Window {
       let mut painter = self.painter.borrow_mut();
       let cmds = painter.finish();
 
-      shell.draw_commands(wnd_size, Rect::from_size(wnd_size), surface, &cmds);
+      shell.draw_commands(wnd_size, viewport, damage, surface, &cmds);
     }
 
     draw
   }
 
+  /// Render the whole window to an image at the device pixel ratio of the
+  /// window, so the image has the resolution of the screen. It's resolved to
+  /// `None` if the shell doesn't support rendering offscreen. **Note**: this
+  /// function must be called after layout.
+  pub fn capture(&self) -> BoxFuture<'static, Option<PixelImage>> {
+    let root = self.tree().root();
+    let rect = Rect::from_size(self.size());
+    let ratio = self.shell_wnd.borrow().device_pixel_ratio();
+    self.capture_subtree(root, rect, ratio, self.surface_color())
+  }
+
+  /// Render the subtree of the widget `id` to an image scaled by `scale`.
+  ///
+  /// The image covers the box and the visual box of the widget, the area that
+  /// isn't painted is transparent. It's resolved to `None` if the widget isn't
+  /// laid out, or the shell doesn't support rendering offscreen.
+  pub fn capture_widget(&self, id: WidgetId, scale: f32) -> BoxFuture<'static, Option<PixelImage>> {
+    let tree = self.tree();
+    let Some(info) = tree.store.layout_info(id) else { return Box::pin(async { None }) };
+    let Some(size) = info.size else { return Box::pin(async { None }) };
+
+    let mut bounds = Rect::from_size(size);
+    if let Some(visual) = info.visual_box.bounds_rect() {
+      bounds = bounds.union(&visual);
+    }
+    // The widget is painted at its position in the parent.
+    let pos = info.pos;
+    self.capture_subtree(id, bounds.translate(pos.to_vector()), scale, Color::TRANSPARENT)
+  }
+
+  fn capture_subtree(
+    &self, id: WidgetId, bounds: Rect, scale: f32, surface: Color,
+  ) -> BoxFuture<'static, Option<PixelImage>> {
+    let size = (bounds.size * scale).ceil();
+    if size.is_empty() {
+      return Box::pin(async { None });
+    }
+
+    let mut painter = Painter::new(Rect::from_size(size));
+    painter
+      .scale(scale, scale)
+      .translate(-bounds.min_x(), -bounds.min_y());
+    id.paint_subtree(self.tree(), &mut painter);
+    let cmds = painter.finish();
+    self
+      .shell_wnd
+      .borrow_mut()
+      .capture(size, surface, &cmds)
+  }
+
+  fn surface_color(&self) -> Color {
+    let _guard = BuildCtx::init_for(self.tree().root(), self.tree);
+    Palette::of(BuildCtx::get()).surface()
+  }
+
   pub fn layout(&self, size: Size) -> bool {
     let mut layout_queue = Vec::with_capacity(64);
     let mut notified_widgets = ahash::HashSet::default();
     let mut is_need_redraw = false;
+    if self.tree().is_dirty() {
+      self.layout_count.set(0);
+    }
     loop {
       self.run_frame_tasks();
 
       let tree = self.tree_mut();
       is_need_redraw |= tree.is_dirty();
       tree.layout(size, &mut layout_queue);
+      self
+        .layout_count
+        .set(self.layout_count.get() + layout_queue.len());
 
       // Process layout completion events
       layout_queue
@@ -456,6 +551,8 @@ impl Window {
     }
   }
 
+  pub(crate) fn layout_count(&self) -> usize { self.layout_count.get() }
+
   pub fn update_painter_viewport(&self) {
     let size = self.shell_wnd.borrow().inner_size();
     if self.painter.borrow().viewport().size != size {
@@ -463,6 +560,7 @@ impl Window {
       let root = tree.root();
       tree.dirty_marker().mark(root, DirtyPhase::Layout);
       tree.store.remove(root);
+      tree.damage_all();
       let mut painter = self.painter.borrow_mut();
       painter.set_viewport(Rect::from_size(size));
       painter.reset();
@@ -491,6 +589,7 @@ impl Window {
       delay_drop_widgets: <_>::default(),
       flags: Cell::new(flags),
       pre_edit: <_>::default(),
+      layout_count: Cell::new(0),
     };
 
     Sc::new(window)
@@ -725,10 +824,11 @@ impl Window {
           self.top_down_emit(&mut Event::CharsCapture(event), id);
           self.bottom_up_emit(&mut Event::Chars(CharsEvent::new(chars, id, self)), None);
         }
-        DelayEvent::Wheel { id, delta_x, delta_y } => {
-          let event = WheelEvent::new(delta_x, delta_y, self.tree().root(), self);
+        DelayEvent::Wheel { id, delta_x, delta_y, phase } => {
+          let event = WheelEvent::new(delta_x, delta_y, phase, self.tree().root(), self);
           self.top_down_emit(&mut Event::WheelCapture(event), id);
-          self.bottom_up_emit(&mut Event::Wheel(WheelEvent::new(delta_x, delta_y, id, self)), None);
+          let event = WheelEvent::new(delta_x, delta_y, phase, id, self);
+          self.bottom_up_emit(&mut Event::Wheel(event), None);
         }
         DelayEvent::PointerDown(id) => {
           let root = self.tree().root();
@@ -1088,6 +1188,7 @@ pub(crate) enum DelayEvent {
     id: WidgetId,
     delta_x: f32,
     delta_y: f32,
+    phase: WheelPhase,
   },
   PointerDown(WidgetId),
   PointerMove(WidgetId),
@@ -1130,6 +1231,65 @@ mod tests {
   use super::*;
   use crate::{reset_test_env, test_helper::*};
 
+  #[test]
+  fn capture_at_device_pixel_ratio() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @Container { size: Size::new(10., 10.), background: Color::RED }
+        }
+      },
+      Size::new(20., 10.),
+    );
+    wnd.set_device_pixel_ratio(2.);
+    wnd.set_capture_render(|size, surface, commands| {
+      let size = size.to_i32().cast_unit();
+      Some(ribir_dev_helper::wgpu_render_image(commands, size, surface))
+    });
+    wnd.draw_frame();
+
+    let img = futures::executor::block_on(wnd.capture()).unwrap();
+    assert_eq!((img.width(), img.height()), (40, 20));
+    let pixel = |x: usize, y: usize| {
+      let idx = (y * img.width() as usize + x) * 4;
+      &img.pixel_bytes()[idx..idx + 4]
+    };
+    // The container covers the left half of the image.
+    assert_eq!(pixel(18, 15), pixel(0, 0));
+    assert_ne!(pixel(22, 15), pixel(0, 0));
+    assert_eq!(pixel(22, 15), pixel(39, 19));
+  }
+
+  #[test]
+  fn capture_widget() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: Size::new(10., 10.) }
+          @Container { size: Size::new(20., 20.), background: Color::RED }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    let tree = wnd.tree();
+    let multi = tree.root().first_child(tree).unwrap();
+    let container = multi.children(tree).nth(1).unwrap();
+    let _img = wnd.capture_widget(container, 2.);
+
+    let frame = wnd.take_last_capture().unwrap();
+    assert_eq!(frame.viewport, Rect::from_size(Size::new(40., 40.)));
+    assert_eq!(frame.surface, Color::TRANSPARENT);
+    // Only the container is painted, at the origin of the image.
+    let [PaintCommand::Path(cmd)] = &frame.commands[..] else { panic!() };
+    assert_eq!(cmd.transform, Transform::scale(2., 2.));
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn layout_after_wnd_resize() {
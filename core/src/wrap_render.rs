@@ -20,6 +20,10 @@ pub trait WrapRender {
 
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) { host.paint(ctx) }
 
+  fn baseline(&self, kind: BaselineKind, host: &dyn Render, ctx: &mut LayoutCtx) -> Option<f32> {
+    host.baseline(kind, ctx)
+  }
+
   fn size_affected_by_child(&self, host: &dyn Render) -> bool {
     // Detected by its host by default, so we return true here.
     host.size_affected_by_child()
@@ -104,6 +108,12 @@ impl Render for RenderPair {
 
   fn paint(&self, ctx: &mut PaintingCtx) { self.wrapper.paint(self.host.as_render(), ctx); }
 
+  fn baseline(&self, kind: BaselineKind, ctx: &mut LayoutCtx) -> Option<f32> {
+    self
+      .wrapper
+      .baseline(kind, self.host.as_render(), ctx)
+  }
+
   fn size_affected_by_child(&self) -> bool {
     self
       .wrapper
@@ -132,6 +142,10 @@ where
 
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) { self.read().paint(host, ctx) }
 
+  fn baseline(&self, kind: BaselineKind, host: &dyn Render, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.read().baseline(kind, host, ctx)
+  }
+
   fn size_affected_by_child(&self, host: &dyn Render) -> bool {
     self.read().size_affected_by_child(host)
   }
@@ -179,19 +179,31 @@ pub fn assert_texture_eq_png(test_img: PixelImage, ref_path: &std::path::Path) {
 pub fn wgpu_render_commands(
   commands: &[ribir_painter::PaintCommand], viewport: ribir_geom::DeviceRect,
   surface: ribir_painter::Color,
+) -> PixelImage {
+  use ribir_geom::DeviceSize;
+
+  let size = DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2);
+  wgpu_render_image(commands, size, surface)
+}
+
+/// Render the commands by wgpu backend to an image of `size` pixels, the
+/// image is filled with the `surface` color before drawing.
+pub fn wgpu_render_image(
+  commands: &[ribir_painter::PaintCommand], size: ribir_geom::DeviceSize,
+  surface: ribir_painter::Color,
 ) -> PixelImage {
   use futures::executor::block_on;
-  use ribir_geom::{DeviceRect, DeviceSize};
-  use ribir_gpu::{GPUBackend, GPUBackendImpl, Texture, WgpuImpl};
+  use ribir_geom::DeviceRect;
+  use ribir_gpu::{GPUBackend, GPUBackendImpl, WgpuImpl};
   use ribir_painter::PainterBackend;
 
   let draw_img = |backend: &mut GPUBackend<WgpuImpl>| {
-    let rect = DeviceRect::from_size(DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2));
+    let rect = DeviceRect::from_size(size);
     let mut texture = backend
       .get_impl_mut()
       .new_texture(rect.size, ColorFormat::Rgba8);
     backend.begin_frame(surface);
-    backend.draw_commands(rect, commands, &Transform::identity(), &mut texture);
+    backend.draw_commands(rect, rect, commands, &Transform::identity(), &mut texture);
     let img = texture.copy_as_image(&rect, backend.get_impl_mut());
     backend.end_frame();
     block_on(img).unwrap()
@@ -82,7 +82,7 @@ macro_rules! assert_widget_eq_image {
     let mut wnd = $widget_tester.create_wnd();
     wnd.0.draw_frame(None);
 
-    let Frame { commands, viewport, surface } = wnd.take_last_frame().unwrap();
+    let Frame { commands, viewport, surface, .. } = wnd.take_last_frame().unwrap();
     let viewport = viewport.to_i32().cast_unit();
     let img = $crate::wgpu_render_commands(&commands, viewport, surface);
 
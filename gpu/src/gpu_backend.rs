@@ -2,11 +2,11 @@ use std::error::Error;
 
 use guillotiere::euclid::Vector2D;
 use ribir_geom::{
-  DeviceRect, DeviceSize, Point, Transform, rect_corners, transform_to_device_rect,
+  DeviceRect, DeviceSize, Point, Rect, Transform, rect_corners, transform_to_device_rect,
 };
 use ribir_painter::{
   Color, ColorMatrix, CommandBrush, PaintCommand, PaintPath, PaintPathAction, PainterBackend,
-  PaintingStyle, PathCommand, PixelImage, Vertex, VertexBuffers, color::ColorFilterMatrix,
+  PaintingStyle, Path, PathCommand, PixelImage, Vertex, VertexBuffers, color::ColorFilterMatrix,
   image::ColorFormat,
 };
 
@@ -97,16 +97,29 @@ where
   }
 
   fn draw_commands(
-    &mut self, viewport: DeviceRect, commands: &[PaintCommand], global_matrix: &Transform,
-    output: &mut Self::Texture,
+    &mut self, viewport: DeviceRect, damage: DeviceRect, commands: &[PaintCommand],
+    global_matrix: &Transform, output: &mut Self::Texture,
   ) {
     let clips = self.clip_layer_stack.len();
     self.viewport = viewport;
     self.begin_draw_phase();
     let output_size = output.size();
+    // A translucent surface color can't cover the old content, so redraw the
+    // whole viewport.
+    let partial = damage != viewport
+      && self
+        .surface_color
+        .is_none_or(|c| c.alpha == u8::MAX);
+    if partial {
+      let damage = damage.intersection(&viewport).unwrap_or_default();
+      self.clip_to_damage(damage, output_size);
+    }
     for cmd in commands {
       self.draw_command(cmd, global_matrix, output_size, output);
     }
+    if partial {
+      self.clip_layer_stack.pop();
+    }
     self.draw_triangles(output);
     self.end_draw_phase();
 
@@ -297,7 +310,7 @@ where
             let matrix = Transform::translation(bounds.origin.x, bounds.origin.y)
               .then_scale(scale, scale)
               .then_translate(slice.origin.to_f32().cast_unit().to_vector());
-            this.draw_commands(*slice, cmds, &matrix, tex);
+            this.draw_commands(*slice, *slice, cmds, &matrix, tex);
 
             // restore the clip layer and viewport
             self.clip_layer_stack.pop();
@@ -327,6 +340,9 @@ where
           .clip_layer_stack
           .last()
           .map_or(-1, |l| l.mask_head);
+        let Some((points, mask_head)) = self.clip_quad_to_viewport(points, mask_head) else {
+          return;
+        };
         self.draw_img_slice(
           slice,
           &view_to_slice,
@@ -483,6 +499,47 @@ where
       .map_or(&self.viewport, |l| &l.viewport)
   }
 
+  /// Clip all the drawing to the `damage` area, and clear it with the surface
+  /// color, the content outside the `damage` is kept.
+  fn clip_to_damage(&mut self, damage: DeviceRect, output_tex_size: DeviceSize) {
+    // The paths are always clipped by the viewport, so it needn't a mask.
+    self
+      .clip_layer_stack
+      .push(ClipLayer { viewport: damage, mask_head: -1 });
+
+    if let Some(color) = self.surface_color.take() {
+      let color_attr = ColorAttr { color: color.into_components(), mask_head: -1 };
+      let rect = rect_corners(&damage.to_f32().cast_unit());
+      add_rect_vertices(rect, output_tex_size, color_attr, &mut self.color_vertices_buffer);
+      self.current_phase = CurrentPhase::Color;
+    }
+  }
+
+  /// Clip a quad that isn't clipped by a mask to the viewport. Return `None` if
+  /// it's invisible.
+  fn clip_quad_to_viewport(
+    &mut self, quad: [Point; 4], mask_head: i32,
+  ) -> Option<([Point; 4], i32)> {
+    let viewport = self.viewport().to_f32().cast_unit();
+    let bounds = Rect::from_points(quad);
+    if mask_head != -1 || viewport.contains_rect(&bounds) {
+      return Some((quad, mask_head));
+    }
+
+    let [lt, rt, _, lb] = quad;
+    if lt.y == rt.y && lt.x == lb.x {
+      let rect = bounds.intersection(&viewport)?;
+      Some((rect_corners(&rect), mask_head))
+    } else {
+      // A transformed quad, clip it by a mask of the viewport.
+      let view = *self.viewport();
+      let path = PaintPath::Own(Path::rect(&viewport));
+      let (_, mask_head) =
+        self.new_mask_layer(&view, &Transform::identity(), &path, &PaintingStyle::Fill);
+      Some((quad, mask_head))
+    }
+  }
+
   fn new_mask_layer(
     &mut self, view: &DeviceRect, matrix: &Transform, path: &PaintPath, style: &PaintingStyle,
   ) -> ([Point; 4], i32) {
@@ -826,4 +883,30 @@ mod tests {
     painter
   }
   painter_backend_eq_image_test!(draw_bundle_svg, comparison = 0.001);
+
+  #[test]
+  fn keep_content_outside_damage() {
+    let mut backend = GPUBackend::new(futures::executor::block_on(crate::WgpuImpl::headless()));
+    let viewport = DeviceRect::from_size(DeviceSize::new(32, 32));
+    let mut texture = backend
+      .get_impl_mut()
+      .new_texture(viewport.size, ColorFormat::Rgba8);
+
+    let mut draw = |surface, damage| {
+      backend.begin_frame(surface);
+      backend.draw_commands(viewport, damage, &[], &Transform::identity(), &mut texture);
+      let img = texture.copy_as_image(&viewport, backend.get_impl_mut());
+      backend.end_frame();
+      futures::executor::block_on(img).unwrap()
+    };
+
+    draw(Color::RED, viewport);
+    let img = draw(Color::BLUE, DeviceRect::from_size(DeviceSize::new(16, 32)));
+    let pixel = |x: usize, y: usize| {
+      let idx = (y * 32 + x) * 4;
+      &img.pixel_bytes()[idx..idx + 4]
+    };
+    assert_eq!(pixel(8, 8), Color::BLUE.into_components());
+    assert_eq!(pixel(24, 8), Color::RED.into_components());
+  }
 }
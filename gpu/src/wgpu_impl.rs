@@ -314,7 +314,12 @@ impl GPUBackendImpl for WgpuImpl {
     &mut self, dist_tex: &mut Self::Texture, dist_pos: DevicePoint, from_tex: &Self::Texture,
     from_rect: &DeviceRect,
   ) {
-    if dist_tex.format() == from_tex.format() {
+    let copyable = dist_tex
+      .inner_tex
+      .texture()
+      .usage()
+      .contains(wgpu::TextureUsages::COPY_DST);
+    if copyable && dist_tex.format() == from_tex.format() {
       self.copy_same_format_texture(
         dist_tex.inner_tex.texture(),
         dist_pos,
@@ -388,6 +393,74 @@ impl InnerTexture {
 impl WgpuTexture {
   fn from_tex(tex: wgpu::Texture) -> Self { Self::new(InnerTexture::Texture(tex)) }
 
+  /// Return an image future of the texture area. Unlike the
+  /// [`Texture::copy_as_image`], the future doesn't borrow the texture.
+  pub fn copy_as_image(
+    &self, rect: &DeviceRect, backend: &mut WgpuImpl,
+  ) -> impl std::future::Future<Output = Result<PixelImage, Box<dyn Error>>> + 'static {
+    let width = rect.width();
+    let height = rect.height();
+    let format = self.color_format();
+    let pixel_bytes = format.bytes_per_pixel();
+    let align_width = align(width as u32, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT / pixel_bytes as u32);
+    let padded_row_bytes = pixel_bytes as u32 * align_width;
+
+    // The output buffer lets us retrieve the data as an array
+    let buffer = backend
+      .device
+      .create_buffer(&wgpu::BufferDescriptor {
+        size: padded_row_bytes as u64 * height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+        label: None,
+      });
+
+    let origin = wgpu::Origin3d { x: rect.min_x() as u32, y: rect.min_y() as u32, z: 0 };
+
+    let encoder = command_encoder!(backend);
+
+    encoder.copy_texture_to_buffer(
+      wgpu::TexelCopyTextureInfo {
+        texture: self.inner_tex.texture(),
+        mip_level: 0,
+        origin,
+        aspect: wgpu::TextureAspect::All,
+      },
+      wgpu::TexelCopyBufferInfo {
+        buffer: &buffer,
+        layout: wgpu::TexelCopyBufferLayout {
+          offset: 0,
+          bytes_per_row: Some(padded_row_bytes),
+          rows_per_image: Some(height as u32),
+        },
+      },
+      wgpu::Extent3d { width: width as u32, height: height as u32, depth_or_array_layers: 1 },
+    );
+
+    backend.submit();
+
+    let (sender, receiver) = oneshot::channel();
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+
+    async move {
+      let _ = receiver.await?;
+
+      let row_bytes = width as usize * pixel_bytes as usize;
+      let mut data = vec![0; row_bytes * height as usize];
+
+      let slice = buffer.slice(..).get_mapped_range();
+      (0..height as usize).for_each(|r| {
+        let padded_start = r * padded_row_bytes as usize;
+        let row_start = r * row_bytes;
+        data[row_start..row_start + row_bytes]
+          .copy_from_slice(&slice[padded_start..padded_start + row_bytes]);
+      });
+
+      Ok(PixelImage::new(data.into(), width as u32, height as u32, format))
+    }
+  }
+
   pub(crate) fn color_attachments(
     &self, clear: Option<Color>,
   ) -> wgpu::RenderPassColorAttachment<'_> {
@@ -456,67 +529,7 @@ impl Texture for WgpuTexture {
   fn copy_as_image(
     &self, rect: &DeviceRect, backend: &mut Self::Host,
   ) -> impl std::future::Future<Output = Result<PixelImage, Box<dyn Error>>> + 'static {
-    let width = rect.width();
-    let height = rect.height();
-    let format = self.color_format();
-    let pixel_bytes = format.bytes_per_pixel();
-    let align_width = align(width as u32, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT / pixel_bytes as u32);
-    let padded_row_bytes = pixel_bytes as u32 * align_width;
-
-    // The output buffer lets us retrieve the data as an array
-    let buffer = backend
-      .device
-      .create_buffer(&wgpu::BufferDescriptor {
-        size: padded_row_bytes as u64 * height as u64,
-        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-        mapped_at_creation: false,
-        label: None,
-      });
-
-    let origin = wgpu::Origin3d { x: rect.min_x() as u32, y: rect.min_y() as u32, z: 0 };
-
-    let encoder = command_encoder!(backend);
-
-    encoder.copy_texture_to_buffer(
-      wgpu::TexelCopyTextureInfo {
-        texture: self.inner_tex.texture(),
-        mip_level: 0,
-        origin,
-        aspect: wgpu::TextureAspect::All,
-      },
-      wgpu::TexelCopyBufferInfo {
-        buffer: &buffer,
-        layout: wgpu::TexelCopyBufferLayout {
-          offset: 0,
-          bytes_per_row: Some(padded_row_bytes),
-          rows_per_image: Some(height as u32),
-        },
-      },
-      wgpu::Extent3d { width: width as u32, height: height as u32, depth_or_array_layers: 1 },
-    );
-
-    backend.submit();
-
-    let (sender, receiver) = oneshot::channel();
-    let slice = buffer.slice(..);
-    slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
-
-    async move {
-      let _ = receiver.await?;
-
-      let row_bytes = width as usize * pixel_bytes as usize;
-      let mut data = vec![0; row_bytes * height as usize];
-
-      let slice = buffer.slice(..).get_mapped_range();
-      (0..height as usize).for_each(|r| {
-        let padded_start = r * padded_row_bytes as usize;
-        let row_start = r * row_bytes;
-        data[row_start..row_start + row_bytes]
-          .copy_from_slice(&slice[padded_start..padded_start + row_bytes]);
-      });
-
-      Ok(PixelImage::new(data.into(), width as u32, height as u32, format))
-    }
+    WgpuTexture::copy_as_image(self, rect, backend)
   }
 
   fn color_format(&self) -> ColorFormat {
@@ -648,15 +661,18 @@ impl WgpuImpl {
 
     let surface = surface.map(|surface| {
       use wgpu::TextureFormat::*;
-      let format = surface
-        .get_capabilities(&adapter)
+      let capabilities = surface.get_capabilities(&adapter);
+      let format = capabilities
         .formats
         .into_iter()
         .find(|&f| f == Rgba8Unorm || f == Bgra8Unorm)
         .expect("No suitable format found for the surface!");
+      // Allow to copy a frame to the surface directly.
+      let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+        | (capabilities.usages & wgpu::TextureUsages::COPY_DST);
 
       let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        usage,
         format,
         width: 0,
         height: 0,
@@ -48,13 +48,17 @@ pub trait PainterBackend {
   /// Paint `commands` to the `output` Texture.  This may be called more than
   /// once during a frame.
   ///
+  /// Only the `damage` area of the `viewport` is painted, the content of the
+  /// `output` outside it is kept. Pass the `viewport` as the `damage` to paint
+  /// the whole viewport.
+  ///
   /// ## Undefined Behavior
   ///
   /// You should guarantee the output be same one in the same frame, otherwise
   /// it may cause undefined behavior.
   fn draw_commands(
-    &mut self, viewport: DeviceRect, commands: &[PaintCommand], global_matrix: &Transform,
-    output: &mut Self::Texture,
+    &mut self, viewport: DeviceRect, damage: DeviceRect, commands: &[PaintCommand],
+    global_matrix: &Transform, output: &mut Self::Texture,
   );
   /// A frame end.
   fn end_frame(&mut self);
@@ -314,6 +318,27 @@ impl Painter {
   /// - Preserves both painters' path builders and state stacks
   pub fn merge(&mut self, forked: &mut Painter) { self.commands.append(forked.finish().0); }
 
+  /// Creates a painter to record the commands of a bundle.
+  ///
+  /// The recorded painter keeps the brushes and the stroke options of the
+  /// current state, but draws in the local axis of the current state without
+  /// the color filter, and only the `bounds` is visible. So the commands can be
+  /// drawn by [`Painter::draw_bundle_commands`] with the same `bounds` in any
+  /// place, and the backend can cache them.
+  pub fn fork_bundle(&self, bounds: Rect) -> Self {
+    let mut init_state = self.current_state().clone();
+    init_state.transform = Transform::identity();
+    init_state.color_filter = ColorMatrix::default();
+    init_state.clip_cnt = 0;
+    init_state.bounds = bounds;
+    Painter {
+      state_stack: vec![init_state.clone()],
+      init_state,
+      commands: vec![],
+      path_builder: Path::builder(),
+    }
+  }
+
   /// Change the default brush and text style of the painter, and then reset
   /// the painter state.
   pub fn set_init_state(&mut self, brush: Brush) {
@@ -786,8 +811,19 @@ impl Painter {
     let unit = face.units_per_em() as f32;
     let matrix = *self.transform();
 
-    let bounds = g.bounds();
-    if let Some(svg) = face.glyph_svg_image(g.glyph_id) {
+    let mut bounds = g.bounds();
+    if g.rotated {
+      // Rotate the em box 90° clockwise around its right top corner, and then
+      // draw the glyph as a horizontal one.
+      self
+        .translate(bounds.min_x() + font_size, bounds.min_y())
+        .apply_transform(&Transform::new(0., 1., -1., 0., 0., 0.));
+      bounds = Rect::from_size(Size::new(bounds.height(), font_size));
+    }
+    if let Some(svg) = face
+      .glyph_svg_image(g.glyph_id)
+      .or_else(|| face.glyph_colr_image(g.glyph_id))
+    {
       let grid_scale = face
         .vertical_height()
         .map(|h| h as f32 / face.units_per_em() as f32)
@@ -1119,6 +1155,32 @@ mod test {
     assert_eq!(painter.current_state().clip_cnt, 0);
   }
 
+  #[test]
+  fn fork_bundle_local_axis() {
+    let mut painter = painter();
+    painter
+      .translate(100., 100.)
+      .apply_alpha(0.5)
+      .set_fill_brush(Color::RED);
+
+    let mut bundle = painter.fork_bundle(rect(0., 0., 20., 20.));
+    assert_eq!(bundle.transform(), &Transform::identity());
+    assert_eq!(bundle.fill_brush(), &Brush::Color(Color::RED));
+    assert!(!bundle.is_transparent());
+
+    // Out of the bundle bounds.
+    bundle.rect(&rect(30., 30., 10., 10.)).fill();
+    assert_eq!(bundle.commands.len(), 0);
+    bundle.rect(&rect(0., 0., 10., 10.)).fill();
+    let cmds = bundle.finish();
+    let PaintCommand::Path(PathCommand { action: PaintPathAction::Paint { brush, .. }, .. }) =
+      &cmds[0]
+    else {
+      panic!("should be a paint path");
+    };
+    assert!(matches!(brush, CommandBrush::Color(c) if *c == Color::RED));
+  }
+
   #[test]
   fn filter_invalid_clip() {
     let mut painter = painter();
@@ -37,7 +37,7 @@ struct StaticSvg {
 impl Svg {
   // FIXME: This is a temporary workaround. Utilize the magic color for the SVG,
   // and replace it with the actual color when rendering.
-  const DYNAMIC_COLOR: Color = Color::from_u32(0x191B1901);
+  pub(crate) const DYNAMIC_COLOR: Color = Color::from_u32(0x191B1901);
   const DYNAMIC_COLOR_STR: &'static str = "#191B1901";
 
   /// Parse SVG from bytes.
@@ -67,15 +67,20 @@ impl Svg {
     paint_group(tree.root(), &mut painter);
 
     let paint_commands = painter.finish().to_owned().into_boxed_slice();
-    let (used_fill_fallback, used_stroke_fallback) = fallback_color_check(&paint_commands);
+    Ok(Svg::from_commands(Size::new(size.width(), size.height()), paint_commands))
+  }
 
-    Ok(Svg {
-      size: Size::new(size.width(), size.height()),
-      commands: Resource::new(paint_commands),
+  /// Create an SVG from the paint commands, the commands that paint with
+  /// [`Svg::DYNAMIC_COLOR`] will inherit the color from the environment.
+  pub(crate) fn from_commands(size: Size, commands: Box<[PaintCommand]>) -> Self {
+    let (used_fill_fallback, used_stroke_fallback) = fallback_color_check(&commands);
+    Svg {
+      size,
+      commands: Resource::new(commands),
       inherited_fill: used_fill_fallback,
       inherited_stroke: used_stroke_fallback,
       last: RefCell::new(None),
-    })
+    }
   }
 
   /// Parse SVG from a file.
@@ -12,11 +12,16 @@ pub use fontdb::{ID, Stretch as FontStretch, Style as FontStyle, Weight as FontW
 pub use ribir_algo::Substr;
 use ribir_geom::{Rect, rect};
 use rustybuzz::{GlyphPosition, ttf_parser::GlyphId};
+use typography::PlaceLineDirection;
 pub mod text_reorder;
 pub mod typography;
 pub use text_reorder::TextReorder;
+pub mod hyphenation;
+pub mod line_break;
+pub use hyphenation::Hyphenator;
 mod typography_store;
 pub use typography_store::{TypographyStore, VisualGlyphs};
+mod colr_glyph_cache;
 mod svg_glyph_cache;
 
 // Enum value descriptions are from the CSS spec.
@@ -83,6 +88,56 @@ pub struct TextStyle {
   pub line_height: f32,
   /// How to handle the visual overflow.
   pub overflow: TextOverflow,
+  /// Where the lines are allowed to break when auto wrap.
+  pub word_break: WordBreak,
+  /// Whether to break a word that can't fit in a line by itself.
+  pub overflow_wrap: OverflowWrap,
+  /// How words should be hyphenated when text wraps across multiple lines.
+  pub hyphens: Hyphens,
+  /// The language of the text as a BCP 47 tag, like `en-US`, used to pick the
+  /// hyphenation dictionary.
+  pub lang: Option<Cow<'static, str>>,
+  /// The OpenType features to enable or disable when shaping the text.
+  pub font_features: Box<[FontFeature]>,
+  /// The values of the variation axes of the variable fonts. The `wght` axis
+  /// follows the weight of the font face if it's not specified here.
+  pub font_variations: Box<[FontVariation]>,
+  /// The index of the palette in the `CPAL` table to paint the color glyphs,
+  /// the first palette `0` by default.
+  pub font_palette: u16,
+  /// Whether the text is set horizontally or vertically.
+  pub writing_mode: WritingMode,
+  /// The orientation of the characters in the vertical lines.
+  pub text_orientation: TextOrientation,
+  /// Whether the white spaces are preserved and the lines are allowed to wrap.
+  pub white_space: WhiteSpace,
+  /// The width of the tab characters.
+  pub tab_size: TabSize,
+  /// The explicit tab stops in logical pixels from the line start, in
+  /// ascending order. A tab advances to the first stop after it, and beyond
+  /// the stops, by the `tab_size`.
+  pub tab_stops: Box<[f32]>,
+}
+
+/// An OpenType feature setting, the
+/// [font-feature-settings](https://www.w3.org/TR/css-fonts-4/#font-feature-settings-prop) in CSS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontFeature {
+  /// The four-letter tag of the feature, like `liga`, `tnum` or `ss01`.
+  pub tag: [u8; 4],
+  /// `0` disables the feature and `1` enables it. The features that select
+  /// from alternates use it as the index of the alternate.
+  pub value: u32,
+}
+
+/// A value of a variation axis of the variable fonts, the
+/// [font-variation-settings](https://www.w3.org/TR/css-fonts-4/#font-variation-settings-def) in CSS.
+#[derive(Clone, Copy, Debug)]
+pub struct FontVariation {
+  /// The four-letter tag of the axis, like `wght`, `wdth`, `opsz` or a custom
+  /// axis.
+  pub tag: [u8; 4],
+  pub value: f32,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
@@ -92,10 +147,142 @@ pub enum TextOverflow {
   AutoWrap,
 }
 
+impl FontFeature {
+  #[inline]
+  pub const fn new(tag: &[u8; 4], value: u32) -> Self { Self { tag: *tag, value } }
+
+  /// Enable the feature.
+  #[inline]
+  pub const fn on(tag: &[u8; 4]) -> Self { Self::new(tag, 1) }
+
+  /// Disable the feature.
+  #[inline]
+  pub const fn off(tag: &[u8; 4]) -> Self { Self::new(tag, 0) }
+}
+
+impl FontVariation {
+  #[inline]
+  pub const fn new(tag: &[u8; 4], value: f32) -> Self { Self { tag: *tag, value } }
+}
+
+impl PartialEq for FontVariation {
+  fn eq(&self, other: &Self) -> bool {
+    self.tag == other.tag && self.value.to_bits() == other.value.to_bits()
+  }
+}
+
+impl Eq for FontVariation {}
+
+impl Hash for FontVariation {
+  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    self.tag.hash(state);
+    self.value.to_bits().hash(state);
+  }
+}
+
 impl TextOverflow {
   fn is_auto_wrap(&self) -> bool { matches!(self, TextOverflow::AutoWrap) }
 }
 
+/// The line break opportunities between letters, the
+/// [word-break](https://www.w3.org/TR/css-text-3/#word-break-property) in CSS.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum WordBreak {
+  /// Break by the Unicode Line Breaking Algorithm, between words for most of
+  /// the scripts and between characters for CJK.
+  #[default]
+  Normal,
+  /// Breaking is allowed between any two letters, except the punctuation rules.
+  BreakAll,
+  /// Breaking is forbidden within CJK words, like the other scripts.
+  KeepAll,
+}
+
+/// Whether to break a unbreakable word when it can't fit in a line, the
+/// [overflow-wrap](https://www.w3.org/TR/css-text-3/#overflow-wrap-property) in CSS.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum OverflowWrap {
+  /// Only break at the allowed break points, a long word may overflow the line.
+  Normal,
+  /// A long word will be broken at an arbitrary point if there are no other
+  /// acceptable break points in the line.
+  #[default]
+  Anywhere,
+}
+
+/// How words should be hyphenated, the
+/// [hyphens](https://www.w3.org/TR/css-text-3/#hyphens-property) in CSS.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Hyphens {
+  /// Words are not hyphenated, even at the soft hyphens.
+  None,
+  /// Words are only hyphenated at the soft hyphens (`U+00AD`).
+  #[default]
+  Manual,
+  /// Words are hyphenated at the soft hyphens and the points found by the
+  /// hyphenator registered for the language of the text.
+  Auto,
+}
+
+/// The direction the lines are laid out, the
+/// [writing-mode](https://www.w3.org/TR/css-writing-modes-4/#block-flow) in CSS.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum WritingMode {
+  /// The text is set horizontally, and the lines are placed from top to
+  /// bottom.
+  #[default]
+  HorizontalTb,
+  /// The text is set vertically, and the lines are placed from right to left.
+  VerticalRl,
+  /// The text is set vertically, and the lines are placed from left to right.
+  VerticalLr,
+}
+
+/// The orientation of the characters in a vertical line, the
+/// [text-orientation](https://www.w3.org/TR/css-writing-modes-4/#text-orientation) in CSS.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum TextOrientation {
+  /// The characters of the CJK scripts are set upright, and the others are
+  /// rotated 90° clockwise.
+  #[default]
+  Mixed,
+  /// All the characters are set upright.
+  Upright,
+  /// All the characters are rotated 90° clockwise.
+  Sideways,
+}
+
+/// How the white spaces are handled and whether the lines wrap, the
+/// [white-space](https://www.w3.org/TR/css-text-3/#white-space-property) in CSS.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum WhiteSpace {
+  /// Sequences of spaces and tabs are collapsed into one space, and the spaces
+  /// at the beginning of a line are removed. The lines wrap by the
+  /// [`TextOverflow`], and the line breaks are kept.
+  Normal,
+  /// The spaces are collapsed like `Normal`, but the lines never wrap.
+  Nowrap,
+  /// The white spaces are preserved, and the lines only break at the line
+  /// breaks.
+  Pre,
+  /// The white spaces are preserved, and the lines wrap by the
+  /// [`TextOverflow`].
+  #[default]
+  PreWrap,
+}
+
+/// The width of the tab characters, a tab advances to the next multiple of it
+/// from the line start. The
+/// [tab-size](https://www.w3.org/TR/css-text-3/#tab-size-property) in CSS.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TabSize {
+  /// The width of the number of the space characters, include the letter
+  /// space.
+  Spaces(u32),
+  /// The width in logical pixels.
+  Pixels(f32),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Glyph {
   /// The font face id of the glyph.
@@ -116,6 +303,9 @@ pub struct Glyph {
   pub glyph_id: GlyphId,
   /// An cluster of origin text as byte index.
   pub cluster: u32,
+  /// If the glyph is rotated 90° clockwise in a vertical line, its advance and
+  /// offsets are in the rotated coordinate.
+  pub rotated: bool,
 }
 
 #[derive(
@@ -144,6 +334,10 @@ pub enum TextAlign {
   Start,
   Center,
   End,
+  /// Stretch the lines to fill the line width by distributing the extra space
+  /// to the gaps between words, or between characters for CJK. The last line
+  /// of a paragraph is aligned to the start.
+  Justify,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -170,6 +364,34 @@ impl TextDirection {
   }
 }
 
+impl WhiteSpace {
+  /// Whether the sequences of spaces are collapsed.
+  #[inline]
+  pub fn is_collapse(&self) -> bool { matches!(self, WhiteSpace::Normal | WhiteSpace::Nowrap) }
+
+  /// Whether the lines never wrap.
+  #[inline]
+  pub fn is_nowrap(&self) -> bool { matches!(self, WhiteSpace::Nowrap | WhiteSpace::Pre) }
+}
+
+impl Default for TabSize {
+  fn default() -> Self { TabSize::Spaces(8) }
+}
+
+impl WritingMode {
+  #[inline]
+  pub fn is_vertical(&self) -> bool { !matches!(self, WritingMode::HorizontalTb) }
+
+  /// The direction to place the lines in this writing mode.
+  pub fn line_dir(&self) -> PlaceLineDirection {
+    match self {
+      WritingMode::HorizontalTb => PlaceLineDirection::TopToBottom,
+      WritingMode::VerticalRl => PlaceLineDirection::RightToLeft,
+      WritingMode::VerticalLr => PlaceLineDirection::LeftToRight,
+    }
+  }
+}
+
 impl GlyphUnit {
   /// Specifies the standard units per EM of the glyph.
   pub const UNITS_PER_EM: u16 = 16384;
@@ -220,6 +442,68 @@ impl TextStyle {
   /// Sets the text overflow of the text style.
   #[inline]
   pub fn with_overflow(self, overflow: TextOverflow) -> Self { Self { overflow, ..self } }
+
+  /// Sets the word break of the text style.
+  #[inline]
+  pub fn with_word_break(self, word_break: WordBreak) -> Self { Self { word_break, ..self } }
+
+  /// Sets the overflow wrap of the text style.
+  #[inline]
+  pub fn with_overflow_wrap(self, overflow_wrap: OverflowWrap) -> Self {
+    Self { overflow_wrap, ..self }
+  }
+
+  /// Sets the hyphens of the text style.
+  #[inline]
+  pub fn with_hyphens(self, hyphens: Hyphens) -> Self { Self { hyphens, ..self } }
+
+  /// Sets the language of the text style.
+  #[inline]
+  pub fn with_lang(self, lang: impl Into<Cow<'static, str>>) -> Self {
+    Self { lang: Some(lang.into()), ..self }
+  }
+
+  /// Sets the OpenType features of the text style.
+  #[inline]
+  pub fn with_font_features(self, font_features: impl Into<Box<[FontFeature]>>) -> Self {
+    Self { font_features: font_features.into(), ..self }
+  }
+
+  /// Sets the variation axis values of the text style.
+  #[inline]
+  pub fn with_font_variations(self, font_variations: impl Into<Box<[FontVariation]>>) -> Self {
+    Self { font_variations: font_variations.into(), ..self }
+  }
+
+  /// Sets the palette of the color glyphs of the text style.
+  #[inline]
+  pub fn with_font_palette(self, font_palette: u16) -> Self { Self { font_palette, ..self } }
+
+  /// Sets the writing mode of the text style.
+  #[inline]
+  pub fn with_writing_mode(self, writing_mode: WritingMode) -> Self {
+    Self { writing_mode, ..self }
+  }
+
+  /// Sets the orientation of the characters in the vertical lines.
+  #[inline]
+  pub fn with_text_orientation(self, text_orientation: TextOrientation) -> Self {
+    Self { text_orientation, ..self }
+  }
+
+  /// Sets how the white spaces are handled of the text style.
+  #[inline]
+  pub fn with_white_space(self, white_space: WhiteSpace) -> Self { Self { white_space, ..self } }
+
+  /// Sets the width of the tab characters of the text style.
+  #[inline]
+  pub fn with_tab_size(self, tab_size: TabSize) -> Self { Self { tab_size, ..self } }
+
+  /// Sets the explicit tab stops of the text style.
+  #[inline]
+  pub fn with_tab_stops(self, tab_stops: impl Into<Box<[f32]>>) -> Self {
+    Self { tab_stops: tab_stops.into(), ..self }
+  }
 }
 
 impl Glyph {
@@ -233,6 +517,7 @@ impl Glyph {
       y_offset: cast(pos.y_offset, scale),
       glyph_id,
       cluster,
+      rotated: false,
     }
   }
   pub fn is_miss(&self) -> bool { self.glyph_id.0 == 0 }
@@ -276,6 +561,18 @@ impl Default for TextStyle {
       letter_space: 0.,
       line_height: 16.,
       overflow: <_>::default(),
+      word_break: <_>::default(),
+      overflow_wrap: <_>::default(),
+      hyphens: <_>::default(),
+      lang: None,
+      font_features: <_>::default(),
+      font_variations: <_>::default(),
+      font_palette: 0,
+      writing_mode: <_>::default(),
+      text_orientation: <_>::default(),
+      white_space: <_>::default(),
+      tab_size: <_>::default(),
+      tab_stops: <_>::default(),
     }
   }
 }
@@ -0,0 +1,317 @@
+use ahash::HashMap;
+use ribir_algo::Resource;
+use ribir_geom::{Point, Rect, Size, Transform, Vector};
+use rustybuzz::ttf_parser::{
+  GlyphId, NormalizedCoordinate, RgbaColor,
+  colr::{self, ClipBox, CompositeMode, GradientExtend},
+};
+
+use crate::{
+  Brush, Color, GradientStop, Painter, Path, SpreadMethod, Svg,
+  color::{LinearGradient, RadialGradient},
+  text::font_db::Face,
+};
+
+/// Cache the color glyphs of the `COLR` table, the layers of a glyph are
+/// painted to an [`Svg`] once.
+#[derive(Default)]
+pub struct ColrGlyphCache {
+  colr_glyphs: HashMap<GlyphId, Option<Svg>>,
+}
+
+impl ColrGlyphCache {
+  pub fn svg_or_insert(&mut self, glyph_id: GlyphId, face: &Face) -> &Option<Svg> {
+    self
+      .colr_glyphs
+      .entry(glyph_id)
+      .or_insert_with(|| colr_glyph_svg(glyph_id, face))
+  }
+}
+
+/// Paint the layers of the color glyph to an [`Svg`], which has the same
+/// coordinate system as the glyphs of the `SVG` table: the viewport is an em
+/// square and the baseline is at the ascender.
+fn colr_glyph_svg(glyph_id: GlyphId, face: &Face) -> Option<Svg> {
+  let rb_face = &face.rb_face;
+  if !rb_face.is_color_glyph(glyph_id) {
+    return None;
+  }
+
+  let unit = face.units_per_em() as f32;
+  let mut painter = Painter::new(Rect::from_size(Size::new(f32::MAX, f32::MAX)));
+  // The font units are y-up, flip them to the y-down painter coordinate.
+  painter.set_transform(Transform::new(1., 0., 0., -1., 0., face.ascender() as f32));
+  let mut colr_painter = ColrPainter::new(face, rb_face.variation_coordinates(), &mut painter);
+  let foreground = Svg::DYNAMIC_COLOR;
+  let foreground =
+    RgbaColor::new(foreground.red, foreground.green, foreground.blue, foreground.alpha);
+  rb_face.paint_color_glyph(glyph_id, face.palette(), foreground, &mut colr_painter)?;
+
+  let commands = painter.finish().to_owned().into_boxed_slice();
+  Some(Svg::from_commands(Size::new(unit, unit), commands))
+}
+
+/// Convert the paint operations of the `COLR` table to the commands of the
+/// [`Painter`].
+struct ColrPainter<'a> {
+  face: &'a Face,
+  coords: &'a [NormalizedCoordinate],
+  painter: &'a mut Painter,
+  /// The outline of the last `outline_glyph`, it's taken by the next
+  /// `push_clip`.
+  outline: Option<Resource<Path>>,
+  /// The clip stack, one item for each `push_clip` and `push_clip_box`. The
+  /// outline of the innermost clip is kept here and filled directly by the
+  /// paint, it's only applied to the painter as a clip when another operation
+  /// is nested in it.
+  clips: Vec<Option<Resource<Path>>>,
+}
+
+impl<'a> ColrPainter<'a> {
+  fn new(face: &'a Face, coords: &'a [NormalizedCoordinate], painter: &'a mut Painter) -> Self {
+    Self { face, coords, painter, outline: None, clips: vec![] }
+  }
+
+  /// Apply the outline of the innermost clip to the painter, before an
+  /// operation nested in it changes the painter state.
+  fn apply_outline(&mut self) {
+    if let Some(path) = self.clips.last_mut().and_then(Option::take) {
+      self.painter.clip(path.into());
+    }
+  }
+}
+
+impl colr::Painter<'_> for ColrPainter<'_> {
+  fn outline_glyph(&mut self, glyph_id: GlyphId) {
+    self.outline = self.face.outline_glyph(glyph_id);
+  }
+
+  fn paint(&mut self, paint: colr::Paint<'_>) {
+    let palette = self.face.palette();
+    let brush = match paint {
+      colr::Paint::Solid(c) => to_color(c).into(),
+      colr::Paint::LinearGradient(g) => {
+        let stops = gradient_stops(g.stops(palette, self.coords));
+        let (start, end) = linear_gradient_line(
+          Point::new(g.x0, g.y0),
+          Point::new(g.x1, g.y1),
+          Point::new(g.x2, g.y2),
+        );
+        Brush::LinearGradient(LinearGradient {
+          start,
+          end,
+          stops,
+          spread_method: to_spread(g.extend),
+        })
+      }
+      colr::Paint::RadialGradient(g) => {
+        let stops = gradient_stops(g.stops(palette, self.coords));
+        Brush::RadialGradient(RadialGradient {
+          start_center: Point::new(g.x0, g.y0),
+          start_radius: g.r0,
+          end_center: Point::new(g.x1, g.y1),
+          end_radius: g.r1,
+          stops,
+          spread_method: to_spread(g.extend),
+        })
+      }
+      colr::Paint::SweepGradient(g) => {
+        // The painter has no sweep gradient, use the average color instead.
+        log::warn!("[painter]: not support the sweep gradient of color glyphs, use a solid color.");
+        average_color(g.stops(palette, self.coords)).into()
+      }
+    };
+
+    match self.clips.last().cloned().flatten() {
+      Some(path) => {
+        self
+          .painter
+          .set_fill_brush(brush)
+          .fill_path(path.into());
+      }
+      // A paint without outline fills the whole clip area.
+      None => {
+        let bounds = self.painter.paint_bounds();
+        if bounds.is_finite() && !bounds.is_empty() {
+          self
+            .painter
+            .set_fill_brush(brush)
+            .rect(&bounds)
+            .fill();
+        }
+      }
+    }
+  }
+
+  fn push_clip(&mut self) {
+    self.apply_outline();
+    self.painter.save();
+    self.clips.push(self.outline.take());
+  }
+
+  fn push_clip_box(&mut self, clip_box: ClipBox) {
+    self.apply_outline();
+    let rect = Rect::new(
+      Point::new(clip_box.x_min, clip_box.y_min),
+      Size::new(clip_box.x_max - clip_box.x_min, clip_box.y_max - clip_box.y_min),
+    );
+    self.painter.save().clip(Path::rect(&rect).into());
+    self.clips.push(None);
+  }
+
+  fn pop_clip(&mut self) {
+    self.clips.pop();
+    self.painter.restore();
+  }
+
+  fn push_layer(&mut self, mode: CompositeMode) {
+    if mode != CompositeMode::SourceOver {
+      log::warn!("[painter]: not support the composite mode {mode:?} of color glyphs, ignored!");
+    }
+    self.apply_outline();
+    self.painter.save();
+  }
+
+  fn pop_layer(&mut self) { self.painter.restore(); }
+
+  fn push_transform(&mut self, ts: rustybuzz::ttf_parser::Transform) {
+    self.apply_outline();
+    let ts = Transform::new(ts.a, ts.b, ts.c, ts.d, ts.e, ts.f);
+    self.painter.save().apply_transform(&ts);
+  }
+
+  fn pop_transform(&mut self) { self.painter.restore(); }
+}
+
+/// The linear gradient of `COLR` is defined by three points, the color line is
+/// from `p0` to `p1` but rotated to be perpendicular to the line from `p0` to
+/// `p2`. Return the start and end points of the gradient.
+fn linear_gradient_line(p0: Point, p1: Point, p2: Point) -> (Point, Point) {
+  let v = p2 - p0;
+  let normal = Vector::new(v.y, -v.x);
+  let len = normal.square_length();
+  if len <= f32::EPSILON {
+    return (p0, p1);
+  }
+
+  let end = p0 + normal * ((p1 - p0).dot(normal) / len);
+  (p0, end)
+}
+
+fn gradient_stops(stops: impl Iterator<Item = colr::ColorStop>) -> Vec<GradientStop> {
+  stops
+    .map(|s| GradientStop::new(to_color(s.color), s.stop_offset))
+    .collect()
+}
+
+fn average_color(stops: impl Iterator<Item = colr::ColorStop>) -> Color {
+  let (mut sum, mut cnt) = ([0u32; 4], 0);
+  for s in stops {
+    let c = s.color;
+    for (v, c) in sum
+      .iter_mut()
+      .zip([c.red, c.green, c.blue, c.alpha])
+    {
+      *v += c as u32;
+    }
+    cnt += 1;
+  }
+  let [r, g, b, a] = sum.map(|v| (v / cnt.max(1)) as u8);
+  Color::new(r, g, b, a)
+}
+
+fn to_color(c: RgbaColor) -> Color { Color::new(c.red, c.green, c.blue, c.alpha) }
+
+fn to_spread(extend: GradientExtend) -> SpreadMethod {
+  match extend {
+    GradientExtend::Pad => SpreadMethod::Pad,
+    GradientExtend::Repeat => SpreadMethod::Repeat,
+    GradientExtend::Reflect => SpreadMethod::Reflect,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use colr::Painter as _;
+
+  use super::*;
+  use crate::{
+    CommandBrush, FontFace, FontFamily, PaintCommand, PaintPathAction, text::font_db::FontDB,
+  };
+
+  #[test]
+  fn perpendicular_gradient_line() {
+    let p0 = Point::new(0., 0.);
+    // `p2` is on the y axis, so the color line is along the x axis.
+    let (start, end) = linear_gradient_line(p0, Point::new(10., 10.), Point::new(0., 5.));
+    assert_eq!(start, p0);
+    assert_eq!(end, Point::new(10., 0.));
+
+    let (_, end) = linear_gradient_line(p0, Point::new(10., 10.), p0);
+    assert_eq!(end, Point::new(10., 10.));
+  }
+
+  #[test]
+  fn paint_layers() {
+    let mut db = FontDB::default();
+    let _ = db.load_font_file(env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf");
+    let id = db
+      .select_best_match(&FontFace {
+        families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+        ..<_>::default()
+      })
+      .unwrap();
+    let face = db.face_data_or_insert(id).unwrap();
+    let glyph = face.rb_face.glyph_index('A').unwrap();
+
+    let red = RgbaColor::new(255, 0, 0, 255);
+    let blue = RgbaColor::new(0, 0, 255, 255);
+    let green = RgbaColor::new(0, 255, 0, 255);
+    let mut painter = Painter::new(Rect::from_size(Size::new(f32::MAX, f32::MAX)));
+    let mut colr = ColrPainter::new(face, &[], &mut painter);
+    // The innermost outline is filled directly.
+    colr.outline_glyph(glyph);
+    colr.push_clip();
+    colr.paint(colr::Paint::Solid(red));
+    colr.pop_clip();
+    // A paint without outline fills the clip box.
+    colr.push_clip_box(ClipBox { x_min: 0., y_min: 0., x_max: 100., y_max: 50. });
+    colr.paint(colr::Paint::Solid(blue));
+    colr.pop_clip();
+    // The outline clips the paints that nest in a transform.
+    colr.outline_glyph(glyph);
+    colr.push_clip();
+    colr.push_transform(rustybuzz::ttf_parser::Transform::new_translate(10., 0.));
+    colr.paint(colr::Paint::Solid(green));
+    colr.pop_transform();
+    colr.pop_clip();
+
+    let cmds = painter.finish().to_owned();
+    let is_clip = |cmd: &PaintCommand| {
+      matches!(cmd, PaintCommand::Path(p) if matches!(p.action, PaintPathAction::Clip))
+    };
+    let bounds = |cmd: &PaintCommand| match cmd {
+      PaintCommand::Path(p) => Some(p.paint_bounds),
+      _ => None,
+    };
+    let color = |cmd: &PaintCommand| match cmd {
+      PaintCommand::Path(p) => match &p.action {
+        PaintPathAction::Paint { brush: CommandBrush::Color(c), .. } => Some(*c),
+        _ => None,
+      },
+      _ => None,
+    };
+    let outline = face.outline_glyph(glyph).unwrap();
+
+    assert_eq!(cmds.len(), 7);
+    assert_eq!(color(&cmds[0]), Some(to_color(red)));
+    assert_eq!(bounds(&cmds[0]), Some(outline.bounds(None)));
+    assert!(is_clip(&cmds[1]));
+    assert_eq!(color(&cmds[2]), Some(to_color(blue)));
+    assert_eq!(bounds(&cmds[2]), Some(Rect::from_size(Size::new(100., 50.))));
+    assert!(matches!(cmds[3], PaintCommand::PopClip));
+    assert!(is_clip(&cmds[4]));
+    assert_eq!(color(&cmds[5]), Some(to_color(green)));
+    assert!(matches!(cmds[6], PaintCommand::PopClip));
+  }
+}
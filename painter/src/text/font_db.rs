@@ -1,22 +1,49 @@
-use std::{cell::RefCell, num::NonZeroU16, ops::Deref, sync::Arc};
+use std::{borrow::Cow, cell::RefCell, num::NonZeroU16, ops::Deref, sync::Arc};
 
-use ahash::HashMap;
+use ahash::{HashMap, HashSet};
 use fontdb::{Database, Query};
 pub use fontdb::{FaceInfo, Family, ID};
 use ribir_algo::{Resource, Sc};
 use ribir_geom::{Point, Rect, rect};
-use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder};
+use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder, Tag, VariationAxis};
+pub use unicode_script::Script;
 
 use crate::{
   Path, PixelImage, Svg,
   path_builder::PathBuilder,
-  text::{FontFace, FontFamily, svg_glyph_cache::SvgGlyphCache},
+  text::{
+    FontFace, FontFamily, FontVariation, colr_glyph_cache::ColrGlyphCache,
+    svg_glyph_cache::SvgGlyphCache,
+  },
 };
+/// The count of the steps that the range of a variation axis is divided into.
+const VARIATION_STEPS: f32 = 256.;
+/// The max count of the variation instances of a face.
+const MAX_VARIATION_INSTANCES: usize = 64;
+
+/// The normalized variation axis values and the instances of them.
+type VariationInstances = Vec<(Box<[FontVariation]>, ID)>;
+
 /// A wrapper of fontdb and cache font data.
 pub struct FontDB {
   default_fonts: Vec<ID>,
   data_base: fontdb::Database,
   cache: HashMap<ID, Option<Face>>,
+  /// The instances of the variable fonts with the normalized variation axis
+  /// values, at most `MAX_VARIATION_INSTANCES` for a face.
+  variation_instances: HashMap<ID, VariationInstances>,
+  /// The instances of the color fonts with the selected palette.
+  palette_instances: HashMap<(ID, u16), ID>,
+  script_fallbacks: HashMap<Script, Vec<ID>>,
+  lang_fallbacks: HashMap<Cow<'static, str>, Vec<ID>>,
+  /// The face found in the database for a char that no fallback face has.
+  char_faces: HashMap<char, Option<ID>>,
+  discover_fallback: bool,
+  /// Increased when the fallback fonts change, the shaped results of an older
+  /// generation are dropped.
+  fallback_generation: usize,
+  /// The chars that have no glyph in any face.
+  missing_chars: HashSet<char>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -40,6 +67,8 @@ pub struct Face {
   raster_image_glyphs: FontGlyphCache<GlyphId, Resource<PixelImage>>,
   outline_glyphs: FontGlyphCache<GlyphId, Resource<Path>>,
   svg_glyphs: Sc<RefCell<SvgGlyphCache>>,
+  colr_glyphs: Sc<RefCell<ColrGlyphCache>>,
+  palette: u16,
   x_height: u16,
   cap_height: i16,
   ascender: i16,
@@ -53,10 +82,108 @@ impl FontDB {
   /// text does not match.
   pub fn set_default_fonts(&mut self, face: &FontFace) {
     self.default_fonts = self.select_all_match(face);
+    self.fallback_generation += 1;
   }
 
   pub fn default_fonts(&self) -> &[ID] { &self.default_fonts }
 
+  /// Sets the fallback fonts for the text of the `script`, they are tried
+  /// before the default fonts when the fonts of the text style miss a char.
+  /// The text shaped before is shaped again with the new fallback fonts.
+  pub fn set_script_fallback(&mut self, script: Script, face: &FontFace) {
+    let ids = self.select_all_match(face);
+    self.script_fallbacks.insert(script, ids);
+    self.fallback_generation += 1;
+  }
+
+  /// Returns the fallback fonts of the `script`.
+  pub fn script_fallback(&self, script: Script) -> &[ID] {
+    self
+      .script_fallbacks
+      .get(&script)
+      .map_or(&[], |ids| ids)
+  }
+
+  /// Sets the fallback fonts for the text of the language `lang`, a BCP 47
+  /// tag. They are tried before the script fallback fonts, for example to
+  /// prefer a Japanese font for the Han characters of the `ja` text.
+  pub fn set_lang_fallback(&mut self, lang: impl Into<Cow<'static, str>>, face: &FontFace) {
+    let ids = self.select_all_match(face);
+    self.lang_fallbacks.insert(lang.into(), ids);
+    self.fallback_generation += 1;
+  }
+
+  /// Returns the fallback fonts of the language `lang`. A language of `zh-TW`
+  /// will use the fallback fonts of `zh` if there are none of `zh-TW`.
+  pub fn lang_fallback(&self, lang: &str) -> &[ID] {
+    self
+      .lang_fallbacks
+      .get(lang)
+      .or_else(|| {
+        let primary = lang.split(['-', '_']).next()?;
+        self.lang_fallbacks.get(primary)
+      })
+      .map_or(&[], |ids| ids)
+  }
+
+  /// Whether to search the whole database, include the system fonts if they are
+  /// loaded, for a face that has the char when all the fallback fonts miss it.
+  /// It's disabled by default.
+  pub fn set_discover_fallback(&mut self, enable: bool) {
+    self.discover_fallback = enable;
+    self.fallback_generation += 1;
+  }
+
+  pub fn discover_fallback(&self) -> bool { self.discover_fallback }
+
+  /// The generation of the fallback fonts, it's increased every time the
+  /// default fonts or the fallback fonts change. The caches of the shaped
+  /// text compare it to know if they are stale.
+  pub fn fallback_generation(&self) -> usize { self.fallback_generation }
+
+  /// Finds a face in the database that has the glyph of `c`, include the system
+  /// fonts if they are loaded. The result is cached until a new font is
+  /// loaded.
+  pub fn find_face_for_char(&mut self, c: char) -> Option<ID> {
+    if let Some(id) = self.char_faces.get(&c) {
+      return *id;
+    }
+
+    let id = self
+      .data_base
+      .faces()
+      // The instances of the variable fonts have no family.
+      .filter(|info| !info.families.is_empty())
+      .map(|info| info.id)
+      .find(|id| {
+        self
+          .data_base
+          .with_face_data(*id, |data, index| {
+            rustybuzz::ttf_parser::Face::parse(data, index)
+              .is_ok_and(|face| face.glyph_index(c).is_some())
+          })
+          .unwrap_or(false)
+      });
+    let id = id.filter(|id| self.face_data_or_insert(*id).is_some());
+    self.char_faces.insert(c, id);
+    id
+  }
+
+  /// The chars that have no glyph in any face since the last
+  /// [`FontDB::clear_missing_chars`], helps to find out which fonts should be
+  /// installed or shipped with the application.
+  pub fn missing_chars(&self) -> impl Iterator<Item = char> + '_ {
+    self.missing_chars.iter().copied()
+  }
+
+  pub fn clear_missing_chars(&mut self) { self.missing_chars.clear(); }
+
+  pub(crate) fn add_missing_char(&mut self, c: char) {
+    if self.missing_chars.insert(c) {
+      log::warn!("No font has the glyph of {c:?} (U+{:04X}).", c as u32);
+    }
+  }
+
   pub fn try_get_face_data(&self, face_id: ID) -> Option<&Face> {
     self.cache.get(&face_id)?.as_ref()
   }
@@ -87,7 +214,10 @@ impl FontDB {
   }
 
   #[inline]
-  pub fn load_from_bytes(&mut self, data: Vec<u8>) { self.data_base.load_font_data(data); }
+  pub fn load_from_bytes(&mut self, data: Vec<u8>) {
+    self.data_base.load_font_data(data);
+    self.char_faces.clear();
+  }
 
   /// Loads a font file into the `Database`.
   ///
@@ -96,6 +226,7 @@ impl FontDB {
   pub fn load_font_file<P: AsRef<std::path::Path>>(
     &mut self, path: P,
   ) -> Result<(), std::io::Error> {
+    self.char_faces.clear();
     self.data_base.load_font_file(path)
   }
 
@@ -111,6 +242,7 @@ impl FontDB {
   /// be added manually.
   pub fn load_system_fonts(&mut self) {
     self.data_base.load_system_fonts();
+    self.char_faces.clear();
     self.static_generic_families();
   }
 
@@ -146,6 +278,127 @@ impl FontDB {
       .collect()
   }
 
+  /// Returns the id of an instance of the face `id` with the variation axis
+  /// values, the shaping and the glyph outlines of the instance use these
+  /// values.
+  ///
+  /// The axes that the face doesn't have and the values equal to the default
+  /// are ignored, so `id` itself is returned for a face that isn't variable.
+  /// The values are clamped to the range of their axes and rounded to
+  /// `VARIATION_STEPS` steps of the range, a later value of the same axis
+  /// overrides the former one. A face has at most `MAX_VARIATION_INSTANCES`
+  /// instances, after that the closest instance is returned.
+  pub fn variation_instance(&mut self, id: ID, variations: &[FontVariation]) -> ID {
+    let Some(face) = self.face_data_or_insert(id) else { return id };
+    let axes: Vec<_> = face
+      .rb_face
+      .variation_axes()
+      .into_iter()
+      .collect();
+    let variations: Box<[FontVariation]> = axes
+      .iter()
+      .filter_map(|a| {
+        let v = variations
+          .iter()
+          .rfind(|v| Tag::from_bytes(&v.tag) == a.tag)?;
+        let range = a.max_value - a.min_value;
+        let step = range / VARIATION_STEPS;
+        let value = v.value.clamp(a.min_value, a.max_value);
+        let value = if step > 0. {
+          a.min_value + ((value - a.min_value) / step).round() * step
+        } else {
+          value
+        };
+        ((value - a.def_value).abs() > step / 2.).then_some(FontVariation { tag: v.tag, value })
+      })
+      .collect();
+    if variations.is_empty() {
+      return id;
+    }
+
+    let instances = self.variation_instances.entry(id).or_default();
+    if let Some((_, instance)) = instances.iter().find(|(v, _)| *v == variations) {
+      return *instance;
+    }
+    if instances.len() >= MAX_VARIATION_INSTANCES {
+      // The value of an axis that isn't set is the default.
+      let value_of = |vs: &[FontVariation], a: &VariationAxis| {
+        vs.iter()
+          .find(|v| Tag::from_bytes(&v.tag) == a.tag)
+          .map_or(a.def_value, |v| v.value)
+      };
+      let distance = |vs: &[FontVariation]| -> f32 {
+        axes
+          .iter()
+          .filter(|a| a.max_value > a.min_value)
+          .map(|a| {
+            let d = (value_of(vs, a) - value_of(&variations, a)) / (a.max_value - a.min_value);
+            d * d
+          })
+          .sum()
+      };
+      return instances
+        .iter()
+        .min_by(|a, b| distance(&a.0).total_cmp(&distance(&b.0)))
+        .map_or(id, |(_, instance)| *instance);
+    }
+
+    let Some(mut info) = self.data_base.face(id).cloned() else { return id };
+    // The instance should not be matched by the family queries.
+    info.families.clear();
+    let instance = self.data_base.push_face_info(info);
+    let face = self.cache[&id].as_ref().and_then(|face| {
+      let mut face = Face::from_data(instance, face.source_data.clone(), face.face_data_index)?;
+      for v in variations.iter() {
+        face
+          .rb_face
+          .set_variation(Tag::from_bytes(&v.tag), v.value);
+      }
+      Some(face)
+    });
+    self.cache.insert(instance, face);
+    self
+      .variation_instances
+      .entry(id)
+      .or_default()
+      .push((variations, instance));
+    instance
+  }
+
+  /// Returns the id of an instance of the face `id` that paints the color
+  /// glyphs of the `COLR` table with the `palette` of the `CPAL` table.
+  ///
+  /// The `id` itself is returned if the palette is the default `0` or the face
+  /// doesn't have it, so a face has at most as many instances as its palettes.
+  pub fn palette_instance(&mut self, id: ID, palette: u16) -> ID {
+    let Some(face) = self.face_data_or_insert(id) else { return id };
+    let palettes = face
+      .rb_face
+      .color_palettes()
+      .map_or(0, |p| p.get());
+    if palette == face.palette || palette >= palettes {
+      return id;
+    }
+
+    if let Some(instance) = self.palette_instances.get(&(id, palette)) {
+      return *instance;
+    }
+    let Some(mut info) = self.data_base.face(id).cloned() else { return id };
+    info.families.clear();
+    let instance = self.data_base.push_face_info(info);
+    let face = self.cache[&id].as_ref().map(|face| Face {
+      face_id: instance,
+      palette,
+      colr_glyphs: <_>::default(),
+      ..face.clone()
+    });
+    self.cache.insert(instance, face);
+    self
+      .palette_instances
+      .insert((id, palette), instance);
+    instance
+  }
+
   fn static_generic_families(&mut self) {
     // We don't like to depends on some system library and not make the fallback
     // font too complicated. So here are some default fonts collect from web.
@@ -267,7 +520,19 @@ impl Default for FontDB {
     let mut data_base = fontdb::Database::new();
     data_base.load_font_data(include_bytes!("./Lato-Regular.ttf").to_vec());
     let default_font = data_base.faces().next().map(|f| f.id).unwrap();
-    let mut this = FontDB { default_fonts: vec![default_font], data_base, cache: <_>::default() };
+    let mut this = FontDB {
+      default_fonts: vec![default_font],
+      data_base,
+      cache: <_>::default(),
+      variation_instances: <_>::default(),
+      palette_instances: <_>::default(),
+      script_fallbacks: <_>::default(),
+      lang_fallbacks: <_>::default(),
+      char_faces: <_>::default(),
+      discover_fallback: false,
+      fallback_generation: 0,
+      missing_chars: <_>::default(),
+    };
     this.face_data_or_insert(default_font);
     this
   }
@@ -311,6 +576,8 @@ impl Face {
       outline_glyphs: <_>::default(),
       raster_image_glyphs: <_>::default(),
       svg_glyphs: <_>::default(),
+      colr_glyphs: <_>::default(),
+      palette: 0,
       x_height,
       ascender,
       descender,
@@ -377,6 +644,19 @@ impl Face {
       .clone()
   }
 
+  /// The palette of the `CPAL` table used to paint the color glyphs.
+  pub fn palette(&self) -> u16 { self.palette }
+
+  /// Returns the color glyph of the `COLR` table as an [`Svg`], the layers use
+  /// the foreground color inherit the fill brush.
+  pub fn glyph_colr_image(&self, glyph_id: GlyphId) -> Option<Svg> {
+    self
+      .colr_glyphs
+      .borrow_mut()
+      .svg_or_insert(glyph_id, self)
+      .clone()
+  }
+
   pub fn glyph_svg_image(&self, glyph_id: GlyphId) -> Option<Svg> {
     self
       .svg_glyphs
@@ -576,4 +856,46 @@ mod tests {
     let info = fonts.face_info(id.unwrap()).unwrap();
     assert_eq!(info.weight, FontWeight::BOLD);
   }
+
+  #[test]
+  fn variable_font_instance() {
+    let mut db = FontDB::default();
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/Nunito-VariableFont_wght.ttf";
+    db.load_font_file(path).unwrap();
+    let face = FontFace {
+      families: vec![FontFamily::Name("Nunito".into())].into_boxed_slice(),
+      ..<_>::default()
+    };
+    let id = db.select_best_match(&face).unwrap();
+
+    // Not a axis of the font.
+    assert_eq!(db.variation_instance(id, &[FontVariation::new(b"wdth", 50.)]), id);
+
+    let black = [FontVariation::new(b"wght", 900.)];
+    let black_id = db.variation_instance(id, &black);
+    assert_ne!(black_id, id);
+    assert_eq!(db.variation_instance(id, &black), black_id);
+    // The values are normalized, and the later one of an axis wins.
+    let close_black = [FontVariation::new(b"wght", 400.), FontVariation::new(b"wght", 900.4)];
+    assert_eq!(db.variation_instance(id, &close_black), black_id);
+    // The instances of a face are bounded.
+    let instances: HashSet<_> = (0..1000)
+      .map(|w| db.variation_instance(id, &[FontVariation::new(b"wght", w as f32)]))
+      .collect();
+    assert!(instances.len() <= MAX_VARIATION_INSTANCES + 1);
+    // The instance is not matched by the family.
+    assert_eq!(db.select_all_match(&face), vec![id]);
+
+    let glyph_width = |db: &mut FontDB, id| {
+      let face = db.face_data_or_insert(id).unwrap();
+      let glyph = face.rb_face.glyph_index('l').unwrap();
+      face
+        .outline_glyph(glyph)
+        .unwrap()
+        .bounds(None)
+        .width()
+    };
+    let regular = glyph_width(&mut db, id);
+    assert!(glyph_width(&mut db, black_id) > regular);
+  }
 }
@@ -0,0 +1,159 @@
+//! A dictionary-based hyphenator by the [Liang's algorithm](https://tug.org/docs/liang/),
+//! the same algorithm used by TeX, LibreOffice and browsers.
+//!
+//! Ribir doesn't ship any dictionary, load the TeX patterns of the language you
+//! need and register it by [`TypographyStore::set_hyphenator`].
+//!
+//! [`TypographyStore::set_hyphenator`]: crate::TypographyStore::set_hyphenator
+use ahash::HashMap;
+
+/// Find the hyphenation points of words by the patterns of a language.
+#[derive(Debug, Clone, Default)]
+pub struct Hyphenator {
+  /// The letters of the pattern map to the priorities between them.
+  patterns: HashMap<String, Vec<u8>>,
+  /// The words that not follow the patterns map to their hyphenation points.
+  exceptions: HashMap<String, Vec<usize>>,
+  max_pattern_len: usize,
+  left_min: usize,
+  right_min: usize,
+}
+
+impl Hyphenator {
+  /// Create a hyphenator from TeX patterns separated by whitespace, like
+  /// `"hy3ph he2n hena4 1na"`.
+  pub fn new(patterns: &str) -> Self {
+    let mut this = Self { left_min: 2, right_min: 3, ..Default::default() };
+    for p in patterns.split_whitespace() {
+      let mut letters = String::with_capacity(p.len());
+      let mut priorities = vec![0];
+      for c in p.chars() {
+        if let Some(d) = c.to_digit(10) {
+          *priorities.last_mut().unwrap() = d as u8;
+        } else {
+          letters.push(c);
+          priorities.push(0);
+        }
+      }
+      this.max_pattern_len = this.max_pattern_len.max(letters.chars().count());
+      this.patterns.insert(letters, priorities);
+    }
+    this
+  }
+
+  /// Add the words that the patterns can't hyphenate correctly, the words are
+  /// separated by whitespace and their hyphenation points are marked by `-`,
+  /// like `"ta-ble pro-ject"`. The words are case insensitive.
+  pub fn with_exceptions(mut self, exceptions: &str) -> Self {
+    // The words are looked up in lowercase.
+    let exceptions = exceptions.to_lowercase();
+    for e in exceptions.split_whitespace() {
+      let mut points = vec![];
+      let mut word = String::with_capacity(e.len());
+      for c in e.chars() {
+        if c == '-' {
+          points.push(word.chars().count());
+        } else {
+          word.push(c);
+        }
+      }
+      self.exceptions.insert(word, points);
+    }
+    self
+  }
+
+  /// Set the minimum count of characters before and after a hyphenation
+  /// point, `2` and `3` by default.
+  pub fn with_min(mut self, left: usize, right: usize) -> Self {
+    self.left_min = left;
+    self.right_min = right;
+    self
+  }
+
+  /// Return the byte offsets in `word` where a hyphen can be inserted, in
+  /// ascending order.
+  pub fn hyphenate(&self, word: &str) -> Vec<usize> {
+    let lower = word.to_lowercase();
+    let chars: Vec<char> = lower.chars().collect();
+    let len = chars.len();
+    // Case conversion may change the count of the characters, we can't map the
+    // points back to the word.
+    if len != word.chars().count() || len < self.left_min + self.right_min {
+      return vec![];
+    }
+
+    let points: Vec<usize> = if let Some(points) = self.exceptions.get(&lower) {
+      points.clone()
+    } else {
+      let dotted: Vec<char> = std::iter::once('.')
+        .chain(chars.iter().copied())
+        .chain(std::iter::once('.'))
+        .collect();
+      let mut priorities = vec![0u8; dotted.len() + 1];
+      let mut key = String::new();
+      for start in 0..dotted.len() {
+        key.clear();
+        for c in &dotted[start..dotted.len().min(start + self.max_pattern_len)] {
+          key.push(*c);
+          if let Some(p) = self.patterns.get(&key) {
+            p.iter()
+              .zip(&mut priorities[start..])
+              .for_each(|(p, v)| *v = (*v).max(*p));
+          }
+        }
+      }
+      // The priority before the character `i` of the word is at `i + 1` because of
+      // the leading dot.
+      (1..len)
+        .filter(|i| priorities[i + 1] % 2 == 1)
+        .collect()
+    };
+
+    let offsets: Vec<usize> = word.char_indices().map(|(idx, _)| idx).collect();
+    points
+      .into_iter()
+      // A hyphen at the start or the end of the word makes no sense, the exceptions
+      // may mark them.
+      .filter(|p| (1..len).contains(p) && *p >= self.left_min && len - *p >= self.right_min)
+      .map(|p| offsets[p])
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn hyphenated(h: &Hyphenator, word: &str) -> String {
+    let mut res = String::new();
+    let mut start = 0;
+    for p in h.hyphenate(word) {
+      res.push_str(&word[start..p]);
+      res.push('-');
+      start = p;
+    }
+    res.push_str(&word[start..]);
+    res
+  }
+
+  #[test]
+  fn liang_patterns() {
+    let h = Hyphenator::new("hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n");
+    assert_eq!(hyphenated(&h, "hyphenation"), "hy-phen-ation");
+    assert_eq!(hyphenated(&h, "Hyphenation"), "Hy-phen-ation");
+    assert_eq!(hyphenated(&h, "hyph"), "hyph");
+
+    let h = h.with_exceptions("hyphen-ation").with_min(1, 1);
+    assert_eq!(hyphenated(&h, "hyphenation"), "hyphen-ation");
+  }
+
+  #[test]
+  fn exceptions() {
+    let h = Hyphenator::new("")
+      .with_exceptions("-Ta-ble- Pro-ject")
+      .with_min(0, 0);
+    assert_eq!(hyphenated(&h, "table"), "ta-ble");
+    assert_eq!(hyphenated(&h, "Table"), "Ta-ble");
+    assert_eq!(hyphenated(&h, "PROJECT"), "PRO-JECT");
+  }
+}
@@ -0,0 +1,82 @@
+//! Find the line break opportunities by the [Unicode Line Breaking Algorithm](https://www.unicode.org/reports/tr14/)
+//! (UAX #14) of `icu_segmenter`.
+//!
+//! The scripts of the complex context, like Thai, Lao, Khmer and Myanmar, have
+//! no space between words, they are broken by the word dictionaries.
+use icu_segmenter::{
+  LineSegmenter,
+  options::{LineBreakOptions, LineBreakStrictness, LineBreakWordOption},
+};
+
+use crate::WordBreak;
+
+/// Return the byte offsets in `text` where a line is allowed to break, in
+/// ascending order. The start and the end of the text are not included.
+///
+/// A mandatory break, like a line feed, is reported as an opportunity too.
+/// Pass the whole paragraph, the opportunities depend on the text around.
+pub fn line_break_opportunities(text: &str, word_break: WordBreak) -> Vec<usize> {
+  let mut options = LineBreakOptions::default();
+  // The conditional Japanese starters, like the small kana, never start a line.
+  options.strictness = Some(LineBreakStrictness::Strict);
+  options.word_option = Some(match word_break {
+    WordBreak::Normal => LineBreakWordOption::Normal,
+    WordBreak::BreakAll => LineBreakWordOption::BreakAll,
+    WordBreak::KeepAll => LineBreakWordOption::KeepAll,
+  });
+  LineSegmenter::new_dictionary(options)
+    .segment_str(text)
+    .filter(|idx| 0 < *idx && *idx < text.len())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn lines(text: &str, word_break: WordBreak) -> Vec<&str> {
+    let mut start = 0;
+    let mut lines = vec![];
+    for b in line_break_opportunities(text, word_break) {
+      lines.push(&text[start..b]);
+      start = b;
+    }
+    lines.push(&text[start..]);
+    lines
+  }
+
+  #[test]
+  fn latin_words() {
+    assert_eq!(
+      lines("Hello, world! A well-known (test).", WordBreak::Normal),
+      ["Hello, ", "world! ", "A ", "well-", "known ", "(test)."]
+    );
+    assert_eq!(lines("$3.14 100%", WordBreak::Normal), ["$3.14 ", "100%"]);
+    assert_eq!(lines("a\u{A0}b c", WordBreak::Normal), ["a\u{A0}b ", "c"]);
+  }
+
+  #[test]
+  fn cjk_and_kinsoku() {
+    assert_eq!(lines("你好，世界。", WordBreak::Normal), ["你", "好，", "世", "界。"]);
+    assert_eq!(lines("「日本」です", WordBreak::Normal), ["「日", "本」", "で", "す"]);
+    assert_eq!(lines("ちょっと", WordBreak::Normal), ["ちょっ", "と"]);
+  }
+
+  #[test]
+  fn word_break_modes() {
+    assert_eq!(lines("ab cd", WordBreak::BreakAll), ["a", "b ", "c", "d"]);
+    assert_eq!(lines("你好 世界。", WordBreak::KeepAll), ["你好 ", "世界。"]);
+  }
+
+  #[test]
+  fn complex_context() {
+    assert_eq!(lines("ภาษาไทย", WordBreak::Normal), ["ภาษา", "ไทย"]);
+    // A word is never broken.
+    assert_eq!(lines("ខ្មែរ", WordBreak::Normal), ["ខ្មែរ"]);
+  }
+
+  #[test]
+  fn keep_grapheme() {
+    assert_eq!(lines("e\u{301}\u{301}x 👨‍👩‍👦", WordBreak::BreakAll), ["e\u{301}\u{301}", "x ", "👨‍👩‍👦"]);
+  }
+}
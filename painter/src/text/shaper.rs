@@ -1,15 +1,18 @@
 use std::{
   cell::RefCell,
   hash::{Hash, Hasher},
+  ops::Range,
 };
 
 use ribir_algo::{FrameCache, Sc, Substr};
 pub use rustybuzz::ttf_parser::GlyphId;
-use rustybuzz::{GlyphInfo, UnicodeBuffer};
+use rustybuzz::{Feature, GlyphInfo, UnicodeBuffer, ttf_parser::Tag};
+use smallvec::SmallVec;
+use unicode_script::{Script, UnicodeScript};
 
 use super::{GlyphUnit, font_db::GlyphBaseline};
 use crate::{
-  Glyph, TextDirection,
+  FontFeature, Glyph, TextDirection, TextOrientation,
   font_db::{Face, FontDB, ID},
 };
 
@@ -20,7 +23,10 @@ pub const NEWLINE_GLYPH_ID: GlyphId = GlyphId(u16::MAX);
 /// This shaper will cache shaper result for per frame.
 pub struct TextShaper {
   font_db: Sc<RefCell<FontDB>>,
-  shape_cache: FrameCache<ShapeKey, Sc<ShapeResult>>,
+  shape_cache: FrameCache<ShapeKey, CachedShape>,
+  /// The fallback generation of the font database that the cache is shaped
+  /// with.
+  fallback_generation: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -29,9 +35,18 @@ pub struct ShapeResult {
   pub glyphs: Vec<Glyph>,
 }
 
+/// The shape result with the characters no font has, they're reported again
+/// when the result is reused.
+#[derive(Clone)]
+struct CachedShape {
+  result: Sc<ShapeResult>,
+  missing: Box<[char]>,
+}
+
 #[derive(PartialEq, Eq, Hash, Clone)]
 struct ShapeKey {
   face_ids: Box<[ID]>,
+  features: Box<[FontFeature]>,
   text: Substr,
   direction: TextDirection,
   baseline: GlyphBaseline,
@@ -44,22 +59,63 @@ struct GlyphsWithoutFallback {
 
 impl TextShaper {
   #[inline]
-  pub fn new(font_db: Sc<RefCell<FontDB>>) -> Self { Self { font_db, shape_cache: <_>::default() } }
+  pub fn new(font_db: Sc<RefCell<FontDB>>) -> Self {
+    let fallback_generation = font_db.borrow().fallback_generation();
+    Self { font_db, shape_cache: <_>::default(), fallback_generation }
+  }
+
+  /// Drop the cache if the fallback fonts changed after it's shaped.
+  fn sync_fallback_generation(&mut self) {
+    let generation = self.font_db.borrow().fallback_generation();
+    if self.fallback_generation != generation {
+      self.fallback_generation = generation;
+      self.shape_cache.clear();
+    }
+  }
 
   pub fn end_frame(&mut self) { self.shape_cache.end_frame("Text shape"); }
 
-  /// Shape text and return the glyphs, caller should do text reorder before
-  /// call this method.
+  /// Shape text with the OpenType `features` and return the glyphs, caller
+  /// should do text reorder before call this method.
   pub fn shape_text(
-    &mut self, text: &Substr, face_ids: &[ID], direction: TextDirection, baseline: GlyphBaseline,
+    &mut self, text: &Substr, face_ids: &[ID], features: &[FontFeature], direction: TextDirection,
+    baseline: GlyphBaseline,
   ) -> Sc<ShapeResult> {
-    if let Some(res) = self.get_cache(text, face_ids, direction, baseline) {
-      res.clone()
+    self.sync_fallback_generation();
+    let key = (face_ids, features, &**text, direction, baseline);
+    if let Some(cached) = self.shape_cache.get(&key as &dyn ShapeKeySlice) {
+      let mut font_db = self.font_db.borrow_mut();
+      cached
+        .missing
+        .iter()
+        .for_each(|c| font_db.add_missing_char(*c));
+      cached.result.clone()
     } else {
       let mut glyphs = self
-        .shape_text_with_fallback(text, direction, face_ids, baseline)
+        .shape_text_with_fallback(text, direction, face_ids, features, baseline)
         .unwrap_or_default();
 
+      let mut font_db = self.font_db.borrow_mut();
+      let str: &str = text;
+      let mut missing = vec![];
+      for g in glyphs.iter_mut().filter(|g| g.is_miss()) {
+        match str[g.cluster as usize..].chars().next() {
+          // Few fonts have a tab glyph, paint it as a space and let the typography
+          // expand it to the tab stop.
+          Some('\t') => {
+            if let Some(face) = font_db.face_data_or_insert(g.face_id) {
+              tab_as_space(g, face, direction);
+            }
+          }
+          Some(c) if !c.is_control() => {
+            font_db.add_missing_char(c);
+            missing.push(c);
+          }
+          _ => {}
+        }
+      }
+      drop(font_db);
+
       if let Some(last_char) = text.bytes().last() {
         if last_char == b'\r' || last_char == b'\n' {
           if let Some(g) = glyphs.last_mut() {
@@ -70,8 +126,14 @@ impl TextShaper {
 
       let glyphs = Sc::new(ShapeResult { text: text.clone(), glyphs });
       self.shape_cache.put(
-        ShapeKey { face_ids: face_ids.into(), text: text.clone(), direction, baseline },
-        glyphs.clone(),
+        ShapeKey {
+          face_ids: face_ids.into(),
+          features: features.into(),
+          text: text.clone(),
+          direction,
+          baseline,
+        },
+        CachedShape { result: glyphs.clone(), missing: missing.into() },
       );
       glyphs
     }
@@ -79,32 +141,38 @@ impl TextShaper {
 
   /// Directly shape text without bidi reordering.
   pub fn shape_text_with_fallback(
-    &self, text: &str, dir: TextDirection, face_ids: &[ID], baseline: GlyphBaseline,
+    &self, text: &str, dir: TextDirection, face_ids: &[ID], features: &[FontFeature],
+    baseline: GlyphBaseline,
   ) -> Option<Vec<Glyph>> {
-    let mut font_fallback = FallBackFaceHelper::new(face_ids, &self.font_db);
+    let mut font_fallback = FallBackFaceHelper::new(face_ids, text, &self.font_db);
     let face = font_fallback.next_fallback_face(text)?;
     let mut buffer = UnicodeBuffer::new();
     buffer.push_str(text);
     buffer.set_direction(dir.into());
 
+    let features: Vec<_> = features
+      .iter()
+      .map(|f| Feature::new(Tag::from_bytes(&f.tag), f.value, ..))
+      .collect();
     let GlyphsWithoutFallback { mut glyphs, mut buffer } =
-      Self::directly_shape(buffer, baseline, dir.is_horizontal(), &face);
+      Self::directly_shape(buffer, baseline, dir.is_horizontal(), &face, &features);
     let mut new_part = vec![(0, glyphs.len(), font_fallback.clone())];
     loop {
       if new_part.is_empty() {
         break;
       }
       let miss_part = collect_miss_part(&glyphs, &new_part);
-      (buffer, new_part) = regen_miss_part(text, dir, baseline, &mut glyphs, miss_part, buffer);
+      (buffer, new_part) =
+        regen_miss_part(text, dir, baseline, &features, &mut glyphs, miss_part, buffer);
     }
 
     Some(glyphs)
   }
 
   fn directly_shape(
-    text: UnicodeBuffer, baseline: GlyphBaseline, hor_text: bool, face: &Face,
+    text: UnicodeBuffer, baseline: GlyphBaseline, hor_text: bool, face: &Face, features: &[Feature],
   ) -> GlyphsWithoutFallback {
-    let output = rustybuzz::shape(face.as_rb_face(), &[], text);
+    let output = rustybuzz::shape(face.as_rb_face(), features, text);
     let mut glyphs = Vec::with_capacity(output.len());
 
     let infos = output.glyph_infos();
@@ -130,17 +198,128 @@ impl TextShaper {
   }
 
   pub fn get_cache(
-    &mut self, text: &str, face_ids: &[ID], direction: TextDirection, baseline: GlyphBaseline,
+    &mut self, text: &str, face_ids: &[ID], features: &[FontFeature], direction: TextDirection,
+    baseline: GlyphBaseline,
   ) -> Option<Sc<ShapeResult>> {
+    self.sync_fallback_generation();
     self
       .shape_cache
-      .get(&(face_ids, text, direction, baseline) as &(dyn ShapeKeySlice))
-      .cloned()
+      .get(&(face_ids, features, text, direction, baseline) as &dyn ShapeKeySlice)
+      .map(|cached| cached.result.clone())
+  }
+
+  /// Shape the text to set in a vertical line. The characters upright in the
+  /// `orientation` are shaped vertically with the `vert` feature, and the
+  /// others are shaped in the `dir` direction and rotated 90° clockwise.
+  ///
+  /// The glyphs advance by `y_advance`, and each glyph is drawn in an em box
+  /// whose top is at the pen position.
+  pub fn shape_vertical(
+    &mut self, text: &Substr, face_ids: &[ID], features: &[FontFeature],
+    orientation: TextOrientation, dir: TextDirection, baseline: GlyphBaseline,
+  ) -> Sc<ShapeResult> {
+    let em = GlyphUnit::STANDARD_EM;
+    let mut glyphs = vec![];
+    for (range, upright) in orientation_runs(text, orientation) {
+      let start = range.start as u32;
+      let sub = text.substr(range);
+      if upright {
+        // The baseline is meaningless for the upright glyphs.
+        let dir = TextDirection::TopToBottom;
+        let res = self.shape_text(&sub, face_ids, features, dir, GlyphBaseline::Alphabetic);
+        glyphs.extend(res.glyphs.iter().map(|g| Glyph {
+          x_advance: em,
+          y_advance: -g.y_advance,
+          // The vertical origin is at the center top of the glyph, move it to the
+          // left top of the em box.
+          x_offset: em / 2. + g.x_offset,
+          y_offset: -g.y_offset - em,
+          cluster: g.cluster + start,
+          ..g.clone()
+        }));
+      } else {
+        let res = self.shape_text(&sub, face_ids, features, dir, baseline);
+        glyphs.extend(res.glyphs.iter().map(|g| Glyph {
+          x_advance: em,
+          y_advance: g.x_advance,
+          x_offset: -g.y_offset,
+          y_offset: g.x_offset,
+          cluster: g.cluster + start,
+          rotated: true,
+          ..g.clone()
+        }));
+      }
+    }
+    Sc::new(ShapeResult { text: text.clone(), glyphs })
   }
 
   pub fn font_db(&self) -> &Sc<RefCell<FontDB>> { &self.font_db }
 }
 
+/// Split the text to the runs of the upright characters and the rotated
+/// characters.
+fn orientation_runs(text: &str, orientation: TextOrientation) -> Vec<(Range<usize>, bool)> {
+  match orientation {
+    TextOrientation::Upright => return vec![(0..text.len(), true)],
+    TextOrientation::Sideways => return vec![(0..text.len(), false)],
+    TextOrientation::Mixed => {}
+  }
+
+  let mut runs: Vec<(Range<usize>, bool)> = vec![];
+  for (idx, c) in text.char_indices() {
+    let end = idx + c.len_utf8();
+    match runs.last_mut() {
+      // The combining marks and the joiners follow the previous character.
+      Some((rg, _)) if c.script() == Script::Inherited || c == '\u{200D}' => rg.end = end,
+      Some((rg, upright)) if *upright == is_upright_char(c) => rg.end = end,
+      _ => runs.push((idx..end, is_upright_char(c))),
+    }
+  }
+  runs
+}
+
+/// If the character is set upright in the vertical line by default, a simple
+/// version of the [Unicode Vertical Text Layout](https://www.unicode.org/reports/tr50/).
+fn is_upright_char(c: char) -> bool {
+  matches!(
+    c.script(),
+    Script::Han
+      | Script::Hiragana
+      | Script::Katakana
+      | Script::Hangul
+      | Script::Bopomofo
+      | Script::Yi
+  ) || matches!(
+    c,
+    '\u{1100}'..='\u{11FF}'
+      | '\u{2600}'..='\u{27BF}'
+      | '\u{2E80}'..='\u{2FFF}'
+      | '\u{3000}'..='\u{33FF}'
+      | '\u{FE10}'..='\u{FE1F}'
+      | '\u{FE30}'..='\u{FE4F}'
+      | '\u{FF01}'..='\u{FF60}'
+      | '\u{FFE0}'..='\u{FFE6}'
+      | '\u{1F000}'..='\u{1FAFF}'
+  )
+}
+
+/// Replace the missing tab glyph with the space glyph of the face.
+fn tab_as_space(g: &mut Glyph, face: &Face, direction: TextDirection) {
+  let rb_face = face.as_rb_face();
+  let Some(space) = rb_face.glyph_index(' ') else { return };
+  let scale = GlyphUnit::UNITS_PER_EM as f32 / face.units_per_em() as f32;
+  g.glyph_id = space;
+  if direction.is_horizontal() {
+    let advance = rb_face.glyph_hor_advance(space).unwrap_or(0);
+    g.x_advance = super::cast(advance as i32, scale);
+  } else {
+    let advance = rb_face
+      .glyph_ver_advance(space)
+      .unwrap_or(face.units_per_em());
+    g.y_advance = -super::cast(advance as i32, scale);
+  }
+}
+
 fn collect_miss_part<'a>(
   glyphs: &[Glyph], new_part: &[(usize, usize, FallBackFaceHelper<'a>)],
 ) -> Vec<(usize, usize, FallBackFaceHelper<'a>)> {
@@ -181,8 +360,9 @@ fn collect_miss_part<'a>(
 }
 
 fn regen_miss_part<'a>(
-  text: &str, dir: TextDirection, baseline: GlyphBaseline, glyphs: &mut Vec<Glyph>,
-  miss_part: Vec<(usize, usize, FallBackFaceHelper<'a>)>, mut buffer: UnicodeBuffer,
+  text: &str, dir: TextDirection, baseline: GlyphBaseline, features: &[Feature],
+  glyphs: &mut Vec<Glyph>, miss_part: Vec<(usize, usize, FallBackFaceHelper<'a>)>,
+  mut buffer: UnicodeBuffer,
 ) -> (UnicodeBuffer, Vec<(usize, usize, FallBackFaceHelper<'a>)>) {
   let is_rtl = matches!(dir, TextDirection::RightToLeft | TextDirection::BottomToTop);
   let hb_direction = dir.into();
@@ -211,7 +391,8 @@ fn regen_miss_part<'a>(
     if let Some(face) = helper.next_fallback_face(miss_text) {
       buffer.push_str(miss_text);
       buffer.set_direction(hb_direction);
-      let mut res = TextShaper::directly_shape(buffer, baseline, dir.is_horizontal(), &face);
+      let mut res =
+        TextShaper::directly_shape(buffer, baseline, dir.is_horizontal(), &face, features);
       buffer = res.buffer;
       for g in res.glyphs.iter_mut() {
         g.cluster += miss_range.start as u32;
@@ -227,6 +408,7 @@ fn regen_miss_part<'a>(
 
 trait ShapeKeySlice {
   fn face_ids(&self) -> &[ID];
+  fn features(&self) -> &[FontFeature];
   fn text(&self) -> &str;
   fn direction(&self) -> TextDirection;
   fn baseline(&self) -> GlyphBaseline;
@@ -239,6 +421,7 @@ impl<'a> std::borrow::Borrow<dyn ShapeKeySlice + 'a> for ShapeKey {
 impl Hash for dyn ShapeKeySlice + '_ {
   fn hash<H: Hasher>(&self, state: &mut H) {
     self.face_ids().hash(state);
+    self.features().hash(state);
     self.text().hash(state);
     self.direction().hash(state);
     self.baseline().hash(state);
@@ -248,6 +431,7 @@ impl Hash for dyn ShapeKeySlice + '_ {
 impl PartialEq for dyn ShapeKeySlice + '_ {
   fn eq(&self, other: &Self) -> bool {
     self.face_ids() == other.face_ids()
+      && self.features() == other.features()
       && self.text() == other.text()
       && self.direction() == other.direction()
       && self.baseline() == other.baseline()
@@ -259,6 +443,8 @@ impl Eq for dyn ShapeKeySlice + '_ {}
 impl ShapeKeySlice for ShapeKey {
   fn face_ids(&self) -> &[ID] { &self.face_ids }
 
+  fn features(&self) -> &[FontFeature] { &self.features }
+
   fn text(&self) -> &str { &self.text }
 
   fn direction(&self) -> TextDirection { self.direction }
@@ -266,14 +452,16 @@ impl ShapeKeySlice for ShapeKey {
   fn baseline(&self) -> GlyphBaseline { self.baseline }
 }
 
-impl ShapeKeySlice for (&[ID], &str, TextDirection, GlyphBaseline) {
+impl ShapeKeySlice for (&[ID], &[FontFeature], &str, TextDirection, GlyphBaseline) {
   fn face_ids(&self) -> &[ID] { self.0 }
 
-  fn text(&self) -> &str { self.1 }
+  fn features(&self) -> &[FontFeature] { self.1 }
 
-  fn direction(&self) -> TextDirection { self.2 }
+  fn text(&self) -> &str { self.2 }
 
-  fn baseline(&self) -> GlyphBaseline { self.3 }
+  fn direction(&self) -> TextDirection { self.3 }
+
+  fn baseline(&self) -> GlyphBaseline { self.4 }
 }
 
 impl From<TextDirection> for rustybuzz::Direction {
@@ -295,18 +483,30 @@ struct FallBackFaceHelper<'a> {
 }
 
 impl<'a> FallBackFaceHelper<'a> {
-  fn new(ids: &'a [ID], font_db: &'a RefCell<FontDB>) -> Self {
+  /// The fallback order is the faces of `ids`, the script fallback faces of
+  /// the scripts in `text`, and the default fonts.
+  fn new(ids: &'a [ID], text: &str, font_db: &'a RefCell<FontDB>) -> Self {
     let mut ids = ids.to_vec();
-    let set: ahash::HashSet<ID> = ahash::HashSet::from_iter(ids.iter().cloned());
+    let mut set: ahash::HashSet<ID> = ahash::HashSet::from_iter(ids.iter().cloned());
 
     {
       let font_db = font_db.borrow();
-      let default_ids = font_db.default_fonts();
-      for id in default_ids.iter() {
-        if set.contains(id) {
-          continue;
+      let mut scripts = SmallVec::<[Script; 2]>::new();
+      for c in text.chars() {
+        let script = c.script();
+        if !matches!(script, Script::Common | Script::Inherited | Script::Unknown)
+          && !scripts.contains(&script)
+        {
+          scripts.push(script);
+        }
+      }
+      let script_ids = scripts
+        .into_iter()
+        .flat_map(|s| font_db.script_fallback(s));
+      for id in script_ids.chain(font_db.default_fonts()) {
+        if set.insert(*id) {
+          ids.push(*id);
         }
-        ids.push(*id);
       }
     }
 
@@ -314,29 +514,35 @@ impl<'a> FallBackFaceHelper<'a> {
   }
 
   fn next_fallback_face(&mut self, text: &str) -> Option<Face> {
-    let font_db = self.font_db.borrow();
     loop {
       if self.face_idx >= self.ids.len() {
         return None;
       }
 
-      let face = self
-        .ids
-        .get(self.face_idx)
-        .and_then(|id| font_db.try_get_face_data(*id))
-        .cloned();
+      let face = self.ids.get(self.face_idx).and_then(|id| {
+        self
+          .font_db
+          .borrow()
+          .try_get_face_data(*id)
+          .cloned()
+      });
 
       self.face_idx += 1;
-      if self.face_idx == self.ids.len() {
+      let has_text = |f: &Face| text.is_empty() || text.chars().any(|c| f.has_char(c));
+      if face.as_ref().is_some_and(has_text) {
         return face;
-      } else {
-        let face = face.filter(|f| match text.is_empty() {
-          true => true,
-          false => text.chars().any(|c| f.has_char(c)),
-        });
-        if face.is_some() {
-          return face;
+      } else if self.face_idx == self.ids.len() && self.font_db.borrow().discover_fallback() {
+        // All the fallback faces miss the text, try to find one in the database.
+        let found = text
+          .chars()
+          .find_map(|c| self.font_db.borrow_mut().find_face_for_char(c))
+          .filter(|id| !self.ids.contains(id));
+        match found {
+          Some(id) => self.ids.push(id),
+          None => return face,
         }
+      } else if self.face_idx == self.ids.len() {
+        return face;
       }
     }
   }
@@ -365,16 +571,16 @@ mod tests {
     // No cache exists
     assert!(
       shaper
-        .get_cache(&text, &ids, dir, baseline)
+        .get_cache(&text, &ids, &[], dir, baseline)
         .is_none()
     );
 
-    let result = shaper.shape_text(&text, &ids, dir, GlyphBaseline::Alphabetic);
+    let result = shaper.shape_text(&text, &ids, &[], dir, GlyphBaseline::Alphabetic);
     assert_eq!(result.glyphs.len(), 6);
 
     assert!(
       shaper
-        .get_cache(&text, &ids, dir, baseline)
+        .get_cache(&text, &ids, &[], dir, baseline)
         .is_some()
     );
 
@@ -382,7 +588,7 @@ mod tests {
     shaper.end_frame();
     assert!(
       shaper
-        .get_cache(&text, &ids, dir, baseline)
+        .get_cache(&text, &ids, &[], dir, baseline)
         .is_none()
     );
   }
@@ -423,6 +629,7 @@ mod tests {
     let latin1 = shaper.shape_text(
       &"hello world! 你好，世界".into(),
       &ids_latin,
+      &[],
       dir,
       GlyphBaseline::Alphabetic,
     );
@@ -444,6 +651,7 @@ mod tests {
     let fallback_chinese = shaper.shape_text(
       &"hello world! 你好，世界".into(),
       &ids_all,
+      &[],
       dir,
       GlyphBaseline::Alphabetic,
     );
@@ -461,12 +669,110 @@ mod tests {
     assert_eq!(&clusters, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 16, 19, 22, 25]);
   }
 
+  #[test]
+  fn script_fallback() {
+    let mut shaper = TextShaper::new(<_>::default());
+    let path = env!("CARGO_MANIFEST_DIR").to_owned();
+    let mut font_db = shaper.font_db.borrow_mut();
+    let _ = font_db.load_font_file(path.clone() + "/../fonts/DejaVuSans.ttf");
+    let _ = font_db.load_font_file(path + "/../fonts/NotoSerifSC-Bold.你好世界.otf");
+    let ids_latin = font_db.select_all_match(&FontFace {
+      families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+      ..<_>::default()
+    });
+    drop(font_db);
+
+    let text: Substr = "hi 你好世界".into();
+    let dir = TextDirection::LeftToRight;
+    let miss_count = |shaper: &mut TextShaper| {
+      shaper
+        .shape_text(&text, &ids_latin, &[], dir, GlyphBaseline::Alphabetic)
+        .glyphs
+        .iter()
+        .filter(|g| !g.is_not_miss())
+        .count()
+    };
+
+    assert_eq!(miss_count(&mut shaper), 4);
+    let mut missing: Vec<_> = shaper.font_db.borrow().missing_chars().collect();
+    missing.sort();
+    assert_eq!(missing, ['世', '你', '好', '界']);
+
+    // The cached result reports the missing characters again.
+    shaper.font_db.borrow_mut().clear_missing_chars();
+    assert_eq!(miss_count(&mut shaper), 4);
+    assert_eq!(shaper.font_db.borrow().missing_chars().count(), 4);
+
+    // The fallback of the script.
+    shaper.font_db.borrow_mut().set_script_fallback(
+      Script::Han,
+      &FontFace {
+        families: Box::new([FontFamily::Name("Noto Serif SC".into())]),
+        ..<_>::default()
+      },
+    );
+    // The results shaped before the fallback are dropped.
+    assert_eq!(miss_count(&mut shaper), 0);
+
+    // Discover the face from the database.
+    let mut font_db = shaper.font_db.borrow_mut();
+    font_db
+      .set_script_fallback(Script::Han, &FontFace { families: Box::new([]), ..<_>::default() });
+    font_db.set_discover_fallback(true);
+    font_db.clear_missing_chars();
+    drop(font_db);
+    assert_eq!(miss_count(&mut shaper), 0);
+    assert_eq!(shaper.font_db.borrow().missing_chars().count(), 0);
+  }
+
+  #[test]
+  fn shape_vertical() {
+    let mut shaper = TextShaper::new(<_>::default());
+    let path = env!("CARGO_MANIFEST_DIR").to_owned();
+    let mut font_db = shaper.font_db.borrow_mut();
+    let _ = font_db.load_font_file(path.clone() + "/../fonts/DejaVuSans.ttf");
+    let _ = font_db.load_font_file(path + "/../fonts/NotoSerifSC-Bold.你好世界.otf");
+    let ids = font_db.select_all_match(&FontFace {
+      families: Box::new([
+        FontFamily::Name("DejaVu Sans".into()),
+        FontFamily::Name("Noto Serif SC".into()),
+      ]),
+      ..<_>::default()
+    });
+    drop(font_db);
+
+    let text: Substr = "你好ab".into();
+    let dir = TextDirection::LeftToRight;
+    let horizontal = shaper.shape_text(&text, &ids, &[], dir, GlyphBaseline::Alphabetic);
+    let vertical = |shaper: &mut TextShaper, orientation| {
+      shaper.shape_vertical(&text, &ids, &[], orientation, dir, GlyphBaseline::Alphabetic)
+    };
+
+    let mixed = vertical(&mut shaper, TextOrientation::Mixed);
+    let rotated: Vec<_> = mixed.glyphs.iter().map(|g| g.rotated).collect();
+    assert_eq!(rotated, [false, false, true, true]);
+    assert!(
+      mixed
+        .glyphs
+        .iter()
+        .all(|g| g.y_advance > GlyphUnit::ZERO)
+    );
+    // The rotated glyphs advance as they are in the horizontal line.
+    assert_eq!(mixed.glyphs[3].y_advance, horizontal.glyphs[3].x_advance);
+    assert_eq!(mixed.glyphs[3].cluster, 7);
+
+    let upright = vertical(&mut shaper, TextOrientation::Upright);
+    assert!(upright.glyphs.iter().all(|g| !g.rotated));
+    let sideways = vertical(&mut shaper, TextOrientation::Sideways);
+    assert!(sideways.glyphs.iter().all(|g| g.rotated));
+  }
+
   #[test]
   fn shape_miss_font() {
     let mut shaper = TextShaper::new(<_>::default());
 
     let dir = TextDirection::LeftToRight;
-    let result = shaper.shape_text(&"你好世界".into(), &[], dir, GlyphBaseline::Alphabetic);
+    let result = shaper.shape_text(&"你好世界".into(), &[], &[], dir, GlyphBaseline::Alphabetic);
     assert_eq!(result.glyphs.len(), 4);
   }
 
@@ -497,6 +803,7 @@ mod tests {
       let res = shaper.shape_text(
         &text.substr(..),
         &ids,
+        &[],
         TextDirection::LeftToRight,
         GlyphBaseline::Alphabetic,
       );
@@ -523,6 +830,7 @@ mod tests {
       let res = shaper.shape_text(
         &text.substr(..),
         &ids,
+        &[],
         TextDirection::LeftToRight,
         GlyphBaseline::Alphabetic,
       );
@@ -557,9 +865,39 @@ mod tests {
     let res = shaper.shape_text(
       &"👨‍👩‍👦‍👦".into(),
       &ids_all,
+      &[],
       TextDirection::LeftToRight,
       GlyphBaseline::Alphabetic,
     );
     assert!(res.glyphs.len() == 7);
   }
+
+  #[test]
+  fn shape_with_features() {
+    let mut shaper = TextShaper::new(<_>::default());
+    let _ = shaper
+      .font_db
+      .borrow_mut()
+      .load_font_file(env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf");
+    let ids = shaper
+      .font_db
+      .borrow_mut()
+      .select_all_match(&FontFace {
+        families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+        ..<_>::default()
+      });
+    let text: Substr = "fi".into();
+    let dir = TextDirection::LeftToRight;
+    let baseline = GlyphBaseline::Alphabetic;
+
+    let liga = shaper.shape_text(&text, &ids, &[], dir, baseline);
+    let no_liga = shaper.shape_text(&text, &ids, &[FontFeature::off(b"liga")], dir, baseline);
+    assert_eq!(liga.glyphs.len(), 1);
+    assert_eq!(no_liga.glyphs.len(), 2);
+    assert!(
+      shaper
+        .get_cache(&text, &ids, &[FontFeature::off(b"liga")], dir, baseline)
+        .is_some()
+    );
+  }
 }
@@ -4,6 +4,8 @@ use ribir_algo::{FrameCache, Sc, Substr};
 use unicode_bidi::{BidiClass, BidiInfo, Level, LevelRun};
 
 pub struct Paragraph {
+  /// The levels of the bytes in the paragraph, indexed from the start of the
+  /// paragraph.
   pub levels: Vec<Level>,
   pub runs: Vec<LevelRun>,
   pub range: Range<usize>,
@@ -32,6 +34,7 @@ impl TextReorder {
         .iter()
         .map(|p| {
           let (levels, runs) = info.visual_runs(p, p.range.clone());
+          let levels = levels[p.range.clone()].to_vec();
           Paragraph { levels, runs, range: p.range.clone() }
         })
         .collect();
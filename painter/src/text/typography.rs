@@ -6,7 +6,10 @@ use smallvec::{SmallVec, smallvec};
 use unicode_script::{Script, UnicodeScript};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{Glyph, GlyphUnit, TextAlign, TextOverflow, shaper::ShapeResult};
+use crate::{
+  Glyph, GlyphUnit, Hyphenator, OverflowWrap, TextAlign, TextOverflow, WhiteSpace,
+  shaper::ShapeResult,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlaceLineDirection {
@@ -38,7 +41,7 @@ pub trait InlineCursor {
   fn measure(&self, glyph: &Glyph, origin_text: &str) -> GlyphUnit;
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct VisualLine {
   pub x: GlyphUnit,
   pub y: GlyphUnit,
@@ -46,6 +49,14 @@ pub struct VisualLine {
   pub width: GlyphUnit,
   /// The glyph position is relative the line x/y
   pub glyphs: Vec<Glyph>,
+  /// The indices of the glyphs that the justification can insert space before.
+  justify_gaps: Vec<usize>,
+  /// The count of the glyphs exclude the trailing spaces.
+  content_glyphs: usize,
+  /// The inline extent of the line exclude the trailing spaces.
+  content_extent: GlyphUnit,
+  /// If this line is the last line of a paragraph.
+  para_end: bool,
 }
 
 pub struct VisualInfos {
@@ -64,6 +75,12 @@ pub struct TypographyMan<Paras> {
   line_height: GlyphUnit,
   bounds: Size<GlyphUnit>,
   overflow: TextOverflow,
+  white_space: WhiteSpace,
+  /// The explicit tab stops relative to the line start, in ascending order.
+  tab_stops: Box<[GlyphUnit]>,
+  /// The interval of the tab stops after the explicit ones, the tabs are not
+  /// expanded if it's zero.
+  tab_size: GlyphUnit,
   /// Not directly use text as inputs, but accept glyphs after text shape
   /// because both simple text and rich text can custom compose its glyph runs
   /// by text reorder result and its style .
@@ -71,6 +88,8 @@ pub struct TypographyMan<Paras> {
   inline_cursor: GlyphUnit,
   visual_lines: SmallVec<[VisualLine; 1]>,
   over_bounds: bool,
+  /// The cluster and the char of the last glyph in the current line.
+  last_glyph: Option<(u32, char)>,
 }
 
 impl<Paras> TypographyMan<Paras>
@@ -87,24 +106,47 @@ where
       line_height,
       bounds,
       overflow,
+      white_space: WhiteSpace::default(),
+      tab_stops: Box::new([]),
+      tab_size: GlyphUnit::ZERO,
       inputs,
       inline_cursor: GlyphUnit::ZERO,
       visual_lines: smallvec![],
       over_bounds: false,
+      last_glyph: None,
     }
   }
 
+  /// Sets how the white spaces are handled, the collapsible spaces are not
+  /// placed and the lines don't wrap if the mode is no wrap.
+  pub fn with_white_space(mut self, white_space: WhiteSpace) -> Self {
+    self.white_space = white_space;
+    self
+  }
+
+  /// Sets the tab stops to expand the tabs to. A tab advances to the first
+  /// stop after it in `tab_stops`, and beyond them, to the next multiple of
+  /// `tab_size`. The positions are relative to the line start.
+  pub fn with_tab_stops(
+    mut self, tab_stops: impl Into<Box<[GlyphUnit]>>, tab_size: GlyphUnit,
+  ) -> Self {
+    self.tab_stops = tab_stops.into();
+    self.tab_size = tab_size;
+    self
+  }
+
   pub fn typography_all(mut self) -> VisualInfos {
     while let Some(p) = self.inputs.next() {
       self.consume_paragraph(p);
     }
 
-    if self.line_dir.is_reverse() {
+    // The vertical lines keep the logical order, and the `RightToLeft` lines are
+    // placed from the right.
+    if self.line_dir == PlaceLineDirection::BottomToTop {
       self.visual_lines.reverse();
     }
 
-    let visual_size = self.visual_size();
-    self.adjust_lines(visual_size);
+    let visual_size = self.adjust_lines();
 
     VisualInfos {
       visual_size,
@@ -115,7 +157,11 @@ where
     }
   }
 
-  fn adjust_lines(&mut self, visual_size: Size<GlyphUnit>) {
+  fn adjust_lines(&mut self) -> Size<GlyphUnit> {
+    if self.text_align == TextAlign::Justify {
+      self.justify_lines();
+    }
+    let visual_size = self.visual_size();
     let text_align = self.text_align;
     let lines = self.visual_lines.iter_mut();
     match self.line_dir {
@@ -153,6 +199,60 @@ where
         }
       });
     }
+    visual_size
+  }
+
+  /// Stretch the lines to the line width, except the last line of the
+  /// paragraphs. If the text is not auto wrap or the bounds is infinite, the
+  /// widest line is used as the line width.
+  fn justify_lines(&mut self) {
+    let hor = self.line_dir.is_horizontal();
+    let inline_bound = if hor { self.bounds.height } else { self.bounds.width };
+    let target = if self.is_auto_wrap() && inline_bound < GlyphUnit::MAX {
+      inline_bound
+    } else {
+      self
+        .visual_lines
+        .iter()
+        .map(|l| l.content_extent)
+        .max()
+        .unwrap_or_default()
+    };
+
+    for l in self
+      .visual_lines
+      .iter_mut()
+      .filter(|l| !l.para_end)
+    {
+      let content_glyphs = l.content_glyphs;
+      let gaps = l
+        .justify_gaps
+        .iter()
+        .take_while(|idx| **idx < content_glyphs)
+        .count();
+      if gaps == 0 || target <= l.content_extent {
+        continue;
+      }
+
+      let extra = (target - l.content_extent) / gaps as f32;
+      let mut shift = GlyphUnit::ZERO;
+      let mut gap_iter = l.justify_gaps[..gaps].iter().peekable();
+      for (idx, g) in l.glyphs.iter_mut().enumerate() {
+        while gap_iter.next_if(|i| **i == idx).is_some() {
+          shift += extra;
+        }
+        if hor {
+          g.y_offset += shift;
+        } else {
+          g.x_offset += shift;
+        }
+      }
+      if hor {
+        l.height = target;
+      } else {
+        l.width = target;
+      }
+    }
   }
 
   fn visual_size(&self) -> Size<GlyphUnit> {
@@ -188,6 +288,9 @@ where
         .for_each(|r| self.consume_run_with_letter_space_cursor(r, &mut cursor));
     }
     self.end_line();
+    if let Some(line) = self.visual_lines.last_mut() {
+      line.para_end = true;
+    }
 
     false
   }
@@ -209,51 +312,129 @@ where
     let text = run.text();
     let base = run.range.start as u32;
     let line_offset = (self.line_height - em) / 2.;
-    let is_auto_wrap = self.overflow.is_auto_wrap();
-
-    let new_line = |this: &mut Self, cursor: &mut dyn InlineCursor| {
+    let is_auto_wrap = self.is_auto_wrap();
+    let collapse = self.white_space.is_collapse();
+
+    let new_line = |this: &mut Self, cursor: &mut dyn InlineCursor, hyphen: Option<Glyph>| {
+      if let Some(mut hyphen) = hyphen {
+        cursor.advance_glyph(&mut hyphen, line_offset, text);
+        hyphen.cluster += base;
+        this.push_glyph(hyphen, '-', cursor.position());
+      }
       this.end_line();
       this.begin_line();
       cursor.reset();
     };
 
-    for word in run.word_glyphs() {
-      let width: GlyphUnit = word
+    let emergency_break = is_auto_wrap && run.overflow_wrap == OverflowWrap::Anywhere;
+    let hyphen_glyph = run
+      .hyphen
+      .as_ref()
+      .map(|h| h.clone().cast_to(font_size));
+    // The hyphen need to insert if the line breaks after the last segment.
+    let mut pending_hyphen = None;
+    for (idx, (segment, hyphenated)) in run.segment_glyphs().enumerate() {
+      // The trailing spaces of a segment hang at the end of the line, they are not
+      // considered when fitting the segment in the line.
+      let mut width: GlyphUnit = segment
         .clone()
+        .filter(|g| !is_space_glyph(g, text))
         .fold(GlyphUnit::ZERO, |acc, g| acc + cursor.measure(&g, text));
+      let hyphen = if hyphenated { hyphen_glyph.clone() } else { None };
+      if let Some(hyphen) = &hyphen {
+        width += cursor.measure(hyphen, text);
+      }
 
       if is_auto_wrap
+        && (idx > 0 || run.break_before)
         && self.inline_cursor != GlyphUnit::ZERO
         && self.is_over_line_bound(width + self.inline_cursor)
       {
-        new_line(self, cursor);
+        new_line(self, cursor, pending_hyphen.take());
       }
 
-      let mut word = word.peekable();
-      while let Some(g) = word.peek() {
+      let mut segment = segment.peekable();
+      let mut last_cluster = 0;
+      while let Some(g) = segment.peek() {
         let mut at = (*g).clone();
+        let c = text[at.cluster as usize..]
+          .chars()
+          .next()
+          .unwrap();
+        let hang = c.is_whitespace();
+        last_cluster = at.cluster;
+        if collapse
+          && is_collapsible(c)
+          && self
+            .last_glyph
+            .is_none_or(|(_, p)| is_collapsible(p))
+        {
+          segment.next();
+          continue;
+        }
+        if c == '\t' && !collapse {
+          self.expand_tab(&mut at, cursor, text);
+        }
 
         cursor.advance_glyph(&mut at, line_offset, text);
 
         at.cluster += base;
 
         if self.inline_cursor == GlyphUnit::ZERO
-          || !is_auto_wrap
+          || !emergency_break
+          || hang
           || !self.is_over_line_bound(cursor.position())
         {
-          self.push_glyph(at);
-          self.inline_cursor = cursor.position();
-          word.next();
+          self.push_glyph(at, c, cursor.position());
+          segment.next();
         } else {
-          new_line(self, cursor);
+          new_line(self, cursor, None);
         }
       }
+      pending_hyphen = hyphen.map(|mut h| {
+        h.cluster = last_cluster;
+        h
+      });
     }
   }
 
-  fn push_glyph(&mut self, g: Glyph) {
-    let line = self.visual_lines.last_mut();
-    line.unwrap().glyphs.push(g)
+  fn is_auto_wrap(&self) -> bool { self.overflow.is_auto_wrap() && !self.white_space.is_nowrap() }
+
+  /// Set the advance of the tab glyph to reach the next tab stop.
+  fn expand_tab(&self, tab: &mut Glyph, cursor: &impl InlineCursor, text: &str) {
+    let pos = cursor.position();
+    let stop = match self.tab_stops.iter().find(|s| **s > pos) {
+      Some(stop) => *stop,
+      None if self.tab_size > GlyphUnit::ZERO => {
+        GlyphUnit::new((pos.0 / self.tab_size.0 + 1) * self.tab_size.0)
+      }
+      None => return,
+    };
+    // The lines placed horizontally are vertical text.
+    let vertical = self.line_dir.is_horizontal();
+    let set_advance = |g: &mut Glyph, v| {
+      if vertical { g.y_advance = v } else { g.x_advance = v }
+    };
+    set_advance(tab, GlyphUnit::ZERO);
+    // The cursor may add the letter space after the tab.
+    let spacing = cursor.measure(tab, text);
+    set_advance(tab, stop - pos - spacing);
+  }
+
+  fn push_glyph(&mut self, g: Glyph, c: char, position: GlyphUnit) {
+    let line = self.visual_lines.last_mut().unwrap();
+    if let Some((cluster, prev)) = self.last_glyph {
+      if cluster != g.cluster && (prev.is_whitespace() || is_cjk(prev) || is_cjk(c)) {
+        line.justify_gaps.push(line.glyphs.len());
+      }
+    }
+    self.last_glyph = Some((g.cluster, c));
+    line.glyphs.push(g);
+    if !c.is_whitespace() {
+      line.content_glyphs = line.glyphs.len();
+      line.content_extent = position;
+    }
+    self.inline_cursor = position;
   }
 
   fn begin_line(&mut self) {
@@ -277,6 +458,7 @@ where
     self.over_bounds |= self.is_over_line_bound(self.inline_cursor);
     self.over_bounds |= self.is_last_line_over();
     self.inline_cursor = GlyphUnit::ZERO;
+    self.last_glyph = None;
   }
 
   fn is_over_line_bound(&self, position: GlyphUnit) -> bool {
@@ -314,7 +496,20 @@ pub struct InputRun {
   pub(crate) font_size_factor: f32,
   pub(crate) letter_space: GlyphUnit,
   pub(crate) range: Range<usize>,
-  reorder_text: String,
+  pub(crate) overflow_wrap: OverflowWrap,
+  segments: Vec<Segment>,
+  /// Whether the line is allowed to break before the run, it depends on the
+  /// text before the run.
+  break_before: bool,
+  /// The hyphen glyph to insert when the line breaks at a hyphenation point.
+  hyphen: Option<Glyph>,
+}
+
+/// The glyphs that the line can't break in.
+struct Segment {
+  glyphs: Range<usize>,
+  /// If the line breaks after this segment, a hyphen should be inserted.
+  hyphen: bool,
 }
 
 pub struct HInlineCursor {
@@ -356,7 +551,9 @@ impl InlineCursor for VInlineCursor {
   fn advance_glyph(&mut self, g: &mut Glyph, line_offset: GlyphUnit, _: &str) {
     g.x_offset += line_offset;
     g.y_offset += self.pos;
-    self.pos = g.y_offset + g.y_advance;
+    // The vertical glyphs have an offset to their em box, that not affects the
+    // advance.
+    self.pos += g.y_advance;
   }
 
   fn advance(&mut self, c: GlyphUnit) { self.pos += c; }
@@ -437,7 +634,7 @@ pub(crate) fn text_align_offset(
   content: GlyphUnit, container: GlyphUnit, text_align: TextAlign,
 ) -> GlyphUnit {
   match text_align {
-    TextAlign::Start => GlyphUnit::ZERO,
+    TextAlign::Start | TextAlign::Justify => GlyphUnit::ZERO,
     TextAlign::Center => (container - content) / 2.,
     TextAlign::End => container - content,
   }
@@ -469,39 +666,117 @@ fn letter_spacing_char(c: char) -> bool {
 }
 
 impl InputRun {
+  /// Create a run of the `range` of the text, the `breaks` are the line break
+  /// opportunities of the whole paragraph in the same text, in ascending order.
   pub(crate) fn new(
     shape_result: Sc<ShapeResult>, font_size_factor: f32, letter_space: GlyphUnit,
-    range: Range<usize>,
+    range: Range<usize>, breaks: &[usize], overflow_wrap: OverflowWrap,
   ) -> Self {
-    let text: &str = &shape_result.text;
-    // text and glyphs in run may in different order, so we recollect the chars.
-    // and reorder_text may smaller then src text when have composited glyph,
-    // like '👨‍👩‍👦‍👦'
-    let reorder_text = shape_result
-      .glyphs
-      .iter()
-      .filter_map(|gh| text[gh.cluster as usize..].chars().next())
+    let glyphs = &shape_result.glyphs;
+    let mut segments = vec![];
+    let mut start = 0;
+    for idx in 1..glyphs.len() {
+      if is_break_before(glyphs, idx, breaks, range.start) {
+        segments.push(Segment { glyphs: start..idx, hyphen: false });
+        start = idx;
+      }
+    }
+    if start < glyphs.len() {
+      segments.push(Segment { glyphs: start..glyphs.len(), hyphen: false });
+    }
+
+    Self {
+      break_before: breaks.binary_search(&range.start).is_ok(),
+      shape_result,
+      font_size_factor,
+      letter_space,
+      range,
+      overflow_wrap,
+      segments,
+      hyphen: None,
+    }
+  }
+
+  /// Allow the line to break at the soft hyphens and the hyphenation points
+  /// found by `hyphenator`, and insert the `hyphen` glyph when the line breaks
+  /// there.
+  pub(crate) fn with_hyphens(mut self, hyphen: Glyph, hyphenator: Option<&Hyphenator>) -> Self {
+    let text: &str = &self.shape_result.text;
+    let mut points: Vec<usize> = text
+      .match_indices('\u{AD}')
+      .map(|(idx, s)| idx + s.len())
       .collect();
-    Self { shape_result, font_size_factor, letter_space, range, reorder_text }
+    if let Some(hyphenator) = hyphenator {
+      for (offset, word) in text.split_word_bound_indices() {
+        if word.chars().all(char::is_alphabetic) {
+          points.extend(
+            hyphenator
+              .hyphenate(word)
+              .into_iter()
+              .map(|p| p + offset),
+          );
+        }
+      }
+      points.sort_unstable();
+    }
+
+    let glyphs = &self.shape_result.glyphs;
+    let mut segments = Vec::with_capacity(self.segments.len());
+    for seg in self.segments.drain(..) {
+      let Range { mut start, end } = seg.glyphs;
+      for idx in start + 1..end {
+        if is_break_before(glyphs, idx, &points, 0) {
+          segments.push(Segment { glyphs: start..idx, hyphen: true });
+          start = idx;
+        }
+      }
+      let hyphen = seg.hyphen || (end < glyphs.len() && is_break_before(glyphs, end, &points, 0));
+      segments.push(Segment { glyphs: start..end, hyphen });
+    }
+    self.segments = segments;
+    self.hyphen = Some(hyphen);
+    self
   }
 
   #[inline]
   fn text(&self) -> &str { &self.shape_result.text }
 
-  fn word_glyphs(&self) -> impl Iterator<Item = impl Iterator<Item = Glyph> + Clone + '_> + '_ {
-    let Self { reorder_text, font_size_factor, shape_result, .. } = self;
+  fn segment_glyphs(
+    &self,
+  ) -> impl Iterator<Item = (impl Iterator<Item = Glyph> + Clone + '_, bool)> + '_ {
+    let Self { segments, font_size_factor, shape_result, .. } = self;
     let font_size = *font_size_factor * GlyphUnit::PIXELS_PER_EM as f32;
-    reorder_text
-      .split_word_bounds()
-      .scan(0, move |init, w| {
-        let base = *init;
-        *init += w.chars().count();
-        Some(
-          w.chars()
-            .enumerate()
-            .filter_map(move |(idx, _)| shape_result.glyphs.get(base + idx))
-            .map(move |g| g.clone().cast_to(font_size)),
-        )
-      })
+    segments.iter().map(move |seg| {
+      let glyphs = shape_result.glyphs[seg.glyphs.clone()]
+        .iter()
+        .map(move |g| g.clone().cast_to(font_size));
+      (glyphs, seg.hyphen)
+    })
   }
 }
+
+/// Glyphs may in a different order of the text, and a cluster may have
+/// multiple glyphs. So the line can break before the glyph at `idx` only if
+/// the cluster changed and the text is allowed to break between the two
+/// clusters. The clusters are relative to the run that starts at `base` of the
+/// text.
+fn is_break_before(glyphs: &[Glyph], idx: usize, breaks: &[usize], base: usize) -> bool {
+  let prev = glyphs[idx - 1].cluster as usize;
+  let cur = glyphs[idx].cluster as usize;
+  prev != cur && breaks.binary_search(&(prev.max(cur) + base)).is_ok()
+}
+
+fn is_cjk(c: char) -> bool {
+  matches!(c.script(), Script::Han | Script::Hiragana | Script::Katakana | Script::Bopomofo)
+}
+
+/// The spaces that collapse into one if the white spaces are not preserved.
+fn is_collapsible(c: char) -> bool { c == ' ' || c == '\t' }
+
+/// The space glyph hang at the end of the line.
+fn is_space_glyph(g: &Glyph, text: &str) -> bool {
+  text[g.cluster as usize..]
+    .chars()
+    .next()
+    .is_some_and(char::is_whitespace)
+}
@@ -1,13 +1,16 @@
-use std::{cell::RefCell, ops::Range};
+use std::{borrow::Cow, cell::RefCell, ops::Range};
 
 use font_db::GlyphBaseline;
 use ribir_algo::{FrameCache, Sc, Substr};
 use ribir_geom::{Point, Rect, Size};
+use smallvec::SmallVec;
+use unicode_bidi::{BidiClass, Level};
 
 use crate::{
   font_db::FontDB,
+  line_break::line_break_opportunities,
   shaper::{NEWLINE_GLYPH_ID, TextShaper},
-  text_reorder::ReorderResult,
+  text_reorder::{Paragraph, ReorderResult},
   typography::*,
   *,
 };
@@ -17,6 +20,15 @@ struct RunKey {
   pub ids: Box<[ID]>,
   pub line_height: GlyphUnit,
   pub letter_space: GlyphUnit,
+  pub word_break: WordBreak,
+  pub overflow_wrap: OverflowWrap,
+  pub hyphens: Hyphens,
+  pub lang: Option<Cow<'static, str>>,
+  pub features: Box<[FontFeature]>,
+  pub orientation: TextOrientation,
+  pub white_space: WhiteSpace,
+  pub tab_size: GlyphUnit,
+  pub tab_stops: Box<[GlyphUnit]>,
   pub text: Substr,
 }
 
@@ -39,6 +51,10 @@ pub struct TypographyStore {
   shaper: TextShaper,
   font_db: Sc<RefCell<FontDB>>,
   cache: FrameCache<TypographyKey, Sc<VisualInfos>>,
+  hyphenators: ahash::HashMap<Cow<'static, str>, Hyphenator>,
+  /// The fallback generation of the font database that the cache is laid out
+  /// with.
+  fallback_generation: usize,
 }
 
 #[derive(Clone)]
@@ -78,13 +94,133 @@ impl VisualGlyphs {
   }
 
   pub fn font_size(&self) -> f32 { self.font_size }
+
+  /// Stack the glyphs of the paragraphs laid out one by one from top to
+  /// bottom, as the glyphs of the whole text.
+  ///
+  /// Every item is the byte offset of a paragraph in the whole text and its
+  /// glyphs. The bytes between the end of a paragraph and the start of the
+  /// next one are the line break, a newline glyph is appended to the last line
+  /// of the paragraph for them. `len` is the byte length of the whole text.
+  ///
+  /// The paragraphs must be horizontal lines of the same font size, and
+  /// `paras` must not be empty.
+  pub fn stack_paras(paras: &[(usize, VisualGlyphs)], len: usize) -> Self {
+    let first = &paras[0].1;
+    let mut original_classes = Vec::with_capacity(len);
+    let mut reorder_paras = Vec::with_capacity(paras.len());
+    let mut visual_lines = SmallVec::new();
+    let mut visual_size = Size::new(GlyphUnit::ZERO, GlyphUnit::ZERO);
+    let mut over_bounds = false;
+
+    for (i, (start, glyphs)) in paras.iter().enumerate() {
+      let start = *start;
+      let end = paras.get(i + 1).map_or(len, |(next, _)| *next);
+      let order = &glyphs.order_info;
+      let text_end = start + order.paras.last().map_or(0, |p| p.range.end);
+      let para_level = order
+        .paras
+        .iter()
+        .flat_map(|p| p.levels.iter())
+        .min()
+        .copied()
+        .unwrap_or_else(Level::ltr);
+
+      let mut levels: Vec<_> = order
+        .paras
+        .iter()
+        .flat_map(|p| p.levels.iter())
+        .copied()
+        .collect();
+      levels.resize(end - start, para_level);
+      original_classes.extend(order.original_classes.iter().copied());
+      original_classes.resize(text_end, BidiClass::L);
+      original_classes.resize(end, BidiClass::B);
+      let mut runs: Vec<_> = order
+        .paras
+        .iter()
+        .flat_map(|p| p.runs.iter())
+        .filter(|r| !r.is_empty())
+        .map(|r| r.start + start..r.end + start)
+        .collect();
+      if text_end < end || runs.is_empty() {
+        runs.push(text_end..end);
+      }
+      reorder_paras.push(Paragraph { levels, runs, range: start..end });
+
+      let info = &glyphs.visual_info;
+      over_bounds |= info.over_bounds;
+      let top = visual_size.height;
+      let line_count = info.visual_lines.len();
+      visual_lines.extend(
+        info
+          .visual_lines
+          .iter()
+          .enumerate()
+          .map(|(idx, line)| {
+            let mut line = line.clone();
+            line.y += top;
+            line
+              .glyphs
+              .iter_mut()
+              .for_each(|g| g.cluster += start as u32);
+            if text_end < end && idx + 1 == line_count {
+              let last = line.glyphs.last();
+              let newline = Glyph {
+                face_id: last.map_or_else(ID::dummy, |g| g.face_id),
+                x_advance: GlyphUnit::ZERO,
+                y_advance: line.height,
+                x_offset: last.map_or(GlyphUnit::ZERO, |g| g.x_offset + g.x_advance),
+                y_offset: GlyphUnit::ZERO,
+                glyph_id: NEWLINE_GLYPH_ID,
+                cluster: text_end as u32,
+                rotated: false,
+              };
+              line.glyphs.push(newline);
+            }
+            line
+          }),
+      );
+      visual_size.width = visual_size.width.max(info.visual_size.width);
+      visual_size.height += info.visual_size.height;
+    }
+
+    let order_info = ReorderResult { original_classes, paras: reorder_paras };
+    let visual_info = VisualInfos {
+      visual_lines,
+      text_align: first.visual_info.text_align,
+      over_bounds,
+      line_dir: first.visual_info.line_dir,
+      visual_size,
+    };
+    VisualGlyphs::new(first.font_size, Sc::new(order_info), Sc::new(visual_info))
+  }
 }
 
 impl TypographyStore {
   pub fn new(font_db: Sc<RefCell<FontDB>>) -> Self {
     let reorder = TextReorder::default();
     let shaper = TextShaper::new(font_db.clone());
-    TypographyStore { reorder, shaper, font_db, cache: <_>::default() }
+    let fallback_generation = font_db.borrow().fallback_generation();
+    TypographyStore {
+      reorder,
+      shaper,
+      font_db,
+      cache: <_>::default(),
+      hyphenators: <_>::default(),
+      fallback_generation,
+    }
+  }
+
+  /// Register the hyphenator for a language, the text with
+  /// [`Hyphens::Auto`] and the language will be hyphenated by it.
+  ///
+  /// The `lang` is a BCP 47 tag. A text of `en-US` will use the hyphenator
+  /// of `en` if there is no hyphenator of `en-US`.
+  pub fn set_hyphenator(&mut self, lang: impl Into<Cow<'static, str>>, hyphenator: Hyphenator) {
+    self.hyphenators.insert(lang.into(), hyphenator);
+    // The cached typography may be hyphenated by the old hyphenator.
+    self.cache.clear();
   }
 
   pub fn end_frame(&mut self) {
@@ -98,8 +234,27 @@ impl TypographyStore {
     &mut self, text: Substr, style: &TextStyle, bounds: Size, mut text_align: TextAlign,
     baseline: GlyphBaseline, line_dir: PlaceLineDirection,
   ) -> VisualGlyphs {
-    let TextStyle { font_size, ref font_face, letter_space, line_height, overflow } = *style;
-    if text_align != TextAlign::Start {
+    let TextStyle {
+      font_size,
+      ref font_face,
+      letter_space,
+      line_height,
+      overflow,
+      word_break,
+      overflow_wrap,
+      hyphens,
+      ref lang,
+      ref font_features,
+      ref font_variations,
+      font_palette,
+      writing_mode: _,
+      text_orientation,
+      white_space,
+      tab_size,
+      ref tab_stops,
+    } = *style;
+    let overflow = if white_space.is_nowrap() { TextOverflow::Overflow } else { overflow };
+    if !matches!(text_align, TextAlign::Start | TextAlign::Justify) {
       // If the text align is not start, we must ensure that the bounds are finite.
       if (!bounds.width.is_finite() && !line_dir.is_horizontal())
         || (!bounds.height.is_finite() && line_dir.is_horizontal())
@@ -120,37 +275,143 @@ impl TypographyStore {
       GlyphUnit::from_pixel(line_height / font_size * GlyphUnit::PIXELS_PER_EM as f32);
 
     let info = self.reorder.reorder_text(&text).clone();
-    let ids = self
-      .font_db
-      .borrow_mut()
-      .select_all_match(font_face)
-      .into_boxed_slice();
-    let runs = [RunKey { ids, line_height, letter_space, text }].into();
+    let mut variations = font_variations.to_vec();
+    if !variations.iter().any(|v| &v.tag == b"wght") {
+      variations.push(FontVariation::new(b"wght", font_face.weight.0 as f32));
+    }
+    let mut font_db = self.font_db.borrow_mut();
+    let mut ids = font_db.select_all_match(font_face);
+    if let Some(lang) = lang.as_deref() {
+      let fallback = font_db.lang_fallback(lang);
+      ids.extend(
+        fallback
+          .iter()
+          .filter(|id| !ids.contains(id))
+          .copied()
+          .collect::<Vec<_>>(),
+      );
+    }
+    let ids: Box<[ID]> = ids
+      .into_iter()
+      .map(|id| {
+        let id = font_db.variation_instance(id, &variations);
+        font_db.palette_instance(id, font_palette)
+      })
+      .collect();
+    drop(font_db);
+    let has_tab = !white_space.is_collapse() && text.contains('\t');
+    let tab_stops: Box<[GlyphUnit]> = if has_tab {
+      tab_stops
+        .iter()
+        .map(|px| GlyphUnit::from_pixel(px / scale))
+        .collect()
+    } else {
+      Box::new([])
+    };
+    let tab_size = if has_tab {
+      match tab_size {
+        TabSize::Pixels(px) => GlyphUnit::from_pixel(px / scale),
+        TabSize::Spaces(n) => {
+          let dir = TextDirection::LeftToRight;
+          let space = if line_dir.is_horizontal() {
+            let res = self.shaper.shape_vertical(
+              &" ".into(),
+              &ids,
+              font_features,
+              text_orientation,
+              dir,
+              baseline,
+            );
+            res.glyphs.first().map(|g| g.y_advance)
+          } else {
+            let res = self
+              .shaper
+              .shape_text(&" ".into(), &ids, font_features, dir, baseline);
+            res.glyphs.first().map(|g| g.x_advance)
+          };
+          (space.unwrap_or_default() + letter_space) * n as i32
+        }
+      }
+    } else {
+      GlyphUnit::ZERO
+    };
+    let runs = [RunKey {
+      ids,
+      line_height,
+      letter_space,
+      word_break,
+      overflow_wrap,
+      hyphens,
+      lang: lang.clone(),
+      features: font_features.clone(),
+      orientation: text_orientation,
+      white_space,
+      tab_size,
+      tab_stops,
+      text,
+    }]
+    .into();
     let key = TypographyKey::new(runs, bounds, text_align, line_dir, overflow, baseline);
+    // The cached typography may be shaped by the old fallback fonts.
+    let generation = self.font_db.borrow().fallback_generation();
+    if self.fallback_generation != generation {
+      self.fallback_generation = generation;
+      self.cache.clear();
+    }
     let infos = if let Some(infos) = self.cache.get(&key).cloned() {
       infos
     } else {
       let ids = &key.runs[0].ids;
+      let features = &key.runs[0].features;
       let text = &key.runs[0].text;
+      let hyphenator = if hyphens == Hyphens::Auto {
+        lang
+          .as_deref()
+          .and_then(|lang| find_hyphenator(&self.hyphenators, lang))
+      } else {
+        None
+      };
       let inputs = info.paras.iter().map(|p| {
+        // The break opportunities depend on the text around, find them in the
+        // whole paragraph instead of every run.
+        let breaks: Vec<usize> = line_break_opportunities(&text[p.range.clone()], word_break)
+          .into_iter()
+          .map(|idx| idx + p.range.start)
+          .collect();
         p.runs
           .iter()
           .map(|r| {
-            let dir = if r.is_empty() || p.levels[r.start].is_ltr() {
+            let dir = if r.is_empty() || p.levels[r.start - p.range.start].is_ltr() {
               TextDirection::LeftToRight
             } else {
               TextDirection::RightToLeft
             };
 
-            let shape_result = self
-              .shaper
-              .shape_text(&text.substr(r.clone()), ids, dir, baseline);
-            InputRun::new(shape_result, 1., letter_space, r.clone())
+            // The lines are vertical if they are placed horizontally.
+            let shape = |shaper: &mut TextShaper, text: &Substr| {
+              if line_dir.is_horizontal() {
+                shaper.shape_vertical(text, ids, features, text_orientation, dir, baseline)
+              } else {
+                shaper.shape_text(text, ids, features, dir, baseline)
+              }
+            };
+            let shape_result = shape(&mut self.shaper, &text.substr(r.clone()));
+            let run =
+              InputRun::new(shape_result, 1., letter_space, r.clone(), &breaks, overflow_wrap);
+            let hyphen = (hyphens != Hyphens::None)
+              .then(|| shape(&mut self.shaper, &"-".into()))
+              .and_then(|res| res.glyphs.first().cloned());
+            match hyphen {
+              Some(hyphen) => run.with_hyphens(hyphen, hyphenator),
+              None => run,
+            }
           })
           .collect()
       });
 
-      let t_man = TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow);
+      let t_man = TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow)
+        .with_white_space(white_space)
+        .with_tab_stops(key.runs[0].tab_stops.clone(), tab_size);
       let visual_info = t_man.typography_all();
       let infos = Sc::new(visual_info);
       self.cache.put(key, infos.clone());
@@ -181,6 +442,9 @@ impl VisualGlyphs {
     let scale = self.font_size / GlyphUnit::PIXELS_PER_EM as f32;
     let x = GlyphUnit::from_pixel(offset_x / scale) - self.x;
     let y = GlyphUnit::from_pixel(offset_y / scale) - self.y;
+    if !self.is_horizontal_line() {
+      return self.nearest_vertical_glyph(x, y);
+    }
     let mut bottom = self.visual_info.visual_size.height;
 
     let mut iter = self
@@ -209,6 +473,38 @@ impl VisualGlyphs {
     (0, 0)
   }
 
+  /// The nearest glyph of the vertical lines, the lines are placed in the
+  /// visual order from left to right.
+  fn nearest_vertical_glyph(&self, x: GlyphUnit, y: GlyphUnit) -> (usize, usize) {
+    let distance = |l: &VisualLine| {
+      if x < l.x {
+        l.x - x
+      } else if l.x + l.width <= x {
+        x - l.x - l.width
+      } else {
+        GlyphUnit::ZERO
+      }
+    };
+    let Some((row, line)) = self
+      .visual_info
+      .visual_lines
+      .iter()
+      .enumerate()
+      .min_by_key(|(_, l)| distance(l))
+    else {
+      return (0, 0);
+    };
+    let idx = line
+      .glyphs
+      .iter()
+      .enumerate()
+      .rev()
+      .find(|(_, g)| GlyphUnit::ZERO < g.y_advance && g.y_offset <= y)
+      .map(|(i, _)| i)
+      .unwrap_or(0);
+    (row, idx)
+  }
+
   pub fn position_by_cluster(&self, cluster: usize) -> (usize, usize) {
     struct RangeLocator<'a> {
       ranges: Vec<(&'a Range<usize>, usize)>,
@@ -250,7 +546,10 @@ impl VisualGlyphs {
     let order_info = &self.order_info.paras[para];
     let locator = RangeLocator::from_unorder_ranges(order_info.runs.iter());
     let dst_run = locator.range_index(cluster);
-    let is_ltr = dst_run.is_none_or(|run| order_info.levels[order_info.runs[run].start].is_ltr());
+    let is_ltr = dst_run.is_none_or(|run| {
+      let at = order_info.runs[run].start - order_info.range.start;
+      order_info.levels[at].is_ltr()
+    });
     let is_layout_before = |glyph_cluster: usize| {
       if dst_run.is_none() {
         return true;
@@ -333,10 +632,11 @@ impl VisualGlyphs {
         ),
       },
       |glyph| {
-        let origin = Point::new(
-          self.to_pixel_value(glyph.x_offset + line.x),
-          self.to_pixel_value(glyph.y_offset + line.y),
-        );
+        // The glyph of a vertical line is centered in the line, the rect is the
+        // whole line width.
+        let x = if line_dir.is_horizontal() { line.x } else { glyph.x_offset + line.x };
+        let origin =
+          Point::new(self.to_pixel_value(x), self.to_pixel_value(glyph.y_offset + line.y));
         let size = match line_dir.is_horizontal() {
           true => Size::new(self.to_pixel_value(line.width), self.to_pixel_value(glyph.y_advance)),
           false => {
@@ -358,6 +658,22 @@ impl VisualGlyphs {
       .map_or(0., |line| self.to_pixel_value(line.height))
   }
 
+  /// Return the alphabetic baseline of the line `para` in pixel, relative to
+  /// the top of the visual rect's coordinate. Return `None` if the line is not
+  /// a horizontal line or has no glyph.
+  pub fn line_baseline(&self, para: usize) -> Option<f32> {
+    if !self.is_horizontal_line() {
+      return None;
+    }
+    let line = self.visual_info.visual_lines.get(para)?;
+    let glyph = line.glyphs.first()?;
+    // The glyph is drawn in an em box, the baseline is at the bottom of the box.
+    Some(self.to_pixel_value(self.y + line.y + glyph.y_offset) + self.font_size)
+  }
+
+  /// The number of the visual lines.
+  pub fn line_count(&self) -> usize { self.visual_info.visual_lines.len() }
+
   pub fn select_range(&self, rg: &Range<usize>) -> Vec<Rect> {
     let mut rects = vec![];
     for line in &self.visual_info.visual_lines {
@@ -477,6 +793,15 @@ impl VisualGlyphs {
   fn is_horizontal_line(&self) -> bool { !self.visual_info.line_dir.is_horizontal() }
 }
 
+fn find_hyphenator<'a>(
+  hyphenators: &'a ahash::HashMap<Cow<'static, str>, Hyphenator>, lang: &str,
+) -> Option<&'a Hyphenator> {
+  hyphenators.get(lang).or_else(|| {
+    let primary = lang.split(['-', '_']).next()?;
+    hyphenators.get(primary)
+  })
+}
+
 impl TypographyKey {
   fn new(
     runs: Box<[RunKey]>, bounds: Size<GlyphUnit>, text_align: TextAlign,
@@ -520,7 +845,14 @@ mod tests {
     FontFace { families: Box::new([FontFamily::Name("DejaVu Sans".into())]), ..<_>::default() }
   }
   fn text_style(font_size: f32, overflow: TextOverflow, letter_space: f32) -> TextStyle {
-    TextStyle { font_size, font_face: test_face(), letter_space, line_height: font_size, overflow }
+    TextStyle {
+      font_size,
+      font_face: test_face(),
+      letter_space,
+      line_height: font_size,
+      overflow,
+      ..<_>::default()
+    }
   }
   fn zero_letter_space_style(font_size: f32, overflow: TextOverflow) -> TextStyle {
     text_style(font_size, overflow, 0.)
@@ -586,6 +918,38 @@ mod tests {
     assert_eq!(visual.visual_rect().size, Size::new(34.164063, 28.));
   }
 
+  #[test]
+  fn stack_paras_as_whole_text() {
+    let text: Substr = "let a;\n\nb\nc".into();
+    let style = zero_letter_space_style(14., TextOverflow::Overflow);
+    let bounds = Size::new(f32::MAX, f32::MAX);
+    let mut store = test_store();
+    let mut layout = |text: Substr| {
+      let (align, base, dir) =
+        (TextAlign::Start, GlyphBaseline::Alphabetic, PlaceLineDirection::TopToBottom);
+      store.typography(text, &style, bounds, align, base, dir)
+    };
+    let whole = layout(text.clone());
+    let paras: Vec<_> = [(0, 6), (7, 7), (8, 9), (10, 11)]
+      .into_iter()
+      .map(|(start, end)| (start, layout(text.substr(start..end))))
+      .collect();
+    let stacked = VisualGlyphs::stack_paras(&paras, text.len());
+
+    // The whole text gives the advance of the line breaks to the width.
+    assert_eq!(stacked.visual_rect().height(), whole.visual_rect().height());
+    assert_eq!(stacked.line_count(), whole.line_count());
+    for at in 0..=text.len() {
+      let pos = stacked.position_by_cluster(at);
+      assert_eq!(pos, whole.position_by_cluster(at), "cluster {at}");
+      assert_eq!(stacked.position_to_cluster(pos.0, pos.1), whole.position_to_cluster(pos.0, pos.1));
+    }
+    for row in 0..whole.line_count() {
+      assert_eq!(stacked.glyph_count(row, true), whole.glyph_count(row, true));
+      assert_eq!(stacked.line_height(row), whole.line_height(row));
+    }
+  }
+
   #[test]
   fn simple_typography_text() {
     fn glyphs(
@@ -771,6 +1135,21 @@ mod tests {
 
     assert_eq!(store.cache.len(), 1);
 
+    // The typography is laid out again after the fallback fonts change.
+    store
+      .font_db
+      .borrow_mut()
+      .set_lang_fallback("ja", &FontFace::default());
+    store.typography(
+      "ok".into(),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+    assert_eq!(store.cache.len(), 1);
+
     store.end_frame();
     store.end_frame();
 
@@ -832,6 +1211,118 @@ mod tests {
     );
   }
 
+  #[test]
+  fn cjk_line_break() {
+    let style = zero_letter_space_style(16., TextOverflow::AutoWrap);
+    let char_width = typography_text(
+      "你".into(),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    )
+    .visual_rect()
+    .width();
+    let bounds = Size::new(char_width * 4.5, f32::MAX);
+    let wrap = |text: &'static str, style: &TextStyle| {
+      typography_text(text.into(), style, bounds, TextAlign::Start, PlaceLineDirection::TopToBottom)
+    };
+
+    // "。" can't start a line, so "界" wraps with it.
+    let glyphs = wrap("你好世界。", &style);
+    assert_eq!(glyphs.position_by_cluster("你好世".len()), (1, 0));
+
+    let keep_all = style.clone().with_word_break(WordBreak::KeepAll);
+    let glyphs = wrap("你好 世界", &keep_all);
+    assert_eq!(glyphs.position_by_cluster("你好 ".len()), (1, 0));
+
+    let glyphs = wrap("一二三四五六", &keep_all);
+    assert_eq!(glyphs.position_by_cluster(4 * "一".len()), (1, 0));
+
+    let overflow = keep_all.with_overflow_wrap(OverflowWrap::Normal);
+    let glyphs = wrap("一二三四五六", &overflow);
+    assert_eq!(glyphs.position_by_cluster(5 * "一".len()), (0, 5));
+  }
+
+  #[test]
+  fn justify_lines() {
+    let style = zero_letter_space_style(16., TextOverflow::AutoWrap);
+    let text = "aa bb cc dd\nee ff";
+    let width = typography_text(
+      "aa bb cc".into(),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    )
+    .visual_rect()
+    .width()
+      + 10.;
+    let bounds = Size::new(width, f32::MAX);
+    let justify = typography_text(
+      text.into(),
+      &style,
+      bounds,
+      TextAlign::Justify,
+      PlaceLineDirection::TopToBottom,
+    );
+    let start = typography_text(
+      text.into(),
+      &style,
+      bounds,
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+
+    let lines = &justify.visual_info.visual_lines;
+    assert_eq!(lines.len(), 3);
+    // The first line is stretched to the bounds.
+    let last = &lines[0].glyphs["aa bb cc".len() - 1];
+    let right =
+      (last.x_offset + last.x_advance).into_pixel() * 16. / GlyphUnit::PIXELS_PER_EM as f32;
+    assert!((right - width).abs() < 0.1);
+    // The last lines of the paragraphs are not justified.
+    let start_lines = &start.visual_info.visual_lines;
+    for (l1, l2) in lines.iter().zip(start_lines.iter()).skip(1) {
+      assert_eq!(l1.glyphs, l2.glyphs);
+    }
+  }
+
+  #[test]
+  fn hyphenate_at_break() {
+    let mut store = test_store();
+    store.set_hyphenator("en", Hyphenator::new("hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n"));
+    let style = zero_letter_space_style(16., TextOverflow::AutoWrap)
+      .with_hyphens(Hyphens::Auto)
+      .with_lang("en-US")
+      .with_overflow_wrap(OverflowWrap::Normal);
+    let width = store
+      .typography(
+        "hyphen-".into(),
+        &style,
+        Size::new(f32::MAX, f32::MAX),
+        TextAlign::Start,
+        GlyphBaseline::Alphabetic,
+        PlaceLineDirection::TopToBottom,
+      )
+      .visual_rect()
+      .width();
+
+    let glyphs = store.typography(
+      "hyphenation".into(),
+      &style,
+      Size::new(width + 1., f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+    let lines = &glyphs.visual_info.visual_lines;
+    assert_eq!(lines.len(), 2);
+    // "hyphen" with an inserted hyphen glyph.
+    assert_eq!(lines[0].glyphs.len(), 7);
+    assert_eq!(glyphs.position_by_cluster("hyphen".len()), (1, 0));
+  }
+
   #[test]
   fn text_in_different_bounds() {
     let mut store = test_store();
@@ -869,4 +1360,126 @@ mod tests {
     );
     assert_eq!(1, store.cache.len());
   }
+
+  #[test]
+  fn vertical_lines() {
+    let style = zero_letter_space_style(10., TextOverflow::Overflow);
+    let text: Substr = "ab\ncd".into();
+    let visual = typography_text(
+      text,
+      &style,
+      Size::new(100., 100.),
+      TextAlign::Start,
+      PlaceLineDirection::RightToLeft,
+    );
+
+    let rect = visual.visual_rect();
+    assert_eq!(rect.width(), 20.);
+    // The first line is at the right.
+    let a = visual.glyph_rect(0, 0);
+    let c = visual.glyph_rect(1, 0);
+    assert_eq!(visual.position_to_cluster(0, 0), 0);
+    assert_eq!((a.min_x(), c.min_x()), (10., 0.));
+    // The latin characters are rotated and advance downward.
+    let glyphs: Vec<_> = visual.glyphs().collect();
+    assert!(glyphs.iter().all(|g| g.rotated));
+    let b = visual.glyph_rect(0, 1);
+    assert_eq!(a.width(), 10.);
+    assert!(a.min_y() < b.min_y());
+    assert_eq!(a.max_y(), b.min_y());
+
+    assert_eq!(visual.nearest_glyph(15., b.min_y() + 1.), (0, 1));
+    assert_eq!(visual.nearest_glyph(2., 0.), (1, 0));
+    let rects = visual.select_range(&(0..2));
+    assert_eq!(rects, [Rect::new(a.origin, Size::new(10., b.max_y() - a.min_y()))]);
+  }
+
+  #[test]
+  fn white_space_and_tabs() {
+    let style = zero_letter_space_style(16., TextOverflow::AutoWrap);
+    let layout = |text: &'static str, style: &TextStyle, width: f32| {
+      typography_text(
+        text.into(),
+        style,
+        Size::new(width, f32::MAX),
+        TextAlign::Start,
+        PlaceLineDirection::TopToBottom,
+      )
+    };
+
+    let px = style.clone().with_tab_size(TabSize::Pixels(40.));
+    let glyphs = layout("a\tb\tc", &px, f32::MAX);
+    assert_eq!(glyphs.glyph_rect(0, 2).min_x(), 40.);
+    assert_eq!(glyphs.glyph_rect(0, 4).min_x(), 80.);
+
+    let space = layout(" b", &style, f32::MAX)
+      .glyph_rect(0, 1)
+      .min_x();
+    let spaces = style.clone().with_tab_size(TabSize::Spaces(4));
+    let b = layout("\tb", &spaces, f32::MAX).glyph_rect(0, 1);
+    assert!((b.min_x() - 4. * space).abs() < 0.1);
+
+    // The explicit stops go first, then the multiple of the tab size.
+    let stops = px.clone().with_tab_stops([25., 60.]);
+    let glyphs = layout("a\tb\tc\td", &stops, f32::MAX);
+    assert_eq!(glyphs.glyph_rect(0, 2).min_x(), 25.);
+    assert_eq!(glyphs.glyph_rect(0, 4).min_x(), 60.);
+    assert_eq!(glyphs.glyph_rect(0, 6).min_x(), 80.);
+
+    // The spaces and tabs are collapsed into one space.
+    let normal = style.clone().with_white_space(WhiteSpace::Normal);
+    let collapsed = layout("  a \t  b", &normal, f32::MAX);
+    let expect = layout("a b", &style, f32::MAX);
+    assert_eq!(collapsed.glyph_rect(0, 0), expect.glyph_rect(0, 0));
+    assert_eq!(collapsed.glyph_rect(0, 2), expect.glyph_rect(0, 2));
+
+    let narrow = layout("aa", &style, f32::MAX)
+      .visual_rect()
+      .width()
+      + 1.;
+    assert_eq!(layout("aa bb cc", &style, narrow).line_count(), 3);
+    let nowrap = style.clone().with_white_space(WhiteSpace::Nowrap);
+    assert_eq!(layout("aa bb cc", &nowrap, narrow).line_count(), 1);
+    let pre = style.with_white_space(WhiteSpace::Pre);
+    assert_eq!(layout("aa  bb\ncc", &pre, narrow).line_count(), 2);
+  }
+
+  #[test]
+  fn explicit_tab_stops() {
+    let store = test_store();
+    let ids = store
+      .font_db()
+      .borrow_mut()
+      .select_all_match(&test_face());
+    let mut shaper = TextShaper::new(store.font_db().clone());
+    let text: Substr = "a\tb\tc\td".into();
+    let shaped =
+      shaper.shape_text(&text, &ids, &[], TextDirection::LeftToRight, GlyphBaseline::Alphabetic);
+    let run = InputRun::new(
+      shaped,
+      1.,
+      GlyphUnit::ZERO,
+      0..text.len(),
+      &[],
+      OverflowWrap::Normal,
+    );
+
+    let stops = [GlyphUnit::from_pixel(50.), GlyphUnit::from_pixel(70.)];
+    let infos = TypographyMan::new(
+      [smallvec::smallvec![run]].into_iter(),
+      PlaceLineDirection::TopToBottom,
+      TextAlign::Start,
+      GlyphUnit::STANDARD_EM,
+      Size::new(GlyphUnit::MAX, GlyphUnit::MAX),
+      TextOverflow::Overflow,
+    )
+    .with_tab_stops(stops, GlyphUnit::from_pixel(100.))
+    .typography_all();
+
+    let glyphs = &infos.visual_lines[0].glyphs;
+    assert_eq!(glyphs[2].x_offset, stops[0]);
+    assert_eq!(glyphs[4].x_offset, stops[1]);
+    // After the explicit stops, the tab advances to the multiple of the tab size.
+    assert_eq!(glyphs[6].x_offset, GlyphUnit::from_pixel(100.));
+  }
 }
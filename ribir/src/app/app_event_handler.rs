@@ -1,7 +1,7 @@
 use ribir_core::window::UiEvent;
 use winit::{
   application::*,
-  event::{ElementState, MouseScrollDelta, WindowEvent},
+  event::{ElementState, MouseScrollDelta, TouchPhase, WindowEvent},
   event_loop::*,
 };
 
@@ -96,17 +96,22 @@ impl ApplicationHandler<RibirAppEvent> for AppHandler {
       WindowEvent::CursorLeft { .. } => {
         App::send_event(UiEvent::CursorLeft { wnd_id });
       }
-      WindowEvent::MouseWheel { delta, .. } => {
+      WindowEvent::MouseWheel { delta, phase, .. } => {
         if let Some(shell_wnd) = App::shell_window(wnd_id) {
           let wnd_factor = shell_wnd.borrow().winit_wnd.scale_factor();
-          let (delta_x, delta_y) = match delta {
-            MouseScrollDelta::LineDelta(x, y) => (x * 16., y * 16.),
+          let (delta_x, delta_y, phase) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => (x * 16., y * 16., WheelPhase::Line),
             MouseScrollDelta::PixelDelta(delta) => {
               let winit::dpi::LogicalPosition { x, y } = delta.to_logical(wnd_factor);
-              (x, y)
+              let phase = match phase {
+                TouchPhase::Started => WheelPhase::Started,
+                TouchPhase::Moved => WheelPhase::Moved,
+                TouchPhase::Ended | TouchPhase::Cancelled => WheelPhase::Ended,
+              };
+              (x, y, phase)
             }
           };
-          App::send_event(UiEvent::MouseWheel { wnd_id, delta_x, delta_y });
+          App::send_event(UiEvent::MouseWheel { wnd_id, delta_x, delta_y, phase });
         }
       }
       _ => (),
@@ -1,20 +1,75 @@
-use ribir_core::prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, Transform};
-use ribir_gpu::Surface;
+use std::{collections::VecDeque, future::Future};
+
+use ribir_core::prelude::{
+  Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, PixelImage, Transform,
+  image::ColorFormat,
+};
+use ribir_gpu::{GPUBackendImpl, Surface, Texture, WgpuTexture};
 
 use crate::winit_shell_wnd::WinitBackend;
 
+/// The backend draws a fully damaged frame directly to the surface. A surface
+/// texture isn't a reliable copy of the last frame, so a partially damaged
+/// frame is drawn to an offscreen texture that keeps the last frame, only the
+/// damage area is redrawn, and then the area changed since the surface texture
+/// was last drawn is copied to it. A frame without damage isn't drawn or
+/// presented at all.
+///
+/// Reporting the damage area to the platform is not supported, neither wgpu
+/// nor winit has an API for it, so the whole surface is presented.
 pub struct WgpuBackend<'a> {
   surface: Surface<'a>,
+  /// The offscreen texture for the partially damaged frames.
+  frame: Option<WgpuTexture>,
+  /// The `frame` texture doesn't keep the last frame, because the last frame
+  /// is drawn to the surface directly.
+  frame_outdated: bool,
+  /// The damage of the last frames presented, the newest at the back.
+  damages: VecDeque<DeviceRect>,
+  surface_color: Color,
   backend: ribir_gpu::GPUBackend<ribir_gpu::WgpuImpl>,
 }
 
+/// The number of the surface textures that the surface may use in turn, the
+/// frame latency of wgpu is 2 and the surface has one more texture. One more is
+/// counted for the platforms that create more textures than requested.
+const SURFACE_TEXTURES: usize = 4;
+
+impl WgpuBackend<'_> {
+  /// Record the damage of the frame to present, and return the area that the
+  /// current surface texture misses. It's the union of the damage of the frames
+  /// since the surface texture was last drawn, which is unknown until as many
+  /// frames as the surface textures are recorded.
+  fn push_damage(&mut self, damage: DeviceRect) -> DeviceRect {
+    if self.damages.len() == SURFACE_TEXTURES {
+      self.damages.pop_front();
+    }
+    self.damages.push_back(damage);
+    if self.damages.len() < SURFACE_TEXTURES {
+      DeviceRect::from_size(self.surface.size())
+    } else {
+      self
+        .damages
+        .iter()
+        .fold(DeviceRect::zero(), |acc, d| acc.union(d))
+    }
+  }
+}
+
 impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
   async fn new(window: &'a winit::window::Window) -> WgpuBackend<'a> {
     let (wgpu, surface) = ribir_gpu::WgpuImpl::new(window).await;
     let size = window.inner_size();
     let size = DeviceSize::new(size.width as i32, size.height as i32);
 
-    let mut wgpu = WgpuBackend { surface, backend: ribir_gpu::GPUBackend::new(wgpu) };
+    let mut wgpu = WgpuBackend {
+      surface,
+      frame: None,
+      frame_outdated: true,
+      damages: VecDeque::new(),
+      surface_color: Color::TRANSPARENT,
+      backend: ribir_gpu::GPUBackend::new(wgpu),
+    };
     wgpu.on_resize(size);
 
     wgpu
@@ -23,24 +78,91 @@ impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
   fn on_resize(&mut self, size: DeviceSize) {
     if size != self.surface.size() {
       self.surface.resize(size, self.backend.get_impl());
+      self.damages.clear();
     }
   }
 
-  fn begin_frame(&mut self, surface_color: Color) { self.backend.begin_frame(surface_color); }
+  fn begin_frame(&mut self, surface_color: Color) {
+    self.surface_color = surface_color;
+    self.backend.begin_frame(surface_color);
+  }
 
   fn draw_commands(
-    &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
+    &mut self, viewport: DeviceRect, mut damage: DeviceRect, global_matrix: &Transform,
+    commands: &[PaintCommand],
   ) {
-    self.backend.draw_commands(
-      viewport,
-      commands,
-      global_matrix,
-      self.surface.get_current_texture(),
-    );
+    if damage.is_empty() {
+      // Nothing changed, the window keeps showing the last frame.
+      return;
+    }
+
+    // A translucent surface color can't cover the old content, so it's always
+    // redrawn as a whole.
+    let partial = damage != viewport && self.surface_color.alpha == u8::MAX;
+    if !partial {
+      let surface = self.surface.get_current_texture();
+      self
+        .backend
+        .draw_commands(viewport, viewport, commands, global_matrix, surface);
+      self.frame_outdated = true;
+      self.push_damage(viewport);
+      return;
+    }
+
+    let size = self.surface.size();
+    if self
+      .frame
+      .as_ref()
+      .is_none_or(|f| f.size() != size)
+    {
+      let frame = self
+        .backend
+        .get_impl_mut()
+        .new_texture(size, ColorFormat::Rgba8);
+      self.frame = Some(frame);
+      self.frame_outdated = true;
+    }
+    if self.frame_outdated {
+      // The content of the frame texture isn't the last frame, redraw it all.
+      damage = viewport;
+      self.frame_outdated = false;
+    }
+    let frame = self.frame.as_mut().unwrap();
+    self
+      .backend
+      .draw_commands(viewport, damage, commands, global_matrix, frame);
+
+    let copy_rect = self
+      .push_damage(damage)
+      .intersection(&DeviceRect::from_size(size))
+      .unwrap_or_default();
+    let frame = self.frame.as_mut().unwrap();
+    let surface = self.surface.get_current_texture();
+    self
+      .backend
+      .get_impl_mut()
+      .copy_texture_from_texture(surface, copy_rect.origin, frame, &copy_rect);
   }
 
   fn end_frame(&mut self) {
     self.backend.end_frame();
     self.surface.present();
   }
+
+  fn capture(
+    &mut self, size: DeviceSize, surface_color: Color, commands: &[PaintCommand],
+  ) -> impl Future<Output = Option<PixelImage>> + 'static {
+    let rect = DeviceRect::from_size(size);
+    let mut texture = self
+      .backend
+      .get_impl_mut()
+      .new_texture(size, ColorFormat::Rgba8);
+    self.backend.begin_frame(surface_color);
+    self
+      .backend
+      .draw_commands(rect, rect, commands, &Transform::identity(), &mut texture);
+    let img = texture.copy_as_image(&rect, self.backend.get_impl_mut());
+    self.backend.end_frame();
+    async move { img.await.ok() }
+  }
 }
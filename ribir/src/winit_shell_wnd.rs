@@ -29,9 +29,17 @@ pub enum ShellCmd {
     id: WindowId,
     wnd_size: Size,
     viewport: Rect,
+    damage: Rect,
     surface_color: Color,
     commands: Vec<PaintCommand>,
   },
+  Capture {
+    id: WindowId,
+    size: Size,
+    surface_color: Color,
+    commands: Vec<PaintCommand>,
+    sender: tokio::sync::oneshot::Sender<Option<PixelImage>>,
+  },
   Close {
     id: WindowId,
   },
@@ -43,9 +51,10 @@ pub enum ShellCmd {
 impl ShellCmd {
   pub fn wnd_id(&self) -> Option<WindowId> {
     match self {
-      ShellCmd::RequestDraw { id } | ShellCmd::Draw { id, .. } | ShellCmd::Close { id } => {
-        Some(*id)
-      }
+      ShellCmd::RequestDraw { id }
+      | ShellCmd::Draw { id, .. }
+      | ShellCmd::Capture { id, .. }
+      | ShellCmd::Close { id } => Some(*id),
       ShellCmd::RunAsync { .. } | ShellCmd::Exit => None,
     }
   }
@@ -88,11 +97,20 @@ pub trait WinitBackend<'a>: Sized {
 
   fn begin_frame(&mut self, surface_color: Color);
 
+  /// Draw a full frame of `commands`, but only the `damage` area is changed
+  /// since the last frame.
   fn draw_commands(
-    &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
+    &mut self, viewport: DeviceRect, damage: DeviceRect, global_matrix: &Transform,
+    commands: &[PaintCommand],
   );
 
   fn end_frame(&mut self);
+
+  /// Render the `commands` to an offscreen image of `size`, it's independent of
+  /// the frames of the window.
+  fn capture(
+    &mut self, size: DeviceSize, surface_color: Color, commands: &[PaintCommand],
+  ) -> impl Future<Output = Option<PixelImage>> + 'static;
 }
 
 pub(crate) struct WinitShellWnd {
@@ -113,19 +131,22 @@ impl WinitShellWnd {
   pub(crate) fn deal_cmd(&mut self, cmd: ShellCmd) {
     match cmd {
       ShellCmd::RequestDraw { .. } => self.winit_wnd.request_redraw(),
-      ShellCmd::Draw { viewport, surface_color, commands, wnd_size, .. } => {
+      ShellCmd::Draw { viewport, damage, surface_color, commands, wnd_size, .. } => {
         if wnd_size == window_size(&self.winit_wnd) {
           self.backend.begin_frame(surface_color);
           let scale_factor = self.winit_wnd.scale_factor() as f32;
 
-          let viewport: DeviceRect = viewport
-            .scale(scale_factor, scale_factor)
-            .round_out()
-            .to_i32()
-            .cast_unit();
+          let to_device = |rect: Rect| -> DeviceRect {
+            rect
+              .scale(scale_factor, scale_factor)
+              .round_out()
+              .to_i32()
+              .cast_unit()
+          };
 
           self.backend.draw_commands(
-            viewport,
+            to_device(viewport),
+            to_device(damage),
             &Transform::scale(scale_factor, scale_factor),
             &commands,
           );
@@ -134,6 +155,15 @@ impl WinitShellWnd {
           self.winit_wnd.request_redraw();
         }
       }
+      ShellCmd::Capture { size, surface_color, commands, sender, .. } => {
+        let size = size.round().to_i32().cast_unit();
+        let img = self
+          .backend
+          .capture(size, surface_color, &commands);
+        App::spawn_local(async move {
+          let _ = sender.send(img.await);
+        });
+      }
       ShellCmd::Close { id } => {
         App::remove_shell_window(id);
       }
@@ -149,6 +179,8 @@ impl ShellWindow for ShellWndHandle {
 
   fn inner_size(&self) -> Size { window_size(&self.winit_wnd) }
 
+  fn device_pixel_ratio(&self) -> f32 { self.winit_wnd.scale_factor() as f32 }
+
   fn as_any(&self) -> &dyn Any { self }
 
   fn as_any_mut(&mut self) -> &mut dyn Any { self }
@@ -218,15 +250,31 @@ impl ShellWindow for ShellWndHandle {
   }
 
   fn draw_commands(
-    &mut self, wnd_size: Size, viewport: Rect, surface_color: Color, commands: &[PaintCommand],
+    &mut self, wnd_size: Size, viewport: Rect, damage: Rect, surface_color: Color,
+    commands: &[PaintCommand],
   ) {
     self.sender.send(ShellCmd::Draw {
       id: self.id(),
       wnd_size,
       viewport,
+      damage,
+      surface_color,
+      commands: commands.to_vec(),
+    });
+  }
+
+  fn capture(
+    &mut self, size: Size, surface_color: Color, commands: &[PaintCommand],
+  ) -> BoxFuture<'static, Option<PixelImage>> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    self.sender.send(ShellCmd::Capture {
+      id: self.id(),
+      size,
       surface_color,
       commands: commands.to_vec(),
+      sender,
     });
+    Box::pin(async move { receiver.await.ok().flatten() })
   }
 
   fn request_draw(&self) {
@@ -21,7 +21,7 @@ fn shape_1k(c: &mut Criterion) {
       shaper.end_frame();
 
       let str = include_str!("../../LICENSE").into();
-      shaper.shape_text(&str, &ids, TextDirection::LeftToRight, GlyphBaseline::Alphabetic)
+      shaper.shape_text(&str, &ids, &[], TextDirection::LeftToRight, GlyphBaseline::Alphabetic)
     })
   });
 }
@@ -3,17 +3,24 @@ use ribir_core::prelude::Classes;
 mod avatar_cls;
 mod buttons_cls;
 mod checkbox_cls;
+mod data_table_cls;
+mod date_time_cls;
 mod disabled_cls;
 mod divider_cls;
 mod input_cls;
 mod list_cls;
+mod markdown_cls;
 mod menu_cls;
 mod progress_cls;
 mod radio_cls;
+mod refresh_cls;
 mod scrollbar_cls;
+mod select_cls;
 mod slider_cls;
+mod split_pane_cls;
 mod tabs_cls;
 mod tooltips_cls;
+mod tree_view_cls;
 
 pub fn initd_classes() -> Classes {
   let mut classes = Classes::default();
@@ -32,6 +39,13 @@ pub fn initd_classes() -> Classes {
   disabled_cls::init(&mut classes);
   avatar_cls::init(&mut classes);
   list_cls::init(&mut classes);
+  select_cls::init(&mut classes);
+  date_time_cls::init(&mut classes);
+  tree_view_cls::init(&mut classes);
+  data_table_cls::init(&mut classes);
+  split_pane_cls::init(&mut classes);
+  refresh_cls::init(&mut classes);
+  markdown_cls::init(&mut classes);
 
   classes
 }
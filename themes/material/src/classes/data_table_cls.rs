@@ -0,0 +1,91 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(
+    DATA_TABLE,
+    style_class! {
+      background: Palette::of(BuildCtx::get()).surface(),
+    },
+  );
+  classes.insert(
+    DATA_TABLE_HEADER,
+    style_class! {
+      border: Border::only_bottom(
+        BorderSide::new(1., Palette::of(BuildCtx::get()).outline_variant().into())
+      ),
+    },
+  );
+
+  fn header_cell(w: Widget, background: Option<Color>) -> Widget {
+    let palette = Palette::of(BuildCtx::get());
+    let mut w = FatObj::new(w);
+    w.with_clamp(BoxClamp::min_height(56.))
+      .with_padding(EdgeInsets::only_left(16.))
+      .with_foreground(palette.on_surface_variant())
+      .with_text_style(
+        TypographyTheme::of(BuildCtx::get())
+          .title_small
+          .text
+          .clone(),
+      )
+      .with_cursor(CursorIcon::Pointer);
+    if let Some(background) = background {
+      w.with_background(background);
+    }
+    w.into_widget()
+  }
+  classes.insert(DATA_TABLE_HEADER_CELL, |w| header_cell(w, None));
+  classes.insert(DATA_TABLE_HEADER_CELL_DRAGGING, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    header_cell(w, Some(palette.surface_container_highest()))
+  });
+
+  classes.insert(
+    DATA_TABLE_SORT_DESCENDING,
+    style_class! {
+      text_line_height: 24.,
+    },
+  );
+  // The indicator points down, flip it for the ascending order.
+  classes.insert(
+    DATA_TABLE_SORT_ASCENDING,
+    style_class! {
+      text_line_height: 24.,
+      transform: Transform::translation(-12., -12.)
+        .then_rotate(Angle::pi())
+        .then_translate(Vector::new(12., 12.)),
+    },
+  );
+  classes.insert(
+    DATA_TABLE_RESIZE_HANDLE,
+    style_class! {
+      clamp: BoxClamp::fixed_width(8.),
+      cursor: CursorIcon::ColResize,
+    },
+  );
+
+  classes.insert(
+    DATA_TABLE_ROW,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_medium.text.clone(),
+    },
+  );
+  classes.insert(
+    DATA_TABLE_ROW_SELECTED,
+    style_class! {
+      background: Palette::of(BuildCtx::get()).secondary_container(),
+      foreground: Palette::of(BuildCtx::get()).on_secondary_container(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_medium.text.clone(),
+    },
+  );
+  classes.insert(DATA_TABLE_CELL, |w| {
+    let mut w = FatObj::new(w);
+    w.with_v_align(VAlign::Center)
+      .with_padding(md::EDGES_HOR_16);
+    w.into_widget()
+  });
+}
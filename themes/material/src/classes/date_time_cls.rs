@@ -0,0 +1,258 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use super::input_cls::input_border;
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  calendar_init(classes);
+  date_picker_init(classes);
+  time_picker_init(classes);
+}
+
+fn calendar_init(classes: &mut Classes) {
+  classes.insert(
+    CALENDAR,
+    style_class! {
+      padding: md::EDGES_HOR_12,
+      background: Palette::of(BuildCtx::get()).surface_container_high(),
+      radius: md::RADIUS_16,
+    },
+  );
+  classes.insert(
+    CALENDAR_HEADER,
+    style_class! {
+      padding: EdgeInsets::new(8., 0., 8., 12.),
+    },
+  );
+  classes.insert(
+    CALENDAR_TITLE,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_style: TypographyTheme::of(BuildCtx::get()).title_small.text.clone(),
+    },
+  );
+  nav_button(classes, CALENDAR_PREV);
+  nav_button(classes, CALENDAR_NEXT);
+  classes.insert(CALENDAR_WEEKDAY, |w| {
+    let mut w = FatObj::new(w);
+    w.with_h_align(HAlign::Center)
+      .with_v_align(VAlign::Center);
+    container! {
+      size: md::SIZE_40,
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+      @ { w }
+    }
+    .into_widget()
+  });
+
+  classes.insert(CALENDAR_DAY, |w| {
+    container! {
+      size: md::SIZE_40,
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+      @ { w }
+    }
+    .into_widget()
+  });
+  classes.insert(CALENDAR_DAY_BLANK, empty_cls);
+  classes.insert(
+    CALENDAR_DAY_ENABLED,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    CALENDAR_DAY_DISABLED,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface().with_alpha(0.38),
+    },
+  );
+  classes.insert(
+    CALENDAR_DAY_TODAY,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).primary(),
+      border: Border::all(BorderSide::new(1., Palette::of(BuildCtx::get()).primary().into())),
+      radius: md::RADIUS_20,
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    CALENDAR_DAY_SELECTED,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_primary(),
+      background: Palette::of(BuildCtx::get()).primary(),
+      radius: md::RADIUS_20,
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    CALENDAR_DAY_IN_RANGE,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_secondary_container(),
+      background: Palette::of(BuildCtx::get()).secondary_container(),
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    CALENDAR_DAY_FOCUSED,
+    style_class! {
+      border: Border::all(BorderSide::new(2., Palette::of(BuildCtx::get()).secondary().into())),
+      radius: md::RADIUS_20,
+    },
+  );
+}
+
+fn nav_button(classes: &mut Classes, name: ClassName) {
+  classes.insert(
+    name,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_line_height: 24.,
+      margin: md::EDGES_8,
+      cursor: CursorIcon::Pointer,
+    },
+  );
+}
+
+fn date_picker_init(classes: &mut Classes) {
+  classes.insert(
+    DATE_PICKER,
+    class_multi_impl![
+      style_class! {
+        clamp: BoxClamp::min_height(40.).with_min_width(160.),
+        padding: EdgeInsets::new(0., 4., 0., 12.),
+        cursor: CursorIcon::Pointer,
+      },
+      input_border
+    ],
+  );
+  classes.insert(
+    DATE_PICKER_LABEL,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+    },
+  );
+  classes.insert(
+    DATE_PICKER_PLACEHOLDER,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+    },
+  );
+  classes.insert(
+    DATE_PICKER_INDICATOR,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_line_height: 24.,
+    },
+  );
+  classes.insert(DATE_PICKER_POPUP, style_class! { margin: md::EDGES_TOP_4 });
+}
+
+fn time_picker_init(classes: &mut Classes) {
+  classes.insert(
+    TIME_PICKER,
+    style_class! {
+      padding: md::EDGES_16,
+      background: Palette::of(BuildCtx::get()).surface_container_high(),
+      radius: Radius::all(28.),
+    },
+  );
+  classes.insert(TIME_PICKER_HEADER, style_class! { margin: md::EDGES_BOTTOM_16 });
+
+  fn time_field(w: Widget, background: Color, foreground: Color) -> Widget {
+    let mut w = FatObj::new(w);
+    w.with_h_align(HAlign::Center)
+      .with_v_align(VAlign::Center);
+    container! {
+      size: Size::new(96., 80.),
+      radius: md::RADIUS_8,
+      cursor: CursorIcon::Pointer,
+      background,
+      foreground,
+      text_style: TypographyTheme::of(BuildCtx::get()).display_large.text.clone(),
+      @ { w }
+    }
+    .into_widget()
+  }
+  classes.insert(TIME_PICKER_FIELD, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    time_field(w, palette.surface_container_highest(), palette.on_surface())
+  });
+  classes.insert(TIME_PICKER_FIELD_SELECTED, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    time_field(w, palette.primary_container(), palette.on_primary_container())
+  });
+  classes.insert(
+    TIME_PICKER_SEPARATOR,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      text_style: TypographyTheme::of(BuildCtx::get()).display_large.text.clone(),
+      padding: md::EDGES_HOR_4,
+    },
+  );
+  classes.insert(
+    TIME_PICKER_PERIOD,
+    style_class! {
+      margin: md::EDGES_LEFT_16,
+      border: Border::all(BorderSide::new(1., Palette::of(BuildCtx::get()).outline().into())),
+      radius: md::RADIUS_8,
+    },
+  );
+  classes.insert(
+    TIME_PICKER_PERIOD_ITEM,
+    style_class! {
+      padding: EdgeInsets::new(8., 12., 8., 12.),
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_style: TypographyTheme::of(BuildCtx::get()).title_medium.text.clone(),
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    TIME_PICKER_PERIOD_ITEM_SELECTED,
+    style_class! {
+      padding: EdgeInsets::new(8., 12., 8., 12.),
+      background: Palette::of(BuildCtx::get()).tertiary_container(),
+      foreground: Palette::of(BuildCtx::get()).on_tertiary_container(),
+      text_style: TypographyTheme::of(BuildCtx::get()).title_medium.text.clone(),
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    TIME_PICKER_DIAL,
+    style_class! {
+      background: Palette::of(BuildCtx::get()).surface_container_highest(),
+      radius: Radius::all(128.),
+      cursor: CursorIcon::Pointer,
+    },
+  );
+  classes.insert(
+    TIME_PICKER_DIAL_LABEL,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+    },
+  );
+  classes.insert(
+    TIME_PICKER_DIAL_LABEL_SELECTED,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_primary(),
+      background: Palette::of(BuildCtx::get()).primary(),
+      radius: Radius::all(24.),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+    },
+  );
+  classes.insert(
+    TIME_PICKER_MODE_TOGGLE,
+    style_class! {
+      margin: md::EDGES_TOP_16,
+      padding: md::EDGES_HOR_12,
+      foreground: Palette::of(BuildCtx::get()).primary(),
+      text_style: TypographyTheme::of(BuildCtx::get()).label_large.text.clone(),
+      cursor: CursorIcon::Pointer,
+    },
+  );
+}
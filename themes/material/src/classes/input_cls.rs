@@ -1,5 +1,8 @@
 use ribir_core::prelude::*;
-use ribir_widgets::input::{INPUT, TEXT_CARET, TEXT_SELECTION, TEXTAREA};
+use ribir_widgets::input::{
+  CODE_BRACKET_MATCH, CODE_CURRENT_LINE, CODE_EDITOR, CODE_GUTTER, INPUT, TEXT_CARET,
+  TEXT_SELECTION, TEXTAREA,
+};
 
 use crate::md;
 
@@ -31,19 +34,42 @@ pub(super) fn init(classes: &mut Classes) {
     },
   );
 
-  fn input_border(w: Widget) -> Widget {
-    let mut w = FatObj::new(w);
-    let blur = Palette::of(BuildCtx::get()).on_surface_variant();
-
-    let focus_watcher = w.is_focused();
-    let border = BuildCtx::color().map_with_watcher(focus_watcher, move |c, focus| {
-      let color = if *focus { *c } else { blur };
-      Border::all(BorderSide::new(1., color.into()))
-    });
-
-    w.with_border(border).with_radius(md::RADIUS_2);
-    w.into_widget()
-  }
   classes.insert(INPUT, input_border);
   classes.insert(TEXTAREA, input_border);
+  classes.insert(CODE_EDITOR, input_border);
+  classes.insert(
+    CODE_GUTTER,
+    style_class! {
+      padding: md::EDGES_HOR_8,
+      foreground: Palette::of(BuildCtx::get()).outline(),
+    },
+  );
+  classes.insert(
+    CODE_CURRENT_LINE,
+    style_class! {
+      background: Palette::of(BuildCtx::get()).surface_container_highest(),
+    },
+  );
+  classes.insert(
+    CODE_BRACKET_MATCH,
+    style_class! {
+      border: Border::all(BorderSide::new(1., Palette::of(BuildCtx::get()).outline().into())),
+    },
+  );
+}
+
+/// The border of the text fields, it's highlighted by the color of the
+/// `BuildCtx` when the field is focused.
+pub(super) fn input_border(w: Widget) -> Widget {
+  let mut w = FatObj::new(w);
+  let blur = Palette::of(BuildCtx::get()).on_surface_variant();
+
+  let focus_watcher = w.is_focused();
+  let border = BuildCtx::color().map_with_watcher(focus_watcher, move |c, focus| {
+    let color = if *focus { *c } else { blur };
+    Border::all(BorderSide::new(1., color.into()))
+  });
+
+  w.with_border(border).with_radius(md::RADIUS_2);
+  w.into_widget()
 }
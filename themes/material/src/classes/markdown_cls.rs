@@ -0,0 +1,40 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(
+    MARKDOWN_QUOTE,
+    style_class! {
+      padding: md::EDGES_LEFT_16,
+      border: Border::only_left(
+        BorderSide::new(4., Palette::of(BuildCtx::get()).outline_variant().into())
+      ),
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+    },
+  );
+  classes.insert(
+    MARKDOWN_CODE_BLOCK,
+    style_class! {
+      h_align: HAlign::Stretch,
+      padding: md::EDGES_8,
+      radius: md::RADIUS_4,
+      background: Palette::of(BuildCtx::get()).surface_container(),
+    },
+  );
+  classes.insert(
+    MARKDOWN_INLINE_CODE,
+    style_class! {
+      padding: md::EDGES_HOR_4,
+      radius: md::RADIUS_2,
+      background: Palette::of(BuildCtx::get()).surface_container_highest(),
+    },
+  );
+  classes.insert(
+    MARKDOWN_LINK,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).primary(),
+    },
+  );
+}
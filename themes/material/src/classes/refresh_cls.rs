@@ -0,0 +1,24 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(REFRESH_INDICATOR, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    fat_obj! {
+      padding: md::EDGES_4,
+      radius: Radius::all(28.),
+      background: palette.surface_container_high(),
+      @ { w }
+    }
+    .into_widget()
+  });
+  classes.insert(
+    LOAD_MORE_FOOTER,
+    style_class! {
+      padding: EdgeInsets::vertical(16.),
+      h_align: HAlign::Center,
+    },
+  );
+}
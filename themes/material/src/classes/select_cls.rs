@@ -0,0 +1,67 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use super::input_cls::input_border;
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(
+    SELECT,
+    class_multi_impl![
+      style_class! {
+        clamp: BoxClamp::min_height(40.).with_min_width(112.),
+        padding: EdgeInsets::new(0., 4., 0., 12.),
+        cursor: CursorIcon::Pointer,
+      },
+      input_border
+    ],
+  );
+  classes.insert(
+    SELECT_LABEL,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+    },
+  );
+  classes.insert(
+    SELECT_PLACEHOLDER,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_large.text.clone(),
+    },
+  );
+  classes.insert(
+    SELECT_INDICATOR,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_line_height: 24.,
+    },
+  );
+  classes.insert(
+    SELECT_OPTION_CHECK,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_line_height: 24.,
+    },
+  );
+
+  classes.insert(
+    COMBOBOX,
+    style_class! {
+      clamp: BoxClamp::min_height(40.).with_min_width(112.),
+    },
+  );
+  classes.insert(
+    COMBOBOX_POPUP,
+    style_class! {
+      clamp: BoxClamp::min_width(112.),
+      padding: md::EDGES_VER_8,
+    },
+  );
+  classes.insert(
+    COMBOBOX_MATCH,
+    style_class! {
+      foreground: Palette::of(BuildCtx::get()).primary(),
+    },
+  );
+}
@@ -0,0 +1,34 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(SPLIT_PANE_H_DIVIDER, |w| divider(w, Direction::Horizontal));
+  classes.insert(SPLIT_PANE_V_DIVIDER, |w| divider(w, Direction::Vertical));
+}
+
+// The divider is wider than the line it draws, so it's easy to grab.
+fn divider(w: Widget, dir: Direction) -> Widget {
+  let (clamp, line_clamp, h_align, v_align) = match dir {
+    Direction::Horizontal => {
+      (BoxClamp::fixed_width(8.), BoxClamp::fixed_width(1.), HAlign::Center, VAlign::Stretch)
+    }
+    Direction::Vertical => {
+      (BoxClamp::fixed_height(8.), BoxClamp::fixed_height(1.), HAlign::Stretch, VAlign::Center)
+    }
+  };
+  let mut w = FatObj::new(w);
+  w.with_clamp(clamp);
+  stack! {
+    fit: StackFit::Passthrough,
+    @ { w }
+    @InParentLayout {
+      @Void {
+        clamp: line_clamp,
+        h_align,
+        v_align,
+        background: Palette::of(BuildCtx::get()).outline_variant(),
+      }
+    }
+  }
+  .into_widget()
+}
@@ -0,0 +1,66 @@
+use easing::CubicBezierEasing;
+use ribir_core::{prelude::*, smooth_height};
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+const EXPAND_TRANS: EasingTransition<CubicBezierEasing> = EasingTransition {
+  easing: md::easing::EMPHASIZED_DECELERATE,
+  duration: md::easing::duration::MEDIUM2,
+};
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(
+    TREE_VIEW_ITEM_FOCUSED,
+    style_class! {
+      border: Border::all(BorderSide::new(2., Palette::of(BuildCtx::get()).secondary().into())),
+      radius: md::RADIUS_4,
+    },
+  );
+  classes.insert(
+    TREE_VIEW_ITEM_DROP_BEFORE,
+    style_class! {
+      border: Border::only_top(BorderSide::new(2., Palette::of(BuildCtx::get()).primary().into())),
+    },
+  );
+  classes.insert(
+    TREE_VIEW_ITEM_DROP_AFTER,
+    style_class! {
+      border: Border::only_bottom(
+        BorderSide::new(2., Palette::of(BuildCtx::get()).primary().into())
+      ),
+    },
+  );
+
+  fn expander(w: Widget) -> Widget {
+    container! {
+      size: md::SIZE_24,
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_line_height: 24.,
+      cursor: CursorIcon::Pointer,
+      @ { w }
+    }
+    .into_widget()
+  }
+  classes.insert(TREE_VIEW_EXPANDER, expander);
+  classes.insert(TREE_VIEW_EXPANDER_EXPANDED, expander);
+
+  // The group grows from zero height when the node is expanded, and shrinks
+  // back to zero when the node is collapsed.
+  classes.insert(TREE_VIEW_GROUP, |w| {
+    smooth_height! {
+      transition: EXPAND_TRANS,
+      init_value: 0.,
+      clip_boundary: true,
+      @ { w }
+    }
+    .into_widget()
+  });
+  classes.insert(
+    TREE_VIEW_GUIDE,
+    style_class! {
+      background: Palette::of(BuildCtx::get()).outline_variant(),
+    },
+  );
+  classes.insert(TREE_VIEW_LOADING, style_class! { margin: md::EDGES_HOR_16 });
+}
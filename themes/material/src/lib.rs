@@ -145,6 +145,7 @@ pub fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        ..<_>::default()
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
@@ -0,0 +1,786 @@
+use std::{fmt, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class name for the whole calendar
+  CALENDAR,
+  /// Class name for the header with the month title and navigation buttons
+  CALENDAR_HEADER,
+  /// Class name for the month title
+  CALENDAR_TITLE,
+  /// Class name for the button that navigates to the previous month
+  CALENDAR_PREV,
+  /// Class name for the button that navigates to the next month
+  CALENDAR_NEXT,
+  /// Class name for the weekday labels
+  CALENDAR_WEEKDAY,
+  /// Base class name for all the day cells
+  CALENDAR_DAY,
+  /// Class name for the empty cells before and after the days of the month
+  CALENDAR_DAY_BLANK,
+  /// Class name for a day that can be selected
+  CALENDAR_DAY_ENABLED,
+  /// Class name for a day that is out of the range or disabled by predicate
+  CALENDAR_DAY_DISABLED,
+  /// Class name for the day of today
+  CALENDAR_DAY_TODAY,
+  /// Class name for a selected day, or the endpoints of a selected range
+  CALENDAR_DAY_SELECTED,
+  /// Class name for the days between the endpoints of a selected range
+  CALENDAR_DAY_IN_RANGE,
+  /// Class name for the day that has the keyboard focus
+  CALENDAR_DAY_FOCUSED,
+}
+
+/// A day of the week.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weekday {
+  Monday,
+  Tuesday,
+  Wednesday,
+  Thursday,
+  Friday,
+  Saturday,
+  Sunday,
+}
+
+/// A date in the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+  year: i32,
+  month: u8,
+  day: u8,
+}
+
+/// The selection of a [`Calendar`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DateSelection {
+  /// Nothing is selected.
+  #[default]
+  None,
+  /// A single date is selected.
+  Single(Date),
+  /// A range of dates is selected, the end is `None` until the user picks the
+  /// second date.
+  Range(Date, Option<Date>),
+}
+
+/// A predicate that disables some days of a [`Calendar`], for example, the
+/// weekends.
+#[derive(Clone, Default)]
+pub struct DayPredicate(Option<Rc<DayPredicateFn>>);
+
+type DayPredicateFn = dyn Fn(&Date) -> bool;
+
+/// The locale of the calendar widgets. Provide it by a [`Provider`] to
+/// change the first day of the week and the names of the months and the
+/// weekdays, the default is the `en-US` locale.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = providers! {
+///   providers: [Provider::new(CalendarLocale::en_gb())],
+///   @Calendar {}
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarLocale {
+  /// The day that a week starts with.
+  pub first_day_of_week: Weekday,
+  /// The names of the months, starting from January.
+  pub month_names: [CowArc<str>; 12],
+  /// The short names of the weekdays, starting from Monday.
+  pub weekday_names: [CowArc<str>; 7],
+  /// Whether the year is displayed before the month in the title, e.g.
+  /// "2025年 3月".
+  pub year_first: bool,
+}
+
+/// The date of today that the [`Calendar`] marks. Provide it by a
+/// [`Provider`] to use the date in the user's time zone, the default is
+/// [`Date::today`] that is the date in UTC.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = providers! {
+///   providers: [Provider::new(Today(Date::new(2025, 3, 14).unwrap()))],
+///   @Calendar { month: Date::new(2025, 3, 1).unwrap() }
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Today(pub Date);
+
+/// A month grid that lets the user pick a date or a range of dates.
+///
+/// The calendar supports keyboard navigation when it's focused:
+/// - `ArrowLeft`/`ArrowRight` move the focused day by one day.
+/// - `ArrowUp`/`ArrowDown` move the focused day by one week.
+/// - `PageUp`/`PageDown` move the focused day by one month.
+/// - `Home`/`End` move the focused day to the start/end of the week.
+/// - `Enter`/`Space` select the focused day.
+///
+/// Days out of `min`/`max` and the days that `disabled_days` returns true can
+/// not be selected.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let calendar = @Calendar {
+///     month: Date::new(2025, 3, 1).unwrap(),
+///     min: Date::new(2025, 1, 1),
+///     disabled_days: DayPredicate::new(|d: &Date| d.weekday() == Weekday::Sunday),
+///   };
+///   watch!(*$read(calendar).selection())
+///     .subscribe(|s| println!("selection: {s:?}"));
+///   calendar
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Calendar {
+  /// The displayed month, any day in the month is fine.
+  #[declare(default = Date::today())]
+  month: Date,
+  /// The selected date or range.
+  #[declare(default)]
+  selection: DateSelection,
+  /// Whether the user picks a range of dates instead of a single date.
+  #[declare(default)]
+  range: bool,
+  /// The earliest date that can be selected.
+  #[declare(default)]
+  min: Option<Date>,
+  /// The latest date that can be selected.
+  #[declare(default)]
+  max: Option<Date>,
+  /// The days that can not be selected.
+  #[declare(default)]
+  disabled_days: DayPredicate,
+  /// The day that has the keyboard focus.
+  #[declare(skip)]
+  focused: Option<Date>,
+}
+
+impl Weekday {
+  const ALL: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+  ];
+
+  /// The number of days since Monday.
+  pub fn num_days_from_monday(self) -> u8 { self as u8 }
+
+  /// Return the day that is `n` days after this day.
+  pub fn after(self, n: u8) -> Self { Self::ALL[((self as u8 + n) % 7) as usize] }
+}
+
+impl Date {
+  /// Create a date, return `None` if the date is invalid.
+  pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+    if (1..=12).contains(&month) && day >= 1 && day <= Self::days_in_month(year, month) {
+      Some(Self { year, month, day })
+    } else {
+      None
+    }
+  }
+
+  /// Return today's date in UTC by the system clock. Provide a [`Today`] to
+  /// use the date in the user's time zone.
+  pub fn today() -> Self {
+    let secs = web_time::SystemTime::now()
+      .duration_since(web_time::UNIX_EPOCH)
+      .map_or(0, |d| d.as_secs() as i64);
+    Self::from_days(secs.div_euclid(86400))
+  }
+
+  pub fn year(&self) -> i32 { self.year }
+
+  /// The month of the year, starting from 1.
+  pub fn month(&self) -> u8 { self.month }
+
+  /// The day of the month, starting from 1.
+  pub fn day(&self) -> u8 { self.day }
+
+  pub fn is_leap_year(year: i32) -> bool { (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 }
+
+  pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+      2 if Self::is_leap_year(year) => 29,
+      2 => 28,
+      4 | 6 | 9 | 11 => 30,
+      _ => 31,
+    }
+  }
+
+  pub fn weekday(&self) -> Weekday { Weekday::ALL[(self.days() + 3).rem_euclid(7) as usize] }
+
+  /// Return the first day of the month of this date.
+  pub fn first_of_month(&self) -> Self { Self { day: 1, ..*self } }
+
+  /// Return the date `days` days after this date, `days` can be negative.
+  pub fn add_days(&self, days: i32) -> Self { Self::from_days(self.days() + days as i64) }
+
+  /// Return the date `months` months after this date, `months` can be
+  /// negative. The day is clamped to the length of the new month.
+  pub fn add_months(&self, months: i32) -> Self {
+    let idx = self.year as i64 * 12 + self.month as i64 - 1 + months as i64;
+    let year = idx.div_euclid(12) as i32;
+    let month = idx.rem_euclid(12) as u8 + 1;
+    let day = self.day.min(Self::days_in_month(year, month));
+    Self { year, month, day }
+  }
+
+  /// The number of days since 1970-01-01.
+  fn days(&self) -> i64 {
+    // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let m = self.month as i64;
+    let y = self.year as i64 - (m <= 2) as i64;
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+  }
+
+  fn from_days(days: i64) -> Self {
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+    Self { year, month, day }
+  }
+}
+
+impl fmt::Display for Date {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+  }
+}
+
+impl DateSelection {
+  /// Return true if the date is selected or in the selected range.
+  pub fn contains(&self, date: &Date) -> bool {
+    match self {
+      DateSelection::None => false,
+      DateSelection::Single(d) => d == date,
+      DateSelection::Range(start, None) => start == date,
+      DateSelection::Range(start, Some(end)) => start <= date && date <= end,
+    }
+  }
+
+  /// Return true if the date is the selected date or an endpoint of the
+  /// selected range.
+  pub fn is_endpoint(&self, date: &Date) -> bool {
+    match self {
+      DateSelection::None => false,
+      DateSelection::Single(d) | DateSelection::Range(d, None) => d == date,
+      DateSelection::Range(start, Some(end)) => start == date || end == date,
+    }
+  }
+
+  /// Return true if the selection is finished, that's a single date is
+  /// selected or both endpoints of a range are selected.
+  pub fn is_complete(&self) -> bool {
+    matches!(self, DateSelection::Single(_) | DateSelection::Range(_, Some(_)))
+  }
+
+  /// Return the first selected date.
+  pub fn start(&self) -> Option<Date> {
+    match self {
+      DateSelection::None => None,
+      DateSelection::Single(d) | DateSelection::Range(d, _) => Some(*d),
+    }
+  }
+}
+
+impl fmt::Display for DateSelection {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DateSelection::None => Ok(()),
+      DateSelection::Single(d) => write!(f, "{d}"),
+      DateSelection::Range(start, None) => write!(f, "{start} – "),
+      DateSelection::Range(start, Some(end)) => write!(f, "{start} – {end}"),
+    }
+  }
+}
+
+impl DayPredicate {
+  pub fn new(f: impl Fn(&Date) -> bool + 'static) -> Self { Self(Some(Rc::new(f))) }
+
+  pub fn test(&self, date: &Date) -> bool { self.0.as_ref().is_some_and(|f| f(date)) }
+}
+
+impl Today {
+  /// Return the date of today provided by the context, or [`Date::today`].
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> Date {
+    Provider::of::<Self>(ctx).map_or_else(Date::today, |t| t.0)
+  }
+}
+
+impl CalendarLocale {
+  /// Return the locale provided by the context, or the default locale.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    Provider::of::<Self>(ctx).map_or_else(Self::default, |l| l.clone())
+  }
+
+  /// English locale with Sunday as the first day of the week.
+  pub fn en_us() -> Self {
+    Self {
+      first_day_of_week: Weekday::Sunday,
+      month_names: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+      ]
+      .map(CowArc::from),
+      weekday_names: ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"].map(CowArc::from),
+      year_first: false,
+    }
+  }
+
+  /// English locale with Monday as the first day of the week.
+  pub fn en_gb() -> Self { Self { first_day_of_week: Weekday::Monday, ..Self::en_us() } }
+
+  /// Simplified Chinese locale.
+  pub fn zh_cn() -> Self {
+    Self {
+      first_day_of_week: Weekday::Monday,
+      month_names: [
+        "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+      ]
+      .map(CowArc::from),
+      weekday_names: ["一", "二", "三", "四", "五", "六", "日"].map(CowArc::from),
+      year_first: true,
+    }
+  }
+
+  /// Return the title of the month that the date is in.
+  pub fn month_title(&self, date: &Date) -> String {
+    let month = &self.month_names[date.month() as usize - 1];
+    if self.year_first {
+      format!("{}年 {month}", date.year())
+    } else {
+      format!("{month} {}", date.year())
+    }
+  }
+
+  /// Return the short names of the weekdays in the order of a week.
+  pub fn ordered_weekday_names(&self) -> impl Iterator<Item = &CowArc<str>> {
+    (0..7).map(move |i| {
+      let day = self.first_day_of_week.after(i);
+      &self.weekday_names[day.num_days_from_monday() as usize]
+    })
+  }
+}
+
+impl Default for CalendarLocale {
+  fn default() -> Self { Self::en_us() }
+}
+
+impl Calendar {
+  /// Return the first day of the displayed month.
+  pub fn month(&self) -> Date { self.month.first_of_month() }
+
+  /// Display the month that the date is in.
+  pub fn set_month(&mut self, date: Date) { self.month = date.first_of_month(); }
+
+  pub fn prev_month(&mut self) { self.month = self.month().add_months(-1); }
+
+  pub fn next_month(&mut self) { self.month = self.month().add_months(1); }
+
+  pub fn selection(&self) -> &DateSelection { &self.selection }
+
+  /// Return the day that has the keyboard focus.
+  pub fn focused(&self) -> Option<Date> { self.focused }
+
+  /// Return true if the date can be selected.
+  pub fn is_selectable(&self, date: &Date) -> bool {
+    self.min.is_none_or(|min| min <= *date)
+      && self.max.is_none_or(|max| *date <= max)
+      && !self.disabled_days.test(date)
+  }
+
+  /// Select the date as the user taps it. In range mode, the first date
+  /// starts a new range and the second date finishes it, the endpoints are
+  /// swapped if the second date is before the first one.
+  ///
+  /// Return false if the date can not be selected.
+  pub fn select(&mut self, date: Date) -> bool {
+    if !self.is_selectable(&date) {
+      return false;
+    }
+    self.selection = match self.selection {
+      DateSelection::Range(start, None) if self.range => {
+        DateSelection::Range(start.min(date), Some(start.max(date)))
+      }
+      _ if self.range => DateSelection::Range(date, None),
+      _ => DateSelection::Single(date),
+    };
+    self.focused = Some(date);
+    true
+  }
+
+  /// Move the keyboard focus by `days` days, the displayed month follows the
+  /// focused day. The focus skips the days that can't be selected, and stays
+  /// if there is no selectable day.
+  pub fn move_focus(&mut self, days: i32) {
+    let date = self.focus_base().add_days(days);
+    if let Some(date) = self.nearest_selectable(date, days < 0) {
+      self.focus_to(date);
+    }
+  }
+
+  /// Move the keyboard focus by `months` months, the displayed month follows
+  /// the focused day. The focus skips the days that can't be selected, and
+  /// stays if there is no selectable day.
+  pub fn move_focus_months(&mut self, months: i32) {
+    let date = self.focus_base().add_months(months);
+    if let Some(date) = self.nearest_selectable(date, months < 0) {
+      self.focus_to(date);
+    }
+  }
+
+  /// Return the selectable day nearest to `date` in a year. The date is
+  /// clamped to `min`/`max` first, then the search goes in the moving
+  /// direction and falls back to the opposite one.
+  fn nearest_selectable(&self, mut date: Date, backward: bool) -> Option<Date> {
+    if let Some(min) = self.min {
+      date = date.max(min);
+    }
+    if let Some(max) = self.max {
+      date = date.min(max);
+    }
+    let dir = if backward { -1 } else { 1 };
+    [dir, -dir].into_iter().find_map(|dir| {
+      (0..366)
+        .map(|i| date.add_days(i * dir))
+        .take_while(|d| {
+          self.min.is_none_or(|min| min <= *d) && self.max.is_none_or(|max| *d <= max)
+        })
+        .find(|d| !self.disabled_days.test(d))
+    })
+  }
+
+  fn focus_base(&self) -> Date {
+    self
+      .focused
+      .or_else(|| self.selection.start())
+      .unwrap_or_else(|| self.month())
+  }
+
+  fn focus_to(&mut self, date: Date) {
+    self.focused = Some(date);
+    if date.first_of_month() != self.month() {
+      self.month = date.first_of_month();
+    }
+  }
+
+  /// Return the cells of the month grid by weeks, a `None` cell is a blank
+  /// before the first day or after the last day of the month.
+  fn month_cells(month: Date, first_day_of_week: Weekday) -> Vec<Option<Date>> {
+    let first = month.first_of_month();
+    let blanks =
+      (first.weekday().num_days_from_monday() + 7 - first_day_of_week.num_days_from_monday()) % 7;
+    let days = Date::days_in_month(first.year(), first.month());
+    let mut cells = vec![None; blanks as usize];
+    cells.extend((0..days as i32).map(|i| Some(first.add_days(i))));
+    cells.resize(cells.len().div_ceil(7) * 7, None);
+    cells
+  }
+
+  fn day_class(&self, date: &Date, today: &Date) -> ClassName {
+    if !self.is_selectable(date) {
+      CALENDAR_DAY_DISABLED
+    } else if self.selection.is_endpoint(date) {
+      CALENDAR_DAY_SELECTED
+    } else if self.selection.contains(date) {
+      CALENDAR_DAY_IN_RANGE
+    } else if date == today {
+      CALENDAR_DAY_TODAY
+    } else {
+      CALENDAR_DAY_ENABLED
+    }
+  }
+
+  fn handle_key(&mut self, key: &VirtualKey, first_day_of_week: Weekday) -> bool {
+    match key {
+      VirtualKey::Named(NamedKey::ArrowLeft) => self.move_focus(-1),
+      VirtualKey::Named(NamedKey::ArrowRight) => self.move_focus(1),
+      VirtualKey::Named(NamedKey::ArrowUp) => self.move_focus(-7),
+      VirtualKey::Named(NamedKey::ArrowDown) => self.move_focus(7),
+      VirtualKey::Named(NamedKey::PageUp) => self.move_focus_months(-1),
+      VirtualKey::Named(NamedKey::PageDown) => self.move_focus_months(1),
+      VirtualKey::Named(NamedKey::Home | NamedKey::End) => {
+        let base = self.focus_base();
+        let offset = (base.weekday().num_days_from_monday() + 7
+          - first_day_of_week.num_days_from_monday())
+          % 7;
+        let days = if matches!(key, VirtualKey::Named(NamedKey::Home)) {
+          -(offset as i32)
+        } else {
+          6 - offset as i32
+        };
+        self.move_focus(days);
+      }
+      VirtualKey::Named(NamedKey::Enter | NamedKey::Space) => {
+        let date = self.focus_base();
+        self.select(date);
+      }
+      _ => return false,
+    }
+    true
+  }
+}
+
+impl Compose for Calendar {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let locale = CalendarLocale::of(BuildCtx::get());
+      let first_day_of_week = locale.first_day_of_week;
+      let today = Today::of(BuildCtx::get());
+      let weekdays = locale
+        .ordered_weekday_names()
+        .map(|name| @Text { class: CALENDAR_WEEKDAY, text: name.clone() })
+        .collect::<Vec<_>>();
+
+      @Column {
+        class: CALENDAR,
+        tab_index: 0_i16,
+        on_key_down: move |e| {
+          if $write(this).handle_key(e.key(), first_day_of_week) {
+            e.stop_propagation();
+          }
+        },
+        @Flex {
+          class: CALENDAR_HEADER,
+          align_items: Align::Center,
+          @Icon {
+            class: CALENDAR_PREV,
+            on_tap: move |_| $write(this).prev_month(),
+            @ { svgs::ARROW_BACK }
+          }
+          @Expanded {
+            flex: 1.,
+            @Text {
+              class: CALENDAR_TITLE,
+              text: distinct_pipe!($read(this).month())
+                .map(move |month| CowArc::from(locale.month_title(&month))),
+            }
+          }
+          @Icon {
+            class: CALENDAR_NEXT,
+            on_tap: move |_| $write(this).next_month(),
+            @ { svgs::ARROW_FORWARD }
+          }
+        }
+        @Row { @ { weekdays } }
+        @ {
+          distinct_pipe!($read(this).month()).map(move |month| {
+            let cells = Calendar::month_cells(month, first_day_of_week);
+            let weeks = cells
+              .chunks(7)
+              .map(|week| {
+                let days = week
+                  .iter()
+                  .map(|cell| day_cell(&this, *cell, today))
+                  .collect::<Vec<_>>();
+                @Row { @ { days } }
+              })
+              .collect::<Vec<_>>();
+            @Column { @ { weeks } }
+          })
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn day_cell(
+  this: &impl StateWriter<Value = Calendar>, cell: Option<Date>, today: Date,
+) -> Widget<'static> {
+  let Some(date) = cell else {
+    let classes = class_array![CALENDAR_DAY_BLANK, CALENDAR_DAY];
+    return fn_widget! { @(classes) { @Void {} } }.into_widget();
+  };
+  let this = this.clone_writer();
+  let classes = class_array![
+    distinct_pipe!($read(this).day_class(&date, &today)),
+    distinct_pipe!(($read(this).focused() == Some(date)).then_some(CALENDAR_DAY_FOCUSED)),
+    CALENDAR_DAY
+  ];
+  fn_widget! {
+    @(classes) {
+      @Text {
+        h_align: HAlign::Center,
+        v_align: VAlign::Center,
+        text: date.day().to_string(),
+        on_tap: move |_| { $write(this).select(date); },
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  #[test]
+  fn date_math() {
+    let d = Date::new(2024, 2, 29).unwrap();
+    assert_eq!(d.weekday(), Weekday::Thursday);
+    assert_eq!(d.add_days(1), Date::new(2024, 3, 1).unwrap());
+    assert_eq!(d.add_days(-60), Date::new(2023, 12, 31).unwrap());
+    assert_eq!(d.add_months(12), Date::new(2025, 2, 28).unwrap());
+    assert_eq!(d.add_months(-14), Date::new(2022, 12, 29).unwrap());
+    assert_eq!(Date::new(1970, 1, 1).unwrap().weekday(), Weekday::Thursday);
+    assert_eq!(Date::from_days(Date::new(1900, 3, 1).unwrap().days()).to_string(), "1900-03-01");
+    assert!(Date::new(2023, 2, 29).is_none());
+    assert!(Date::new(2023, 13, 1).is_none());
+  }
+
+  #[test]
+  fn month_cells_follow_first_day_of_week() {
+    // 2025-03-01 is Saturday.
+    let month = Date::new(2025, 3, 1).unwrap();
+    let sunday_first = Calendar::month_cells(month, Weekday::Sunday);
+    assert_eq!(
+      sunday_first
+        .iter()
+        .take_while(|c| c.is_none())
+        .count(),
+      6
+    );
+    assert_eq!(sunday_first.len(), 42);
+
+    let monday_first = Calendar::month_cells(month, Weekday::Monday);
+    assert_eq!(
+      monday_first
+        .iter()
+        .take_while(|c| c.is_none())
+        .count(),
+      5
+    );
+    assert_eq!(monday_first.len(), 42);
+  }
+
+  #[test]
+  fn select_range_and_limits() {
+    let mut calendar = Calendar {
+      month: Date::new(2025, 3, 1).unwrap(),
+      selection: DateSelection::None,
+      range: true,
+      min: Date::new(2025, 3, 2),
+      max: None,
+      disabled_days: DayPredicate::new(|d| d.weekday() == Weekday::Sunday),
+      focused: None,
+    };
+    assert!(!calendar.select(Date::new(2025, 3, 1).unwrap()));
+    // Sunday
+    assert!(!calendar.select(Date::new(2025, 3, 9).unwrap()));
+
+    assert!(calendar.select(Date::new(2025, 3, 12).unwrap()));
+    assert!(calendar.select(Date::new(2025, 3, 4).unwrap()));
+    let selection = *calendar.selection();
+    assert_eq!(
+      selection,
+      DateSelection::Range(Date::new(2025, 3, 4).unwrap(), Date::new(2025, 3, 12))
+    );
+    assert!(selection.contains(&Date::new(2025, 3, 8).unwrap()));
+    assert!(selection.is_complete());
+  }
+
+  #[test]
+  fn focus_skips_unselectable_days() {
+    let mut calendar = Calendar {
+      month: Date::new(2025, 3, 1).unwrap(),
+      selection: DateSelection::Single(Date::new(2025, 3, 7).unwrap()),
+      range: false,
+      min: Date::new(2025, 3, 3),
+      max: Date::new(2025, 3, 20),
+      disabled_days: DayPredicate::new(|d| d.weekday() == Weekday::Saturday),
+      focused: None,
+    };
+    // Friday to the disabled Saturday.
+    calendar.move_focus(1);
+    assert_eq!(calendar.focused(), Date::new(2025, 3, 9));
+    calendar.move_focus(-7);
+    assert_eq!(calendar.focused(), Date::new(2025, 3, 3));
+    calendar.move_focus_months(1);
+    assert_eq!(calendar.focused(), Date::new(2025, 3, 20));
+  }
+
+  #[test]
+  fn keyboard_navigation() {
+    reset_test_env!();
+    let (selection, w_selection) = split_value(DateSelection::None);
+    let (month, w_month) = split_value(Date::new(2025, 3, 1).unwrap());
+    let w = fn_widget! {
+      let calendar = @Calendar {
+        auto_focus: true,
+        month: Date::new(2025, 3, 1).unwrap(),
+        selection: DateSelection::Single(Date::new(2025, 3, 31).unwrap()),
+      };
+      watch!(*$read(calendar).selection())
+        .subscribe(move |s| *$write(w_selection) = s);
+      watch!($read(calendar).month())
+        .subscribe(move |m| *$write(w_month) = m);
+      calendar
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(400., 400.));
+    wnd.draw_frame();
+
+    let key = |key: NamedKey, code: KeyCode| {
+      wnd.process_keyboard_event(
+        PhysicalKey::Code(code),
+        VirtualKey::Named(key),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+    };
+    key(NamedKey::ArrowRight, KeyCode::ArrowRight);
+    key(NamedKey::Enter, KeyCode::Enter);
+    wnd.draw_frame();
+    assert_eq!(*month.read(), Date::new(2025, 4, 1).unwrap());
+    assert_eq!(*selection.read(), DateSelection::Single(Date::new(2025, 4, 1).unwrap()));
+  }
+}
@@ -0,0 +1,452 @@
+use std::{future::Future, ops::Range, pin::Pin, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class name for the combobox widget
+  COMBOBOX,
+  /// Class name for the popup that shows the matched options
+  COMBOBOX_POPUP,
+  /// Class name for the part of an option that matches the input text
+  COMBOBOX_MATCH,
+}
+
+/// The future that resolves the options of an asynchronous combobox source.
+pub type OptionsFuture = Pin<Box<dyn Future<Output = Vec<CowArc<str>>>>>;
+
+/// The source of the options of a [`Combobox`]. An empty input text matches
+/// no option of any source, the async function isn't called for it.
+#[derive(Clone)]
+pub enum ComboboxOptions {
+  /// A fixed list of options, only the options that contain the input text
+  /// (case-insensitive) are shown.
+  Static(Vec<CowArc<str>>),
+  /// A function that resolves the options for the input text. The function
+  /// is called every time the text changes, and the result of a stale query
+  /// is discarded.
+  Async(Rc<dyn Fn(CowArc<str>) -> OptionsFuture>),
+}
+
+/// A text field built on [`Input`] that suggests options matching the text
+/// as the user types.
+///
+/// The matched options are shown in a popup below the field, the part of an
+/// option that matches the input text is highlighted with the
+/// `COMBOBOX_MATCH` class, and the popup reuses the `MENU` classes of the
+/// theme. Use `ArrowDown`/`ArrowUp` to move the active option, `Enter` to
+/// accept it and `Escape` to dismiss the popup. Tapping an option accepts it
+/// too.
+///
+/// The accepted option can be read by [`Combobox::value`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let combobox = @Combobox {
+///     options: ComboboxOptions::Static(vec!["Apple".into(), "Grape".into()]),
+///   };
+///   watch!($read(combobox).value().clone())
+///     .subscribe(|v| println!("accepted: {v}"));
+///   combobox
+/// };
+/// App::run(w);
+/// ```
+///
+/// Options can be loaded asynchronously, for example, from a server:
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = combobox! {
+///   options: ComboboxOptions::from_async(|text: CowArc<str>| async move {
+///     // query the server with the text.
+///     vec![text.to_string().into()]
+///   }),
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Combobox {
+  /// The source of the options.
+  #[declare(default)]
+  options: ComboboxOptions,
+  /// The accepted option.
+  #[declare(skip)]
+  value: CowArc<str>,
+  /// The text that the options are matched with.
+  #[declare(skip)]
+  query: CowArc<str>,
+  /// The options that match the query.
+  #[declare(skip)]
+  matches: Vec<CowArc<str>>,
+  /// The active option in the popup navigated by keyboard.
+  #[declare(skip)]
+  active: Option<usize>,
+  /// The sequence number of the latest query, used to drop stale async
+  /// results.
+  #[declare(skip)]
+  query_seq: usize,
+}
+
+impl ComboboxOptions {
+  /// Create an asynchronous source from a function that returns a future of
+  /// the options for the input text.
+  pub fn from_async<F, Fut>(f: F) -> Self
+  where
+    F: Fn(CowArc<str>) -> Fut + 'static,
+    Fut: Future<Output = Vec<CowArc<str>>> + 'static,
+  {
+    ComboboxOptions::Async(Rc::new(move |text| Box::pin(f(text))))
+  }
+}
+
+impl Default for ComboboxOptions {
+  fn default() -> Self { ComboboxOptions::Static(vec![]) }
+}
+
+impl From<Vec<CowArc<str>>> for ComboboxOptions {
+  fn from(v: Vec<CowArc<str>>) -> Self { ComboboxOptions::Static(v) }
+}
+
+impl Combobox {
+  /// Return the accepted option.
+  pub fn value(&self) -> &CowArc<str> { &self.value }
+
+  /// Return the options that match the current input text.
+  pub fn matches(&self) -> &[CowArc<str>] { &self.matches }
+
+  /// Return the index of the active option in the matches.
+  pub fn active(&self) -> Option<usize> { self.active }
+
+  /// Move the active option forward or backward, wrapping around the ends.
+  pub fn active_next(&mut self, forward: bool) {
+    let len = self.matches.len();
+    if len == 0 {
+      self.active = None;
+      return;
+    }
+    self.active = Some(match (self.active, forward) {
+      (Some(idx), true) => (idx + 1) % len,
+      (Some(idx), false) => (idx + len - 1) % len,
+      (None, true) => 0,
+      (None, false) => len - 1,
+    });
+  }
+
+  /// Dismiss the matched options.
+  pub fn dismiss(&mut self) {
+    self.matches.clear();
+    self.active = None;
+  }
+
+  fn accept(&mut self, idx: usize) -> Option<CowArc<str>> {
+    let label = self.matches.get(idx)?.clone();
+    self.value = label.clone();
+    self.dismiss();
+    Some(label)
+  }
+
+  fn query(this: impl StateWriter<Value = Self>, text: CowArc<str>) {
+    let mut w = this.write();
+    w.query_seq += 1;
+    w.query = text.clone();
+    w.active = None;
+    if text.is_empty() {
+      w.matches.clear();
+      return;
+    }
+    match w.options.clone() {
+      ComboboxOptions::Static(options) => {
+        w.matches = options
+          .into_iter()
+          .filter(|o| match_range(o, &text).is_some())
+          .collect();
+      }
+      ComboboxOptions::Async(f) => {
+        let seq = w.query_seq;
+        drop(w);
+        let fut = f(text);
+        AppCtx::spawn_local(async move {
+          let matches = fut.await;
+          let mut w = this.write();
+          if w.query_seq == seq {
+            w.matches = matches;
+          } else {
+            w.forget_modifies();
+          }
+        });
+      }
+    }
+  }
+}
+
+/// Return the byte range of the first case-insensitive occurrence of `query`
+/// in `label`.
+fn match_range(label: &str, query: &str) -> Option<Range<usize>> {
+  if query.is_empty() {
+    return None;
+  }
+  let eq = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+  label.char_indices().find_map(|(start, _)| {
+    let mut chars = label[start..].char_indices();
+    let mut end = start;
+    for q in query.chars() {
+      let (offset, c) = chars.next()?;
+      if !eq(c, q) {
+        return None;
+      }
+      end = start + offset + c.len_utf8();
+    }
+    Some(start..end)
+  })
+}
+
+fn highlight_label(label: CowArc<str>, query: &str) -> Widget<'static> {
+  fn text(s: &str) -> Widget<'static> {
+    let text: CowArc<str> = s.to_string().into();
+    text! { text }.into_widget()
+  }
+
+  let content = match match_range(&label, query) {
+    Some(rg) => row! {
+      @ { text(&label[..rg.start]) }
+      @Text {
+        class: COMBOBOX_MATCH,
+        text: CowArc::from(label[rg.clone()].to_string()),
+      }
+      @ { text(&label[rg.end..]) }
+    }
+    .into_widget(),
+    None => text(&label),
+  };
+  class! { class: MENU_ITEM_LABEL, @ { content } }.into_widget()
+}
+
+impl Compose for Combobox {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let mut input = @Input {};
+      let input_track = input.track_id();
+      let wnd = BuildCtx::get().window();
+
+      let accept: Rc<dyn Fn(usize)> = Rc::new(move |idx| {
+        if let Some(label) = $write(this).accept(idx) {
+          let mut input = $write(input);
+          input.set_text(&label);
+          input.select(label.len(), label.len());
+        }
+      });
+
+      let popup = Overlay::new(
+        {
+          let accept = accept.clone();
+          fn_widget! {
+            let accept = accept.clone();
+            let classes = class_array![COMBOBOX_POPUP, MENU];
+            @(classes) {
+              @Column {
+                on_disposed: move |_| {
+                  if !$read(this).matches.is_empty() {
+                    $write(this).dismiss();
+                  }
+                },
+                @ {
+                  pipe!($read(this).matches.clone()).map(move |matches| {
+                    let accept = accept.clone();
+                    let query = $read(this).query.clone();
+                    matches.into_iter().enumerate().map(move |(idx, label)| {
+                      let accept = accept.clone();
+                      @Row {
+                        class: distinct_pipe! {
+                          if $read(this).active == Some(idx) {
+                            MENU_ITEM_SELECTED
+                          } else {
+                            MENU_ITEM
+                          }
+                        },
+                        align_items: Align::Center,
+                        on_tap: move |_| accept(idx),
+                        @ { highlight_label(label, &query) }
+                      }
+                    })
+                  })
+                }
+              }
+            }
+          }
+        },
+        OverlayStyle { auto_close_policy: AutoClosePolicy::TAP_OUTSIDE, mask: None },
+      );
+
+      let u = watch!($read(input).text().clone())
+        .distinct_until_changed()
+        .subscribe(move |text| {
+          // The text set by accepting an option isn't a query.
+          if text.is_empty() || text != $read(this).value {
+            Combobox::query($writer(this), text);
+          }
+        });
+
+      let u2 = watch!(!$read(this).matches.is_empty())
+        .distinct_until_changed()
+        .subscribe(move |show| {
+          if !show {
+            popup.close();
+          } else if let Some(id) = input_track.get() {
+            let pos = wnd.map_to_global(Point::zero(), id);
+            let size = wnd.widget_size(id).unwrap_or_default();
+            popup.show_map(anchor_below(Rect::new(pos, size)), wnd.clone());
+          }
+        });
+
+      @(input) {
+        class: COMBOBOX,
+        on_key_down: move |e| match e.key() {
+          VirtualKey::Named(NamedKey::ArrowDown) => $write(this).active_next(true),
+          VirtualKey::Named(NamedKey::ArrowUp) => $write(this).active_next(false),
+          VirtualKey::Named(NamedKey::Enter) => {
+            let active = $read(this).active;
+            if let Some(idx) = active {
+              accept(idx);
+            }
+          }
+          VirtualKey::Named(NamedKey::Escape) => $write(this).dismiss(),
+          _ => {}
+        },
+        on_disposed: move |_| {
+          u.unsubscribe();
+          u2.unsubscribe();
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  #[test]
+  fn match_range_ignore_case() {
+    assert_eq!(match_range("Apple", "pl"), Some(2..4));
+    assert_eq!(match_range("Apple", "AP"), Some(0..2));
+    assert_eq!(match_range("Ärger", "är"), Some(0..3));
+    assert_eq!(match_range("Apple", "x"), None);
+    assert_eq!(match_range("Apple", ""), None);
+  }
+
+  fn key_down(wnd: &TestWindow, key: NamedKey, code: KeyCode) {
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+  }
+
+  #[test]
+  fn filter_and_accept() {
+    reset_test_env!();
+    let (value, w_value) = split_value(CowArc::from(""));
+    let (matches, w_matches) = split_value(vec![]);
+    let w = fn_widget! {
+      let combobox = @Combobox {
+        auto_focus: true,
+        options: ComboboxOptions::Static(
+          vec!["Apple".into(), "Banana".into(), "Grape".into()]
+        ),
+      };
+      watch!($read(combobox).value().clone())
+        .subscribe(move |v| *$write(w_value) = v);
+      watch!($read(combobox).matches().to_vec())
+        .subscribe(move |v| *$write(w_matches) = v);
+      combobox
+    };
+
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    wnd.process_receive_chars("ap".into());
+    wnd.draw_frame();
+    assert_eq!(*matches.read(), vec![CowArc::from("Apple"), CowArc::from("Grape")]);
+
+    key_down(&wnd, NamedKey::ArrowDown, KeyCode::ArrowDown);
+    key_down(&wnd, NamedKey::ArrowDown, KeyCode::ArrowDown);
+    key_down(&wnd, NamedKey::Enter, KeyCode::Enter);
+    wnd.draw_frame();
+    assert_eq!(&**value.read(), "Grape");
+    assert!(matches.read().is_empty());
+  }
+
+  #[test]
+  fn async_options() {
+    reset_test_env!();
+    let (matches, w_matches) = split_value(vec![]);
+    let w = fn_widget! {
+      let combobox = @Combobox {
+        auto_focus: true,
+        options: ComboboxOptions::from_async(|text: CowArc<str>| async move {
+          vec![format!("{text}1").into(), format!("{text}2").into()]
+        }),
+      };
+      watch!($read(combobox).matches().to_vec())
+        .subscribe(move |v| *$write(w_matches) = v);
+      combobox
+    };
+
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    wnd.process_receive_chars("a".into());
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    wnd.draw_frame();
+    assert_eq!(*matches.read(), vec![CowArc::from("a1"), CowArc::from("a2")]);
+  }
+
+  #[test]
+  fn empty_text_clears_matches() {
+    reset_test_env!();
+    let queries = Rc::new(std::cell::RefCell::new(vec![]));
+    let c_queries = queries.clone();
+    let sources = [
+      ComboboxOptions::Static(vec!["Apple".into()]),
+      ComboboxOptions::from_async(move |text: CowArc<str>| {
+        c_queries.borrow_mut().push(text.to_string());
+        async { vec!["Apple".into()] }
+      }),
+    ];
+    for options in sources {
+      let (matches, w_matches) = split_value(vec![]);
+      let w = fn_widget! {
+        let combobox = @Combobox { auto_focus: true, options: options.clone() };
+        watch!($read(combobox).matches().to_vec())
+          .subscribe(move |v| *$write(w_matches) = v);
+        combobox
+      };
+
+      let wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+      wnd.draw_frame();
+      wnd.process_receive_chars("a".into());
+      wnd.draw_frame();
+      AppCtx::run_until_stalled();
+      wnd.draw_frame();
+      assert_eq!(*matches.read(), vec![CowArc::from("Apple")]);
+
+      key_down(&wnd, NamedKey::Backspace, KeyCode::Backspace);
+      wnd.draw_frame();
+      AppCtx::run_until_stalled();
+      wnd.draw_frame();
+      assert!(matches.read().is_empty());
+    }
+    assert_eq!(*queries.borrow(), ["a"]);
+  }
+}
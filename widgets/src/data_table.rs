@@ -0,0 +1,765 @@
+use std::{cell::Cell, cmp::Ordering, ops::Range, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Root container class for the data table
+  DATA_TABLE,
+  /// Class for the header row of the data table
+  DATA_TABLE_HEADER,
+  /// Class for every cell of the header row
+  DATA_TABLE_HEADER_CELL,
+  /// Class for the header cell that is dragged to reorder
+  DATA_TABLE_HEADER_CELL_DRAGGING,
+  /// Class for the indicator of an ascending sorted column
+  DATA_TABLE_SORT_ASCENDING,
+  /// Class for the indicator of a descending sorted column
+  DATA_TABLE_SORT_DESCENDING,
+  /// Class for the handle at the end of a header cell to resize the column
+  DATA_TABLE_RESIZE_HANDLE,
+  /// Class for every row of the body
+  DATA_TABLE_ROW,
+  /// Class for the selected rows of the body
+  DATA_TABLE_ROW_SELECTED,
+  /// Class for every cell of the body
+  DATA_TABLE_CELL,
+}
+
+/// The minimum width of a column when the user resizes it.
+pub const MIN_COLUMN_WIDTH: f32 = 24.;
+
+/// How a column of a [`DataTable`] decides its width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnWidth {
+  /// The column has a fixed width in pixels.
+  Fixed(f32),
+  /// The column shares the remaining width of the table after the fixed
+  /// columns with the other flexible columns by the `flex` factor, but is not
+  /// narrower than `min`.
+  Flex { flex: f32, min: f32 },
+}
+
+/// The direction a [`DataTable`] is sorted by a column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+  Ascending,
+  Descending,
+}
+
+type CellBuilder<R> = dyn Fn(&R) -> Widget<'static>;
+type RowComparator<R> = dyn Fn(&R, &R) -> Ordering;
+
+/// The definition of a column of a [`DataTable`].
+pub struct DataColumn<R> {
+  header: CowArc<str>,
+  width: ColumnWidth,
+  cell: Rc<CellBuilder<R>>,
+  compare: Option<Rc<RowComparator<R>>>,
+}
+
+/// A table that displays rows of data by the column definitions.
+///
+/// The header of the table always stays on the top, and scrolls horizontally
+/// in sync with the body. Only the rows in the view are built, so the table
+/// works with large datasets, every row has the same height of `row_height`.
+///
+/// The user can:
+/// - tap a header to sort the rows by the column if it has a comparator, tap
+///   again to reverse the order and the third time to restore the original
+///   order.
+/// - drag the handle at the end of a header to resize the column.
+/// - drag a header to reorder the column.
+/// - tap a row to select it, follow the `select_mode`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// struct File {
+///   name: &'static str,
+///   size: u64,
+/// }
+///
+/// let w = fn_widget! {
+///   @DataTable {
+///     select_mode: ListSelectMode::Multi,
+///     columns: vec![
+///       DataColumn::new("Name", |f: &File| {
+///         let name = f.name;
+///         text! { text: name }.into_widget()
+///       })
+///         .with_width(ColumnWidth::Flex { flex: 1., min: 120. })
+///         .with_sort_key(|f| f.name),
+///       DataColumn::new("Size", |f: &File| {
+///         let size = f.size.to_string();
+///         text! { text: size }.into_widget()
+///       })
+///         .with_sort_key(|f| f.size),
+///     ],
+///     rows: vec![File { name: "a.txt", size: 12 }, File { name: "b.txt", size: 3 }],
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct DataTable<R: 'static> {
+  /// The columns of the table.
+  #[declare(default)]
+  columns: Vec<DataColumn<R>>,
+  /// The data rows of the table.
+  #[declare(default)]
+  rows: Vec<R>,
+  /// The height of every row in the body.
+  #[declare(default = 52.)]
+  row_height: f32,
+  /// The selection mode of the rows, see [`ListSelectMode`].
+  #[declare(default)]
+  select_mode: ListSelectMode,
+  #[declare(skip)]
+  order: Vec<usize>,
+  #[declare(skip)]
+  sort: Option<(usize, SortDirection)>,
+  #[declare(skip)]
+  selected: Vec<usize>,
+  #[declare(skip)]
+  view_width: f32,
+  #[declare(skip)]
+  dragging: Option<(usize, f32)>,
+  /// Bumps when the rows need to rebuild.
+  #[declare(skip)]
+  revision: usize,
+}
+
+impl<R> DataColumn<R> {
+  /// Create a column with the header text and the builder of its cells, the
+  /// column is 120 pixels wide by default.
+  ///
+  /// The widget that the builder returns can't borrow the row, so copy what it
+  /// needs from the row before building the widget.
+  pub fn new(
+    header: impl Into<CowArc<str>>, cell: impl Fn(&R) -> Widget<'static> + 'static,
+  ) -> Self {
+    Self {
+      header: header.into(),
+      width: ColumnWidth::Fixed(120.),
+      cell: Rc::new(cell),
+      compare: None,
+    }
+  }
+
+  pub fn with_width(mut self, width: ColumnWidth) -> Self {
+    self.width = width;
+    self
+  }
+
+  /// Make the column sortable by the comparator.
+  pub fn with_comparator(mut self, compare: impl Fn(&R, &R) -> Ordering + 'static) -> Self {
+    self.compare = Some(Rc::new(compare));
+    self
+  }
+
+  /// Make the column sortable by the key of the rows.
+  pub fn with_sort_key<K: Ord>(self, key: impl Fn(&R) -> K + 'static) -> Self {
+    self.with_comparator(move |a, b| key(a).cmp(&key(b)))
+  }
+
+  pub fn header(&self) -> &CowArc<str> { &self.header }
+
+  pub fn width(&self) -> ColumnWidth { self.width }
+
+  /// Return true if the column has a comparator.
+  pub fn is_sortable(&self) -> bool { self.compare.is_some() }
+}
+
+impl<R> DataTable<R> {
+  pub fn columns(&self) -> &[DataColumn<R>] { &self.columns }
+
+  pub fn rows(&self) -> &[R] { &self.rows }
+
+  /// Replace the rows, the sort is kept and the selection is cleared.
+  pub fn set_rows(&mut self, rows: Vec<R>) {
+    self.rows = rows;
+    self.selected.clear();
+    self.update_order();
+  }
+
+  /// Return the index of the row displayed at the position `pos`.
+  pub fn display_row(&self, pos: usize) -> usize { self.order.get(pos).copied().unwrap_or(pos) }
+
+  /// Return the indices of the rows in the display order.
+  pub fn display_order(&self) -> impl Iterator<Item = usize> + '_ {
+    (0..self.rows.len()).map(|pos| self.display_row(pos))
+  }
+
+  /// Return the sorted column and the direction.
+  pub fn sort_state(&self) -> Option<(usize, SortDirection)> { self.sort }
+
+  /// Sort the rows by the column, does nothing if the column has no
+  /// comparator.
+  pub fn sort_by(&mut self, column: usize, dir: SortDirection) {
+    if self.columns[column].is_sortable() {
+      self.sort = Some((column, dir));
+      self.update_order();
+    }
+  }
+
+  /// Restore the original order of the rows.
+  pub fn clear_sort(&mut self) {
+    self.sort = None;
+    self.update_order();
+  }
+
+  /// Cycle the sort of the column as the user taps its header: ascending,
+  /// descending, and then the original order.
+  pub fn toggle_sort(&mut self, column: usize) {
+    match self.sort {
+      Some((c, SortDirection::Ascending)) if c == column => {
+        self.sort_by(column, SortDirection::Descending)
+      }
+      Some((c, SortDirection::Descending)) if c == column => self.clear_sort(),
+      _ => self.sort_by(column, SortDirection::Ascending),
+    }
+  }
+
+  /// Return the width of every column in the current view.
+  pub fn column_widths(&self) -> Vec<f32> {
+    let (fixed, flex) = self
+      .columns
+      .iter()
+      .fold((0., 0.), |(fixed, flex), c| match c.width {
+        ColumnWidth::Fixed(w) => (fixed + w, flex),
+        ColumnWidth::Flex { flex: f, .. } => (fixed, flex + f),
+      });
+    let remaining = (self.view_width - fixed).max(0.);
+    self
+      .columns
+      .iter()
+      .map(|c| match c.width {
+        ColumnWidth::Fixed(w) => w,
+        ColumnWidth::Flex { flex: f, min } if flex > 0. => (remaining * f / flex).max(min),
+        ColumnWidth::Flex { min, .. } => min,
+      })
+      .collect()
+  }
+
+  /// Return the width of all the columns.
+  pub fn total_width(&self) -> f32 { self.column_widths().iter().sum() }
+
+  /// Set the width of the column, the column becomes fixed width.
+  pub fn set_column_width(&mut self, column: usize, width: f32) {
+    self.columns[column].width = ColumnWidth::Fixed(width.max(MIN_COLUMN_WIDTH));
+  }
+
+  /// Move the column from the index `from` to the index `to`.
+  pub fn move_column(&mut self, from: usize, to: usize) {
+    if from == to {
+      return;
+    }
+    let column = self.columns.remove(from);
+    self.columns.insert(to, column);
+    if let Some((sorted, _)) = self.sort.as_mut() {
+      *sorted = match *sorted {
+        c if c == from => to,
+        c if from < c && c <= to => c - 1,
+        c if to <= c && c < from => c + 1,
+        c => c,
+      };
+    }
+    self.revision += 1;
+  }
+
+  pub fn selected(&self) -> &[usize] { &self.selected }
+
+  pub fn is_selected(&self, row: usize) -> bool { self.selected.contains(&row) }
+
+  /// Select the row as the user taps it. In `Single` mode the row becomes the
+  /// only selected one, in `Multi` mode the row is toggled.
+  pub fn select(&mut self, row: usize) {
+    match self.select_mode {
+      ListSelectMode::None => {}
+      ListSelectMode::Single => self.selected = vec![row],
+      ListSelectMode::Multi => {
+        if let Some(idx) = self.selected.iter().position(|r| *r == row) {
+          self.selected.remove(idx);
+        } else {
+          self.selected.push(row);
+        }
+      }
+    }
+  }
+
+  pub fn deselect_all(&mut self) { self.selected.clear(); }
+
+  /// Return the range of the display order that is visible in a view of
+  /// `view_height` scrolled to `scroll_y`.
+  pub fn visible_rows(&self, scroll_y: f32, view_height: f32) -> Range<usize> {
+    if self.row_height <= 0. {
+      return 0..self.rows.len();
+    }
+    let start = (scroll_y / self.row_height).floor().max(0.) as usize;
+    let end = ((scroll_y + view_height) / self.row_height).ceil() as usize;
+    start.min(self.rows.len())..end.min(self.rows.len())
+  }
+
+  /// Sort the rows by the sorted column. The order is empty if the rows are
+  /// not sorted, that means the rows are displayed in their original order.
+  fn update_order(&mut self) {
+    self.order.clear();
+    let compare = self
+      .sort
+      .and_then(|(column, dir)| Some((self.columns[column].compare.clone()?, dir)));
+    if let Some((compare, dir)) = compare {
+      self.order = (0..self.rows.len()).collect();
+      self.order.sort_by(|a, b| {
+        let ord = compare(&self.rows[*a], &self.rows[*b]);
+        if dir == SortDirection::Ascending { ord } else { ord.reverse() }
+      });
+    }
+    self.revision += 1;
+  }
+
+  /// Return the index of the column at the position `x` of the header.
+  fn column_at(&self, x: f32) -> usize {
+    let mut end = 0.;
+    for (idx, w) in self.column_widths().into_iter().enumerate() {
+      end += w;
+      if x < end {
+        return idx;
+      }
+    }
+    self.columns.len().saturating_sub(1)
+  }
+
+  fn column_start(&self, column: usize) -> f32 { self.column_widths()[..column].iter().sum() }
+}
+
+impl<R: 'static> Compose for DataTable<R> {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let scrollbar = Scrollbar::new(Scrollable::Both);
+      let body_scroll = scrollbar.inner_scrollable_widget().clone_writer();
+      let mut header_scroll = ScrollableWidget::default();
+      header_scroll.scrollable = Scrollable::X;
+      let header_scroll = Stateful::new(header_scroll);
+
+      // Keep the header and the body scrolled to the same horizontal position.
+      let u1 = watch!($read(body_scroll).get_scroll_pos().x)
+        .distinct_until_changed()
+        .subscribe(move |x| {
+          if $read(header_scroll).get_scroll_pos().x != x {
+            $write(header_scroll).jump_to(Point::new(x, 0.));
+          }
+        });
+      let u2 = watch!($read(header_scroll).get_scroll_pos().x)
+        .distinct_until_changed()
+        .subscribe(move |x| {
+          let pos = $read(body_scroll).get_scroll_pos();
+          if pos.x != x {
+            $write(body_scroll).jump_to(Point::new(x, pos.y));
+          }
+        });
+      let u3 = watch!($read(body_scroll).scroll_view_size().width)
+        .distinct_until_changed()
+        .subscribe(move |width| $write(this).view_width = width);
+
+      // Compute the column widths once for all the cells, every cell only
+      // follows the width of its own column.
+      let widths = Stateful::new($read(this).column_widths());
+      let u4 = watch!($read(this).column_widths())
+        .distinct_until_changed()
+        .subscribe(move |w| {
+          if *$read(widths) != w {
+            *$write(widths) = w;
+          }
+        });
+
+      let header = @(header_scroll.clone_writer()) {
+        @ { header_row(&this, &widths) }
+      };
+
+      let body = @Stack {
+        @SizedBox {
+          size: distinct_pipe! {
+            let this = $read(this);
+            let width = $read(widths).iter().sum();
+            Size::new(width, this.rows.len() as f32 * this.row_height)
+          }
+        }
+        @ {
+          distinct_pipe! {
+            let scroll = $read(body_scroll);
+            let range = $read(this).visible_rows(
+              scroll.get_scroll_pos().y,
+              scroll.scroll_view_size().height,
+            );
+            (range, $read(this).revision)
+          }
+          .map(move |(range, _)| {
+            range
+              .map(|pos| data_row(&this, &widths, $read(this).display_row(pos), pos))
+              .collect::<Vec<_>>()
+          })
+        }
+      };
+
+      @Flex {
+        class: DATA_TABLE,
+        direction: Direction::Vertical,
+        align_items: Align::Stretch,
+        on_disposed: move |_| {
+          u1.unsubscribe();
+          u2.unsubscribe();
+          u3.unsubscribe();
+          u4.unsubscribe();
+        },
+        @ { header }
+        @Expanded {
+          @(scrollbar) { @ { body } }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn header_row<R: 'static>(
+  this: &impl StateWriter<Value = DataTable<R>>, widths: &impl StateWatcher<Value = Vec<f32>>,
+) -> Widget<'static> {
+  let this = this.clone_writer();
+  let widths = widths.clone_watcher();
+  fn_widget! {
+    @Row {
+      class: DATA_TABLE_HEADER,
+      @ {
+        distinct_pipe!(($read(this).columns.len(), $read(this).revision)).map(move |(len, _)| {
+          (0..len)
+            .map(|column| header_cell(&this, &widths, column))
+            .collect::<Vec<_>>()
+        })
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn header_cell<R: 'static>(
+  this: &impl StateWriter<Value = DataTable<R>>, widths: &impl StateWatcher<Value = Vec<f32>>,
+  column: usize,
+) -> Widget<'static> {
+  let this = this.clone_writer();
+  let widths = widths.clone_watcher();
+  fn_widget! {
+    let header = $read(this).columns[column].header.clone();
+    // The pointer grab and the start position of a reorder or resize gesture.
+    let reorder = Stateful::new(None);
+    let resize = Stateful::new(None);
+    let dragged = Rc::new(Cell::new(false));
+    let c_dragged = dragged.clone();
+
+    @Flex {
+      class: distinct_pipe!(match $read(this).dragging {
+        Some((c, _)) if c == column => DATA_TABLE_HEADER_CELL_DRAGGING,
+        _ => DATA_TABLE_HEADER_CELL,
+      }),
+      align_items: Align::Center,
+      clamp: distinct_pipe!(BoxClamp::fixed_width($read(widths)[column])),
+      transform: distinct_pipe!(match $read(this).dragging {
+        Some((c, offset)) if c == column => Transform::translation(offset, 0.),
+        _ => Transform::identity(),
+      }),
+      on_tap: move |_| {
+        if !c_dragged.replace(false) {
+          $write(this).toggle_sort(column);
+        }
+      },
+      on_pointer_down: move |e| {
+        if let Some(handle) = GrabPointer::grab(e.current_target(), &e.window()) {
+          *$write(reorder) = Some((handle, e.global_pos().x));
+        }
+      },
+      on_pointer_move: move |e| {
+        if let Some((_, start)) = $read(reorder).as_ref() {
+          let offset = e.global_pos().x - start;
+          if $read(this).dragging.is_some() || offset.abs() > 4. {
+            $write(this).dragging = Some((column, offset));
+          }
+        }
+      },
+      on_pointer_up: move |_| {
+        $write(reorder).take();
+        let Some((from, offset)) = $write(this).dragging.take() else { return };
+        dragged.set(true);
+        let mut table = $write(this);
+        let center = table.column_start(from) + table.column_widths()[from] / 2. + offset;
+        let to = table.column_at(center);
+        table.move_column(from, to);
+      },
+      @Expanded {
+        @Text { text: header }
+      }
+      @ {
+        distinct_pipe!($read(this).sort_state()).map(move |sort| match sort {
+          Some((c, dir)) if c == column => {
+            let class = match dir {
+              SortDirection::Ascending => DATA_TABLE_SORT_ASCENDING,
+              SortDirection::Descending => DATA_TABLE_SORT_DESCENDING,
+            };
+            Some(@Icon { class, @ { svgs::ARROW_DROP_DOWN } })
+          }
+          _ => None,
+        })
+      }
+      @Void {
+        class: DATA_TABLE_RESIZE_HANDLE,
+        v_align: VAlign::Stretch,
+        on_tap: move |e| e.stop_propagation(),
+        on_pointer_down: move |e| {
+          e.stop_propagation();
+          if let Some(handle) = GrabPointer::grab(e.current_target(), &e.window()) {
+            let width = $read(widths)[column];
+            *$write(resize) = Some((handle, e.global_pos().x, width));
+          }
+        },
+        on_pointer_move: move |e| {
+          e.stop_propagation();
+          if let Some((_, start, width)) = $read(resize).as_ref() {
+            $write(this).set_column_width(column, width + e.global_pos().x - start);
+          }
+        },
+        on_pointer_up: move |e| {
+          e.stop_propagation();
+          $write(resize).take();
+        },
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn data_row<R: 'static>(
+  this: &impl StateWriter<Value = DataTable<R>>, widths: &impl StateWatcher<Value = Vec<f32>>,
+  row: usize, pos: usize,
+) -> Widget<'static> {
+  let this = this.clone_writer();
+  let widths = widths.clone_watcher();
+  fn_widget! {
+    let (cells, row_height) = {
+      let table = $read(this);
+      let cells = table
+        .columns
+        .iter()
+        .map(|c| (c.cell)(&table.rows[row]))
+        .collect::<Vec<_>>();
+      (cells, table.row_height)
+    };
+    let cells = cells.into_iter().enumerate().map(move |(column, cell)| {
+      @SizedBox {
+        size: distinct_pipe!(Size::new($read(widths)[column], row_height)),
+        @Class { class: DATA_TABLE_CELL, @ { cell } }
+      }
+    });
+    @Row {
+      class: distinct_pipe!(if $read(this).is_selected(row) {
+        DATA_TABLE_ROW_SELECTED
+      } else {
+        DATA_TABLE_ROW
+      }),
+      anchor: Anchor::top(pos as f32 * row_height),
+      on_tap: move |_| $write(this).select(row),
+      @ { cells }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::RefCell;
+
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  struct Fruit {
+    name: &'static str,
+    price: u32,
+  }
+
+  fn table(select_mode: ListSelectMode) -> DataTable<Fruit> {
+    let rows = vec![
+      Fruit { name: "banana", price: 3 },
+      Fruit { name: "apple", price: 5 },
+      Fruit { name: "cherry", price: 1 },
+    ];
+    DataTable {
+      columns: vec![
+        DataColumn::new("Name", |f: &Fruit| {
+          let name = f.name;
+          text! { text: name }.into_widget()
+        })
+        .with_sort_key(|f| f.name),
+        DataColumn::new("Price", |f: &Fruit| {
+          let price = f.price.to_string();
+          text! { text: price }.into_widget()
+        })
+        .with_width(ColumnWidth::Flex { flex: 1., min: 50. })
+        .with_sort_key(|f| f.price),
+        DataColumn::new("Note", |_: &Fruit| void! {}.into_widget()),
+      ],
+      rows,
+      row_height: 52.,
+      select_mode,
+      order: vec![],
+      sort: None,
+      selected: vec![],
+      view_width: 0.,
+      dragging: None,
+      revision: 0,
+    }
+  }
+
+  #[test]
+  fn sort_cycle() {
+    let mut table = table(ListSelectMode::None);
+    table.toggle_sort(0);
+    assert_eq!(table.display_order().collect::<Vec<_>>(), [1, 0, 2]);
+    table.toggle_sort(0);
+    assert_eq!(table.display_order().collect::<Vec<_>>(), [2, 0, 1]);
+    table.toggle_sort(0);
+    assert_eq!(table.display_order().collect::<Vec<_>>(), [0, 1, 2]);
+
+    table.toggle_sort(1);
+    assert_eq!(table.display_order().collect::<Vec<_>>(), [2, 0, 1]);
+    // The column without comparator is not sortable.
+    table.toggle_sort(2);
+    assert_eq!(table.sort_state(), Some((1, SortDirection::Ascending)));
+  }
+
+  #[test]
+  fn column_width_and_reorder() {
+    let mut table = table(ListSelectMode::None);
+    assert_eq!(table.column_widths(), [120., 50., 120.]);
+    table.view_width = 400.;
+    assert_eq!(table.column_widths(), [120., 160., 120.]);
+    assert_eq!(table.column_at(130.), 1);
+
+    table.set_column_width(1, 10.);
+    assert_eq!(table.columns()[1].width(), ColumnWidth::Fixed(MIN_COLUMN_WIDTH));
+
+    table.sort_by(1, SortDirection::Descending);
+    table.move_column(1, 2);
+    let headers: Vec<_> = table
+      .columns()
+      .iter()
+      .map(|c| c.header().to_string())
+      .collect();
+    assert_eq!(headers, ["Name", "Note", "Price"]);
+    assert_eq!(table.sort_state(), Some((2, SortDirection::Descending)));
+  }
+
+  #[test]
+  fn row_selection() {
+    let mut single = table(ListSelectMode::Single);
+    single.select(0);
+    single.select(2);
+    assert_eq!(single.selected(), [2]);
+
+    let mut multi = table(ListSelectMode::Multi);
+    multi.select(0);
+    multi.select(2);
+    multi.select(0);
+    assert_eq!(multi.selected(), [2]);
+  }
+
+  #[test]
+  fn virtualized_rows() {
+    reset_test_env!();
+
+    let built = Rc::new(Cell::new(0));
+    let c_built = built.clone();
+    let w = fn_widget! {
+      let c_built = c_built.clone();
+      @DataTable {
+        row_height: 20.,
+        columns: vec![DataColumn::new("Index", move |i: &usize| {
+          c_built.set(c_built.get() + 1);
+          let i = i.to_string();
+          text! { text: i }.into_widget()
+        })],
+        rows: (0..10_000).collect::<Vec<_>>(),
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(built.get() > 0);
+    assert!(built.get() < 30);
+  }
+
+  #[test]
+  fn header_scrolls_with_body() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      @DataTable {
+        columns: (0..3)
+          .map(|i| DataColumn::new(format!("Column {i}"), |_: &usize| void! {}.into_widget()))
+          .collect::<Vec<_>>(),
+        rows: (0..100).collect::<Vec<_>>(),
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    wnd.process_cursor_move(Point::new(100., 100.));
+    wnd.process_wheel(-50., -30.);
+    wnd.draw_frame();
+    let header = wnd.layout_info_by_path(&[0, 0, 0]).unwrap();
+    assert_eq!(header.pos.x, -50.);
+  }
+
+  #[test]
+  fn cells_follow_column_width() {
+    reset_test_env!();
+
+    // The last laid out width of the cells in every column.
+    let widths = Rc::new(RefCell::new([0.; 2]));
+    let column = |i: usize| {
+      let widths = widths.clone();
+      DataColumn::new(format!("Column {i}"), move |_: &usize| {
+        let widths = widths.clone();
+        void! {
+          on_performed_layout: move |e| {
+            widths.borrow_mut()[i] = e.box_size().unwrap().width;
+          }
+        }
+        .into_widget()
+      })
+    };
+    let table = Stateful::new(DataTable {
+      columns: vec![column(0), column(1)],
+      rows: (0..3).collect::<Vec<_>>(),
+      row_height: 52.,
+      select_mode: ListSelectMode::None,
+      order: vec![],
+      sort: None,
+      selected: vec![],
+      view_width: 0.,
+      dragging: None,
+      revision: 0,
+    });
+    let c_table = table.clone_writer();
+    let w = fn_widget! { c_table.clone_writer() };
+    let wnd = TestWindow::new_with_size(w, Size::new(400., 400.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(*widths.borrow(), [120., 120.]);
+
+    table.write().set_column_width(0, 200.);
+    wnd.draw_frame();
+    assert_eq!(*widths.borrow(), [200., 120.]);
+  }
+}
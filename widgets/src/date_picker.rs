@@ -0,0 +1,248 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class name for the field of the date picker
+  DATE_PICKER,
+  /// Class name for the text of the selected date in the field
+  DATE_PICKER_LABEL,
+  /// Class name for the placeholder text when no date is selected
+  DATE_PICKER_PLACEHOLDER,
+  /// Class name for the dropdown indicator of the field
+  DATE_PICKER_INDICATOR,
+  /// Class name for the popup that holds the calendar
+  DATE_PICKER_POPUP,
+}
+
+/// A form control that displays the selected date, and pops up a
+/// [`Calendar`] below the field to pick a date or a range of dates.
+///
+/// Tap the field or press `ArrowDown`, `Enter` or `Space` when it is focused
+/// to open the popup. The popup closes once a date is picked in single mode,
+/// or both endpoints are picked in range mode.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let picker = @DatePicker {
+///     range: true,
+///     placeholder: "Check in – Check out",
+///     min: Date::new(2025, 1, 1),
+///   };
+///   watch!(*$read(picker).selection())
+///     .subscribe(|s| println!("selection: {s}"));
+///   picker
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct DatePicker {
+  /// The selected date or range.
+  #[declare(default)]
+  selection: DateSelection,
+  /// Whether the user picks a range of dates instead of a single date.
+  #[declare(default)]
+  range: bool,
+  /// The earliest date that can be selected.
+  #[declare(default)]
+  min: Option<Date>,
+  /// The latest date that can be selected.
+  #[declare(default)]
+  max: Option<Date>,
+  /// The days that can not be selected.
+  #[declare(default)]
+  disabled_days: DayPredicate,
+  /// The text displayed in the field when no date is selected.
+  #[declare(default)]
+  placeholder: CowArc<str>,
+  #[declare(skip)]
+  open: bool,
+}
+
+impl DatePicker {
+  pub fn selection(&self) -> &DateSelection { &self.selection }
+
+  pub fn set_selection(&mut self, selection: DateSelection) { self.selection = selection; }
+
+  /// Return true if the calendar popup is open.
+  pub fn is_open(&self) -> bool { self.open }
+
+  /// Open the calendar popup.
+  pub fn open(&mut self) { self.open = true; }
+
+  /// Close the calendar popup.
+  pub fn close(&mut self) { self.open = false; }
+
+  fn display_text(&self) -> CowArc<str> {
+    if self.selection == DateSelection::None {
+      self.placeholder.clone()
+    } else {
+      self.selection.to_string().into()
+    }
+  }
+
+  fn calendar_popup(this: impl StateWriter<Value = Self>) -> Overlay {
+    Overlay::new(
+      fn_widget! {
+        let today = Today::of(BuildCtx::get());
+        let (month, selection, range, min, max, disabled_days) = {
+          let this = $read(this);
+          let month = this.selection.start().unwrap_or(today);
+          (month, this.selection, this.range, this.min, this.max, this.disabled_days.clone())
+        };
+        let calendar = @Calendar {
+          auto_focus: true,
+          month, selection, range, min, max, disabled_days,
+        };
+        let u = watch!(*$read(calendar).selection())
+          .distinct_until_changed()
+          .filter(move |selection| *selection != $read(this).selection)
+          .subscribe(move |selection| {
+            let mut this = $write(this);
+            this.selection = selection;
+            if selection.is_complete() {
+              this.open = false;
+            }
+          });
+        // The class wraps the calendar from outside, so the `auto_focus` is
+        // merged with the focus node of the calendar.
+        @Class {
+          class: DATE_PICKER_POPUP,
+          on_disposed: move |_| {
+            u.unsubscribe();
+            if $read(this).open {
+              $write(this).open = false;
+            }
+          },
+          @ { calendar }
+        }
+      },
+      OverlayStyle {
+        auto_close_policy: AutoClosePolicy::TAP_OUTSIDE | AutoClosePolicy::ESC,
+        mask: None,
+      },
+    )
+  }
+}
+
+impl Compose for DatePicker {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let popup = DatePicker::calendar_popup(this.clone_writer());
+      let wnd = BuildCtx::get().window();
+      let mut field = @Flex {
+        class: DATE_PICKER,
+        tab_index: 0_i16,
+        align_items: Align::Center,
+      };
+      let field_track = field.track_id();
+
+      let u = watch!($read(this).open)
+        .distinct_until_changed()
+        .subscribe(move |open| {
+          if !open {
+            popup.close();
+          } else if let Some(id) = field_track.get() {
+            let pos = wnd.map_to_global(Point::zero(), id);
+            let size = wnd.widget_size(id).unwrap_or_default();
+            popup.show_map(anchor_below(Rect::new(pos, size)), wnd.clone());
+          }
+        });
+
+      @(field) {
+        on_tap: move |_| $write(this).open(),
+        on_key_down: move |e| {
+          if matches!(
+            e.key(),
+            VirtualKey::Named(NamedKey::ArrowDown | NamedKey::Enter | NamedKey::Space)
+          ) {
+            $write(this).open();
+          }
+        },
+        on_disposed: move |_| {
+          u.unsubscribe();
+          if $read(this).open {
+            $write(this).close();
+          }
+        },
+        @Expanded {
+          flex: 1.,
+          @Text {
+            text: pipe!($read(this).display_text()),
+            class: distinct_pipe!(if $read(this).selection == DateSelection::None {
+              DATE_PICKER_PLACEHOLDER
+            } else {
+              DATE_PICKER_LABEL
+            }),
+          }
+        }
+        @Icon {
+          class: DATE_PICKER_INDICATOR,
+          @ { svgs::ARROW_DROP_DOWN }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  #[test]
+  fn pick_date_closes_popup() {
+    reset_test_env!();
+    let picker = Stateful::new(DatePicker {
+      selection: DateSelection::Single(Date::new(2025, 3, 14).unwrap()),
+      range: false,
+      min: None,
+      max: None,
+      disabled_days: DayPredicate::default(),
+      placeholder: "".into(),
+      open: false,
+    });
+    let c_picker = picker.clone_writer();
+    let w = fn_widget! {
+      @SizedBox {
+        size: Size::new(200., 24.),
+        @ { c_picker.clone_writer() }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(400., 600.));
+    wnd.draw_frame();
+
+    wnd.process_cursor_move(Point::new(10., 10.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    assert!(picker.read().is_open());
+
+    // The calendar in the popup takes the focus.
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(KeyCode::ArrowDown),
+      VirtualKey::Named(NamedKey::ArrowDown),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(KeyCode::Enter),
+      VirtualKey::Named(NamedKey::Enter),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+    assert_eq!(*picker.read().selection(), DateSelection::Single(Date::new(2025, 3, 21).unwrap()));
+    assert!(!picker.read().is_open());
+    assert_eq!(&*picker.read().display_text(), "2025-03-21");
+  }
+}
@@ -4,12 +4,14 @@ use ribir_core::prelude::*;
 
 use crate::prelude::*;
 
+mod code_editor;
 mod edit_text;
 mod text_glyphs;
 
 mod text_editable;
 mod text_selectable;
 
+pub use code_editor::*;
 pub use edit_text::*;
 pub use text_editable::*;
 pub use text_glyphs::*;
@@ -0,0 +1,852 @@
+use std::{
+  cell::{Ref, RefCell, RefMut},
+  ops::Range,
+  rc::Rc,
+};
+
+use ribir_core::prelude::*;
+
+use super::{edit_text::*, text_glyphs::*, *};
+
+class_names! {
+  #[doc = "Class name for the code editor"]
+  CODE_EDITOR,
+  #[doc = "Class name for the line number gutter of the code editor"]
+  CODE_GUTTER,
+  #[doc = "Class name for the highlight of the line where the caret is"]
+  CODE_CURRENT_LINE,
+  #[doc = "Class name for the highlight of the matched brackets"]
+  CODE_BRACKET_MATCH,
+}
+
+/// A highlighter tokenizes the code line by line and gives the styled spans of
+/// every line to the [`CodeEditor`].
+///
+/// A line is highlighted independently of the others, so the editor only needs
+/// to highlight the lines touched by an edit again.
+pub trait Highlighter {
+  /// Return the styled spans of the `line`, the ranges of the spans are the
+  /// byte offsets relative to the line. The line doesn't include the line
+  /// break.
+  fn highlight_line(&self, line: &str) -> Vec<HighlightSpan>;
+}
+
+/// A span of the code painted with the `brush`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighlightSpan {
+  pub range: Range<usize>,
+  pub brush: Brush,
+}
+
+/// A simple highlighter that colors the keywords, the quoted strings, the
+/// numbers and the line comments.
+#[derive(Clone)]
+pub struct KeywordHighlighter {
+  pub keywords: Vec<CowArc<str>>,
+  /// The prefix of the line comment, e.g. `//` or `#`.
+  pub line_comment: Option<CowArc<str>>,
+  pub keyword: Brush,
+  pub string: Brush,
+  pub number: Brush,
+  pub comment: Brush,
+}
+
+impl KeywordHighlighter {
+  pub fn new<K: Into<CowArc<str>>>(keywords: impl IntoIterator<Item = K>) -> Self {
+    Self {
+      keywords: keywords.into_iter().map(Into::into).collect(),
+      line_comment: None,
+      keyword: Color::from_rgb(0x7C, 0x4D, 0xFF).into(),
+      string: Color::from_rgb(0x0B, 0x80, 0x43).into(),
+      number: Color::from_rgb(0xC1, 0x5C, 0x00).into(),
+      comment: Color::from_rgb(0x80, 0x80, 0x80).into(),
+    }
+  }
+
+  pub fn with_line_comment(mut self, prefix: impl Into<CowArc<str>>) -> Self {
+    self.line_comment = Some(prefix.into());
+    self
+  }
+}
+
+impl Highlighter for KeywordHighlighter {
+  fn highlight_line(&self, line: &str) -> Vec<HighlightSpan> {
+    let mut spans = vec![];
+    let mut span = |range: Range<usize>, brush: &Brush| {
+      spans.push(HighlightSpan { range, brush: brush.clone() })
+    };
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+      if let Some(prefix) = self.line_comment.as_deref() {
+        if line[start..].starts_with(prefix) {
+          span(start..line.len(), &self.comment);
+          break;
+        }
+      }
+
+      if c == '"' || c == '\'' {
+        let mut end = line.len();
+        while let Some((i, ch)) = chars.next() {
+          if ch == '\\' {
+            chars.next();
+          } else if ch == c {
+            end = i + ch.len_utf8();
+            break;
+          }
+        }
+        span(start..end, &self.string);
+      } else if c.is_alphanumeric() || c == '_' {
+        let mut end = start + c.len_utf8();
+        while let Some((i, ch)) = chars.next_if(|(_, ch)| ch.is_alphanumeric() || *ch == '_') {
+          end = i + ch.len_utf8();
+        }
+        let word = &line[start..end];
+        if c.is_ascii_digit() {
+          span(start..end, &self.number);
+        } else if self.keywords.iter().any(|k| &**k == word) {
+          span(start..end, &self.keyword);
+        }
+      }
+    }
+    spans
+  }
+}
+
+/// The text of the [`CodeEditor`].
+///
+/// It keeps the start of every line and caches the highlight spans and the
+/// glyphs of the lines. An edit only drops the caches of the lines it touches,
+/// the others are reused. The lines are highlighted lazily when they are
+/// painted, and laid out one by one, so a keystroke only lays out the lines it
+/// edits again.
+///
+/// When a single line break is inserted, the new line keeps the indent of the
+/// line before it, and is indented more if that line ends with an open
+/// bracket.
+#[derive(Clone)]
+pub struct CodeText {
+  text: CowArc<str>,
+  highlighter: Option<Rc<dyn Highlighter>>,
+  indent_width: usize,
+  /// Count of the edits, it changes whenever the text changes.
+  edits: usize,
+  lines: RefCell<Vec<CodeLine>>,
+  /// The style and the bounds the cached line glyphs are laid out with.
+  layout_key: RefCell<Option<(TextStyle, Size)>>,
+}
+
+#[derive(Clone)]
+struct CodeLine {
+  start: usize,
+  spans: Option<Vec<HighlightSpan>>,
+  glyphs: Option<VisualGlyphs>,
+}
+
+impl CodeLine {
+  fn new(start: usize) -> Self { Self { start, spans: None, glyphs: None } }
+
+  fn invalidate(&mut self) {
+    self.spans = None;
+    self.glyphs = None;
+  }
+}
+
+impl CodeText {
+  pub fn new(text: impl Into<CowArc<str>>) -> Self {
+    Self { text: text.into(), ..Default::default() }
+  }
+
+  pub fn text(&self) -> &CowArc<str> { &self.text }
+
+  /// The count of the edits, it changes whenever the text changes.
+  pub fn edits(&self) -> usize { self.edits }
+
+  fn set_text(&mut self, text: CowArc<str>) {
+    self.text = text;
+    self.edits += 1;
+    self.lines.get_mut().clear();
+  }
+
+  /// How many spaces to indent more after a line ending with an open bracket,
+  /// default is 4.
+  pub fn indent_width(&self) -> usize { self.indent_width }
+
+  pub fn set_indent_width(&mut self, width: usize) { self.indent_width = width; }
+
+  /// Set the highlighter, all the lines will be highlighted again.
+  pub fn set_highlighter(&mut self, highlighter: Option<Rc<dyn Highlighter>>) {
+    self.highlighter = highlighter;
+    self
+      .lines
+      .get_mut()
+      .iter_mut()
+      .for_each(|l| l.spans = None);
+  }
+
+  /// The count of the lines, a text always has one line at least.
+  pub fn line_count(&self) -> usize { self.lines().len() }
+
+  /// Return the line index of the byte offset `at`.
+  pub fn line_of(&self, at: usize) -> usize { line_index(&self.lines(), at) }
+
+  /// Return the byte range of the `line`, not include the line break.
+  pub fn line_range(&self, line: usize) -> Range<usize> {
+    line_range(&self.text, &self.lines(), line)
+  }
+
+  /// Return the highlight spans of the `line`, the line is highlighted if its
+  /// spans are not cached.
+  pub fn line_highlights(&self, line: usize) -> Ref<'_, [HighlightSpan]> {
+    self.highlight(&mut self.lines(), line);
+    Ref::map(self.lines.borrow(), |lines| lines[line].spans.as_deref().unwrap_or_default())
+  }
+
+  /// Return the byte offsets of the bracket beside the `caret` and the one
+  /// matches it. The bracket after the caret is preferred.
+  pub fn matching_bracket(&self, caret: usize) -> Option<(usize, usize)> {
+    const PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+    let text = &*self.text;
+    let after = text
+      .get(caret..)?
+      .chars()
+      .next()
+      .map(|c| (caret, c));
+    let before = text[..caret]
+      .chars()
+      .next_back()
+      .map(|c| (caret - c.len_utf8(), c));
+
+    after
+      .into_iter()
+      .chain(before)
+      .find_map(|(at, c)| {
+        let mut depth = 0;
+        if let Some(&(open, close)) = PAIRS.iter().find(|(open, _)| *open == c) {
+          text[at + 1..]
+            .char_indices()
+            .find(|&(_, ch)| {
+              depth += (ch == open) as i32 - (ch == close) as i32;
+              depth < 0
+            })
+            .map(|(i, _)| (at, at + 1 + i))
+        } else if let Some(&(open, close)) = PAIRS.iter().find(|(_, close)| *close == c) {
+          text[..at]
+            .char_indices()
+            .rev()
+            .find(|&(_, ch)| {
+              depth += (ch == close) as i32 - (ch == open) as i32;
+              depth < 0
+            })
+            .map(|(i, _)| (at, i))
+        } else {
+          None
+        }
+      })
+  }
+
+  /// The indent of a new line broken at `at`.
+  fn indent_at(&self, at: usize) -> String {
+    let start = self.line_range(self.line_of(at)).start;
+    let head = &self.text[start..at];
+    let mut indent: String = head
+      .chars()
+      .take_while(|c| *c == ' ' || *c == '\t')
+      .collect();
+    if head.trim_end().ends_with(['(', '[', '{']) {
+      indent.push_str(&" ".repeat(self.indent_width));
+    }
+    indent
+  }
+
+  /// The heights of the laid out lines, the line not laid out yet is zero.
+  fn line_heights(&self) -> impl Iterator<Item = f32> + '_ {
+    let lines = self.lines.borrow();
+    (0..lines.len()).map(move |i| {
+      lines[i]
+        .glyphs
+        .as_ref()
+        .map_or(0., |g| g.visual_rect().height())
+    })
+  }
+
+  fn lines(&self) -> RefMut<'_, Vec<CodeLine>> {
+    let mut lines = self.lines.borrow_mut();
+    if lines.is_empty() {
+      let starts = std::iter::once(0).chain(self.text.match_indices('\n').map(|(i, _)| i + 1));
+      lines.extend(starts.map(CodeLine::new));
+    }
+    lines
+  }
+
+  fn highlight(&self, lines: &mut [CodeLine], line: usize) {
+    if lines[line].spans.is_none() {
+      let spans = self
+        .highlighter
+        .as_ref()
+        .map_or_else(Vec::new, |h| {
+          let rg = line_range(&self.text, lines, line);
+          h.highlight_line(&self.text[rg])
+        });
+      lines[line].spans = Some(spans);
+    }
+  }
+
+  fn brush_at(&self, lines: &mut [CodeLine], at: usize) -> Option<Brush> {
+    let line = line_index(lines, at);
+    self.highlight(lines, line);
+    let offset = at - lines[line].start;
+    lines[line]
+      .spans
+      .as_ref()?
+      .iter()
+      .find(|s| s.range.contains(&offset))
+      .map(|s| s.brush.clone())
+  }
+}
+
+fn line_index(lines: &[CodeLine], at: usize) -> usize {
+  lines
+    .partition_point(|l| l.start <= at)
+    .saturating_sub(1)
+}
+
+fn line_range(text: &str, lines: &[CodeLine], line: usize) -> Range<usize> {
+  let start = lines[line].start;
+  let end = lines
+    .get(line + 1)
+    .map_or(text.len(), |l| l.start - 1);
+  let end = if text[start..end].ends_with('\r') { end - 1 } else { end };
+  start..end
+}
+
+impl Default for CodeText {
+  fn default() -> Self {
+    Self {
+      text: <_>::default(),
+      highlighter: None,
+      indent_width: 4,
+      edits: 0,
+      lines: <_>::default(),
+      layout_key: <_>::default(),
+    }
+  }
+}
+
+impl PartialEq for CodeText {
+  fn eq(&self, other: &Self) -> bool { self.text == other.text }
+}
+
+impl Eq for CodeText {}
+
+impl BaseText for CodeText {
+  fn len(&self) -> usize { self.text.len() }
+  fn substr(&self, rg: Range<usize>) -> Substr { self.text.substr(rg) }
+  fn measure_bytes(&self, byte_from: usize, char_len: isize) -> usize {
+    self.text.measure_bytes(byte_from, char_len)
+  }
+  fn select_token(&self, byte_from: usize) -> Range<usize> { self.text.select_token(byte_from) }
+}
+
+impl EditText for CodeText {
+  fn insert_str(&mut self, at: usize, v: &str) -> usize {
+    let line_break;
+    let v = if matches!(v, "\n" | "\r\n") {
+      line_break = format!("{v}{}", self.indent_at(at));
+      &line_break
+    } else {
+      v
+    };
+    let mut lines = self.lines();
+    let line = line_index(&lines, at);
+    lines[line].invalidate();
+    lines[line + 1..]
+      .iter_mut()
+      .for_each(|l| l.start += v.len());
+    let new_lines = v
+      .match_indices('\n')
+      .map(|(i, _)| CodeLine::new(at + i + 1));
+    lines.splice(line + 1..line + 1, new_lines);
+    drop(lines);
+
+    self.edits += 1;
+    self.text.insert_str(at, v)
+  }
+
+  fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize> {
+    let rg = self.text.del_rg_str(rg);
+    self.edits += 1;
+    let mut lines = self.lines.borrow_mut();
+    if !rg.is_empty() && !lines.is_empty() {
+      let first = line_index(&lines, rg.start);
+      let last = line_index(&lines, rg.end);
+      lines.drain(first + 1..=last);
+      lines[first].invalidate();
+      lines[first + 1..]
+        .iter_mut()
+        .for_each(|l| l.start -= rg.len());
+    }
+    rg
+  }
+}
+
+impl VisualText for CodeText {
+  fn layout_glyphs(&self, clamp: BoxClamp, ctx: &LayoutCtx) -> VisualGlyphs {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let mut lines = self.lines();
+    let mut key = self.layout_key.borrow_mut();
+    if key
+      .as_ref()
+      .is_none_or(|(s, bounds)| *s != *style || *bounds != clamp.max)
+    {
+      lines.iter_mut().for_each(|l| l.glyphs = None);
+      *key = Some((style.clone(), clamp.max));
+    }
+
+    // Only the lines without the cached glyphs are laid out.
+    let paras: Vec<_> = (0..lines.len())
+      .map(|line| {
+        let rg = line_range(&self.text, &lines, line);
+        let line = &mut lines[line];
+        let glyphs = line.glyphs.get_or_insert_with(|| {
+          text_glyph(self.text.substr(rg), &style, TextAlign::Start, clamp.max)
+        });
+        (line.start, glyphs.clone())
+      })
+      .collect();
+    VisualGlyphs::stack_paras(&paras, self.text.len())
+  }
+
+  fn paint(&self, painter: &mut Painter, style: PaintingStyle, glyphs: &VisualGlyphs, rect: Rect) {
+    if self.highlighter.is_none() {
+      return paint_text(painter, glyphs, style, rect);
+    }
+    let Some(paint_rect) = painter.intersection_paint_bounds(&rect) else { return };
+    let Some(visible) = glyphs.glyphs_in_bounds(&paint_rect) else { return };
+
+    let mut painter = painter.save_guard();
+    let default = if let PaintingStyle::Stroke(options) = style {
+      painter
+        .set_style(PathStyle::Stroke)
+        .set_strokes(options);
+      painter.stroke_brush().clone()
+    } else {
+      painter.set_style(PathStyle::Fill);
+      painter.fill_brush().clone()
+    };
+    let origin = glyphs.visual_rect().origin;
+    painter.translate(origin.x, origin.y);
+
+    let font_db = AppCtx::font_db().clone();
+    let font_db = font_db.borrow();
+    let mut lines = self.lines();
+    // Only the visible lines are highlighted.
+    for g in visible {
+      let brush = self
+        .brush_at(&mut lines, g.cluster as usize)
+        .unwrap_or_else(|| default.clone());
+      painter
+        .set_fill_brush(brush.clone())
+        .set_stroke_brush(brush)
+        .draw_glyph(&g, glyphs.font_size(), &font_db);
+    }
+  }
+}
+
+/// A multi-line editor for the code, built on the [`BasicEditor`].
+///
+/// It highlights the code by a pluggable [`Highlighter`], shows the line
+/// numbers in a gutter, highlights the current line and the matched brackets,
+/// and keeps the indent of the previous line when a new line is inserted.
+///
+/// Only the lines touched by an edit are highlighted and laid out again, the
+/// glyphs of the other lines are reused.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let editor = @CodeEditor { soft_wrap: false };
+///   $write(editor).set_highlighter(
+///     KeywordHighlighter::new(["fn", "let"]).with_line_comment("//"),
+///   );
+///   editor
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct CodeEditor {
+  /// If true, the long lines wrap to the width of the editor, otherwise the
+  /// editor scrolls horizontally.
+  #[declare(default)]
+  pub soft_wrap: bool,
+  /// If true, show the line numbers in the gutter.
+  #[declare(default = true)]
+  pub line_numbers: bool,
+  #[declare(skip)]
+  basic: BasicEditor<CodeText>,
+  #[declare(skip)]
+  line_tops: RefCell<LineTops>,
+}
+
+/// The tops and the heights of the code lines in the laid out text, they are
+/// reused until the text or its layout changes.
+#[derive(Default)]
+struct LineTops {
+  edits: usize,
+  visual_rect: Rect,
+  font_size: f32,
+  tops: Vec<f32>,
+  heights: Rc<[f32]>,
+}
+
+/// The positions of the decorations of the code editor, relative to the text.
+#[derive(Clone, Default, PartialEq)]
+struct CodeGeometry {
+  current_line: Rect,
+  brackets: Vec<Rect>,
+}
+
+impl CodeEditor {
+  /// set the text and the caret selection will be reset to the start.
+  pub fn set_text(&mut self, text: &str) {
+    self
+      .basic
+      .text_mut()
+      .set_text(text.to_string().into());
+    let selection = &mut self.basic.selection;
+    selection.from = CaretPosition::default();
+    selection.to = CaretPosition::default();
+  }
+
+  pub fn text(&self) -> &CowArc<str> { self.basic.text().text() }
+
+  /// Set how many spaces to indent more after a line ending with an open
+  /// bracket.
+  pub fn set_indent_width(&mut self, width: usize) {
+    self.basic.text_mut().set_indent_width(width);
+  }
+
+  pub fn set_highlighter(&mut self, highlighter: impl Highlighter + 'static) {
+    self
+      .basic
+      .text_mut()
+      .set_highlighter(Some(Rc::new(highlighter)));
+  }
+
+  /// set the caret selection, and the caret position will be set to the `to`
+  /// cluster
+  pub fn select(&mut self, from: usize, to: usize) {
+    let selection = &mut self.basic.selection;
+    selection.from = CaretPosition { cluster: from, position: None };
+    selection.to = CaretPosition { cluster: to, position: None };
+  }
+
+  /// return the selection range of the text
+  pub fn selection(&self) -> Range<usize> { self.basic.cluster_rg() }
+
+  /// Return the index of the line where the caret is.
+  pub fn current_line(&self) -> usize {
+    self
+      .basic
+      .text()
+      .line_of(self.basic.selection.to.cluster)
+  }
+
+  /// Return the byte offsets of the bracket beside the caret and the one
+  /// matches it.
+  pub fn matching_bracket(&self) -> Option<(usize, usize)> {
+    self
+      .basic
+      .text()
+      .matching_bracket(self.basic.selection.to.cluster)
+  }
+
+  /// Return the heights of the code lines and the geometry of the decorations.
+  fn geometry(&self, width: f32) -> (Rc<[f32]>, CodeGeometry) {
+    let code = self.basic.text();
+    let Some(glyphs) = self.basic.glyphs() else { return <_>::default() };
+
+    let mut cache = self.line_tops.borrow_mut();
+    let visual_rect = glyphs.visual_rect();
+    let font_size = glyphs.font_size();
+    if cache.tops.is_empty()
+      || cache.visual_rect != visual_rect
+      || cache.font_size != font_size
+      || cache.edits != code.edits()
+    {
+      let heights: Rc<[f32]> = code.line_heights().collect();
+      cache.tops = heights
+        .iter()
+        .scan(visual_rect.min_y(), |top, h| {
+          let line_top = *top;
+          *top += h;
+          Some(line_top)
+        })
+        .collect();
+      if *cache.heights != *heights {
+        cache.heights = heights;
+      }
+      cache.edits = code.edits();
+      cache.visual_rect = visual_rect;
+      cache.font_size = font_size;
+    }
+    let tops = &cache.tops;
+    let line_heights = &cache.heights;
+
+    let line = self.current_line();
+    let current_line = Rect::new(Point::new(0., tops[line]), Size::new(width, line_heights[line]));
+    let brackets = self
+      .matching_bracket()
+      .map(|(a, b)| {
+        [a, b]
+          .into_iter()
+          .filter_map(|at| {
+            glyphs
+              .select_range(&(at..at + 1))
+              .first()
+              .copied()
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    (line_heights.clone(), CodeGeometry { current_line, brackets })
+  }
+}
+
+impl Compose for CodeEditor {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let geometry = Stateful::new(CodeGeometry::default());
+      let line_heights: Stateful<Rc<[f32]>> = Stateful::new(Rc::new([]));
+      let mut basic = FatObj::new(part_writer!(&mut this.basic));
+      let gutter = pipe!($read(this).line_numbers).map(move |show| {
+        show.then(|| fn_widget! {
+          // The line numbers are rebuilt only when the count of the lines
+          // changes, otherwise only their heights are updated.
+          let numbers = distinct_pipe!($read(line_heights).len()).map(move |count| {
+            (0..count).map(move |i| @Text {
+              text: (i + 1).to_string(),
+              clamp: distinct_pipe! {
+                let h = $read(line_heights).get(i).copied().unwrap_or_default();
+                BoxClamp::fixed_height(h)
+              },
+            }).collect::<Vec<_>>()
+          });
+          @Column {
+            class: CODE_GUTTER,
+            align_items: Align::End,
+            @ { numbers }
+          }
+        })
+      });
+
+      @FocusScope {
+        @TextClamp {
+          rows: Some(10.),
+          cols: Some(40.),
+          class: CODE_EDITOR,
+          @Scrollbar {
+            scrollable: pipe!(if $read(this).soft_wrap { Scrollable::Y } else { Scrollable::Both }),
+            text_overflow: pipe!(if $read(this).soft_wrap {
+              TextOverflow::AutoWrap
+            } else {
+              TextOverflow::Overflow
+            }),
+            @Row {
+              @ { gutter }
+              @Expanded {
+                @Stack {
+                  @NoAffectedParentSize {
+                    @Stack {
+                      @pipe! {
+                        let geometry = $read(geometry);
+                        let line = geometry.current_line;
+                        let brackets = geometry.brackets.clone().into_iter().map(|rc| {
+                          @Container {
+                            class: CODE_BRACKET_MATCH,
+                            anchor: Anchor::from_point(rc.origin),
+                            size: rc.size,
+                          }
+                        });
+                        std::iter::once(@Container {
+                          class: CODE_CURRENT_LINE,
+                          anchor: Anchor::from_point(line.origin),
+                          size: line.size,
+                        }.into_widget())
+                        .chain(brackets.map(IntoWidget::into_widget))
+                        .collect::<Vec<_>>()
+                      }
+                    }
+                  }
+                  @(basic) {
+                    on_performed_layout: move |e| {
+                      let width = e.box_size().map_or(0., |s| s.width);
+                      let (heights, new) = $read(this).geometry(width);
+                      if !Rc::ptr_eq(&$read(line_heights), &heights) {
+                        *$write(line_heights) = heights;
+                      }
+                      if *$read(geometry) != new {
+                        *$write(geometry) = new;
+                      }
+                    },
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::Cell;
+
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  struct CountHighlighter(Rc<Cell<usize>>);
+
+  impl Highlighter for CountHighlighter {
+    fn highlight_line(&self, line: &str) -> Vec<HighlightSpan> {
+      self.0.set(self.0.get() + 1);
+      KeywordHighlighter::new(["let"]).highlight_line(line)
+    }
+  }
+
+  #[test]
+  fn highlight_edited_lines() {
+    let count = Rc::new(Cell::new(0));
+    let mut code = CodeText::new("let a = 1;\nlet b = \"x\";\nc");
+    code.set_highlighter(Some(Rc::new(CountHighlighter(count.clone()))));
+    assert_eq!(code.line_count(), 3);
+    let spans: Vec<_> = (0..3)
+      .map(|l| {
+        code
+          .line_highlights(l)
+          .iter()
+          .map(|s| s.range.clone())
+          .collect::<Vec<_>>()
+      })
+      .collect();
+    assert_eq!(spans, [vec![0..3, 8..9], vec![0..3, 8..11], vec![]]);
+    assert_eq!(count.get(), 3);
+
+    // Only the edited line is highlighted again.
+    code.insert_str(15, "b");
+    let _ = code.line_highlights(0);
+    let _ = code.line_highlights(1);
+    let _ = code.line_highlights(2);
+    assert_eq!(count.get(), 4);
+    assert_eq!(code.line_range(1), 11..24);
+
+    code.insert_str(0, "x\n");
+    assert_eq!(code.line_count(), 4);
+    assert_eq!(code.line_range(1), 2..12);
+    code.del_rg_str(0..2);
+    assert_eq!(code.line_count(), 3);
+    assert_eq!(code.line_range(0), 0..10);
+    assert_eq!(&code.text()[code.line_range(2)], "c");
+  }
+
+  #[test]
+  fn layout_edited_lines() {
+    reset_test_env!();
+    let editor = Stateful::new(CodeEditor {
+      soft_wrap: false,
+      line_numbers: true,
+      basic: <_>::default(),
+      line_tops: <_>::default(),
+    });
+    editor.write().set_text("let a = 1;\nlet b = 2;\nc");
+    let c_editor = editor.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut editor = FatObj::new(c_editor.clone_writer());
+        @(editor) { auto_focus: true }
+      },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+    let laid_out = |editor: &Stateful<CodeEditor>| {
+      let editor = editor.read();
+      let lines = editor.basic.text().lines();
+      lines
+        .iter()
+        .map(|l| l.glyphs.is_some())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(laid_out(&editor), [true, true, true]);
+
+    editor
+      .write()
+      .basic
+      .text_mut()
+      .insert_str(15, "x");
+    assert_eq!(laid_out(&editor), [true, false, true]);
+    wnd.draw_frame();
+    assert_eq!(laid_out(&editor), [true, true, true]);
+
+    let editor = editor.read();
+    let glyphs = editor.basic.glyphs().unwrap();
+    assert_eq!(glyphs.line_count(), 3);
+    assert_eq!(glyphs.position_by_cluster(12), (1, 1));
+    assert_eq!(glyphs.position_by_cluster(23), (2, 0));
+    assert_eq!(*editor.line_tops.borrow().heights, [glyphs.line_height(0); 3]);
+  }
+
+  #[test]
+  fn auto_indent_and_brackets() {
+    reset_test_env!();
+    let editor = Stateful::new(CodeEditor {
+      soft_wrap: false,
+      line_numbers: true,
+      basic: <_>::default(),
+      line_tops: <_>::default(),
+    });
+    let c_editor = editor.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut editor = FatObj::new(c_editor.clone_writer());
+        @(editor) { auto_focus: true }
+      },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+
+    wnd.process_receive_chars("fn main() {".into());
+    wnd.draw_frame();
+    wnd.process_receive_chars("\n".into());
+    wnd.draw_frame();
+    assert_eq!(&**editor.read().text(), "fn main() {\n    ");
+    assert_eq!(editor.read().current_line(), 1);
+
+    wnd.process_receive_chars("}".into());
+    wnd.draw_frame();
+    assert_eq!(editor.read().matching_bracket(), Some((16, 10)));
+    editor.write().select(7, 7);
+    assert_eq!(editor.read().matching_bracket(), Some((7, 8)));
+    assert_eq!(editor.read().current_line(), 0);
+
+    editor
+      .write()
+      .set_highlighter(KeywordHighlighter::new(["fn"]));
+    wnd.draw_frame();
+    assert_eq!(
+      editor
+        .read()
+        .basic
+        .text()
+        .line_highlights(0)
+        .len(),
+      1
+    );
+  }
+}
@@ -8,3 +8,7 @@ mod fractionally;
 pub use fractionally::*;
 mod linear;
 pub use linear::*;
+mod grid;
+pub use grid::*;
+mod intrinsic;
+pub use intrinsic::*;
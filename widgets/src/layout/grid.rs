@@ -0,0 +1,819 @@
+use std::ops::Range;
+
+use ribir_core::prelude::{log::warn, *};
+
+/// A layout container that arranges its children in rows and columns, like
+/// the CSS grid layout.
+///
+/// The size of the rows and columns are defined by the tracks of [`rows`] and
+/// [`columns`], a track can be:
+///
+/// - [`GridTrack::Fixed`]: a fixed size in pixels.
+/// - [`GridTrack::Fr`]: a fraction of the remaining space, like the `fr` unit
+///   of CSS.
+/// - [`GridTrack::Auto`]: the size of its largest content.
+/// - [`GridTrack::MinMax`]: a size between a minimum and a maximum.
+///
+/// A child is placed by a [`GridItem`], by the row and the column index or
+/// by the name of an area defined in [`areas`]. It may span multiple rows and
+/// columns. The children without a position are placed one by one into the
+/// first free cells, row by row. The rows beyond the [`rows`] are created by
+/// the [`auto_rows`] track, and the columns beyond the [`columns`] by the
+/// [`auto_columns`] track.
+///
+/// The children are stretched to fill their cells by default, use
+/// [`justify_items`] and [`align_items`] to align all of them, or the
+/// `justify_self` and `align_self` of [`GridItem`] to align one of them.
+///
+/// [`rows`]: Grid::rows
+/// [`columns`]: Grid::columns
+/// [`areas`]: Grid::areas
+/// [`auto_rows`]: Grid::auto_rows
+/// [`auto_columns`]: Grid::auto_columns
+/// [`justify_items`]: Grid::justify_items
+/// [`align_items`]: Grid::align_items
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _dashboard = fn_widget! {
+///   @Grid {
+///     columns: vec![GridTrack::Fixed(200.), GridTrack::Fr(1.), GridTrack::Fr(1.)],
+///     rows: vec![GridTrack::Auto, GridTrack::Fr(1.)],
+///     areas: ["header header header", "nav chart table"],
+///     column_gap: 8.,
+///     row_gap: 8.,
+///     @GridItem { area: "header", @Text { text: "Dashboard" } }
+///     @GridItem { area: "nav", @Text { text: "Navigation" } }
+///     @GridItem {
+///       area: "chart",
+///       justify_self: HAlign::Center,
+///       @Text { text: "Chart" }
+///     }
+///     @GridItem { area: "table", @Text { text: "Table" } }
+///   }
+/// };
+/// ```
+#[derive(MultiChild, Declare, Clone, PartialEq)]
+pub struct Grid {
+  /// The tracks of the columns.
+  #[declare(default)]
+  pub columns: Vec<GridTrack>,
+  /// The tracks of the rows.
+  #[declare(default)]
+  pub rows: Vec<GridTrack>,
+  /// The track of the columns that are not defined by `columns`.
+  #[declare(default)]
+  pub auto_columns: GridTrack,
+  /// The track of the rows that are not defined by `rows`.
+  #[declare(default)]
+  pub auto_rows: GridTrack,
+  /// The named areas of the grid.
+  #[declare(default)]
+  pub areas: GridAreas,
+  /// The gap between columns.
+  #[declare(default)]
+  pub column_gap: f32,
+  /// The gap between rows.
+  #[declare(default)]
+  pub row_gap: f32,
+  /// How the children are placed in their cells along the x-axis.
+  #[declare(default = HAlign::Stretch)]
+  pub justify_items: HAlign,
+  /// How the children are placed in their cells along the y-axis.
+  #[declare(default = VAlign::Stretch)]
+  pub align_items: VAlign,
+}
+
+/// The size of a row or a column of the [`Grid`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum GridTrack {
+  /// A fixed size in pixels.
+  Fixed(f32),
+  /// A flexible track that shares the remaining space with the other flexible
+  /// tracks by its factor. It's never smaller than its content.
+  Fr(f32),
+  /// A track as large as its largest content.
+  #[default]
+  Auto,
+  /// A track at least as large as the minimum size in pixels, and grows up to
+  /// the maximum.
+  MinMax(f32, TrackMax),
+}
+
+/// The maximum size of a [`GridTrack::MinMax`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrackMax {
+  /// Grows up to a fixed size in pixels if there is enough space.
+  Fixed(f32),
+  /// Shares the remaining space by the factor.
+  Fr(f32),
+  /// Grows to fit its content.
+  Auto,
+}
+
+/// The named areas of a [`Grid`], like the `grid-template-areas` of CSS.
+///
+/// Every string describes a row, the names of the cells in the row are
+/// separated by whitespace, and a `.` marks a cell without a name. The cells
+/// with the same name must form a rectangle.
+///
+/// ```
+/// use ribir_widgets::prelude::GridAreas;
+///
+/// let areas = GridAreas::from(["header header", "side main", ". footer"]);
+/// assert_eq!(areas.area("header"), Some((0..1, 0..2)));
+/// assert_eq!(areas.area("footer"), Some((2..3, 1..2)));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GridAreas(Vec<Vec<String>>);
+
+/// A widget that tells its [`Grid`] parent where the child should be placed.
+///
+/// Set `area` to place the child in a named area of the grid, or `row` and
+/// `column` to place it at a cell. The child is placed automatically into
+/// the first free cells if neither is set, if only one of `row` and `column` is
+/// set, the other one is picked automatically.
+#[simple_declare]
+#[derive(Clone, PartialEq)]
+// Like `Expanded`, `GridItem` does not support `FatObj`, so it's always
+// visible to its parent.
+pub struct GridItem {
+  /// The index of the first row of the child.
+  #[declare(custom, default)]
+  pub row: Option<usize>,
+  /// The index of the first column of the child.
+  #[declare(custom, default)]
+  pub column: Option<usize>,
+  /// How many rows the child spans.
+  #[declare(strict, default = 1usize)]
+  pub row_span: usize,
+  /// How many columns the child spans.
+  #[declare(strict, default = 1usize)]
+  pub column_span: usize,
+  /// The name of the area the child is placed in, it takes precedence over
+  /// `row` and `column`.
+  #[declare(custom, default)]
+  pub area: Option<CowArc<str>>,
+  /// Overwrite the `justify_items` of the grid for this child.
+  #[declare(default)]
+  pub justify_self: Option<HAlign>,
+  /// Overwrite the `align_items` of the grid for this child.
+  #[declare(default)]
+  pub align_self: Option<VAlign>,
+}
+
+impl Default for GridItem {
+  fn default() -> Self {
+    Self {
+      row: None,
+      column: None,
+      row_span: 1,
+      column_span: 1,
+      area: None,
+      justify_self: None,
+      align_self: None,
+    }
+  }
+}
+
+impl GridItemDeclarer {
+  /// Place the child in the row.
+  pub fn with_row(&mut self, row: usize) -> &mut Self {
+    self.row = Some(Some(row));
+    self
+  }
+
+  /// Place the child in the column.
+  pub fn with_column(&mut self, column: usize) -> &mut Self {
+    self.column = Some(Some(column));
+    self
+  }
+
+  /// Place the child in the named area.
+  pub fn with_area(&mut self, area: impl Into<CowArc<str>>) -> &mut Self {
+    self.area = Some(Some(area.into()));
+    self
+  }
+}
+
+impl<'c> ComposeChild<'c> for GridItem {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, mut child: Self::Child) -> Widget<'c> {
+    let data: Box<dyn Query> = match this.try_into_value() {
+      Ok(this) => Box::new(Queryable(this)),
+      Err(this) => {
+        child = child.dirty_on(this.raw_modifies(), DirtyPhase::Layout);
+        Box::new(this)
+      }
+    };
+
+    child.attach_data(data)
+  }
+}
+
+impl GridAreas {
+  /// Return the rows and the columns the area covers.
+  pub fn area(&self, name: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let mut bounds: Option<(Range<usize>, Range<usize>)> = None;
+    for (r, row) in self.0.iter().enumerate() {
+      for (c, cell) in row.iter().enumerate() {
+        if cell == name {
+          let (rows, cols) = bounds.get_or_insert((r..r + 1, c..c + 1));
+          rows.start = rows.start.min(r);
+          rows.end = rows.end.max(r + 1);
+          cols.start = cols.start.min(c);
+          cols.end = cols.end.max(c + 1);
+        }
+      }
+    }
+    bounds
+  }
+
+  /// The number of rows of the areas.
+  pub fn row_count(&self) -> usize { self.0.len() }
+
+  /// The number of columns of the areas.
+  pub fn column_count(&self) -> usize { self.0.iter().map(Vec::len).max().unwrap_or(0) }
+}
+
+impl<const N: usize> From<[&str; N]> for GridAreas {
+  fn from(rows: [&str; N]) -> Self { rows.into_iter().collect() }
+}
+
+impl From<Vec<&str>> for GridAreas {
+  fn from(rows: Vec<&str>) -> Self { rows.into_iter().collect() }
+}
+
+impl<'a> FromIterator<&'a str> for GridAreas {
+  fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+    let rows = iter
+      .into_iter()
+      .map(|row| {
+        row
+          .split_whitespace()
+          .map(|name| if name == "." { String::new() } else { name.to_string() })
+          .collect()
+      })
+      .collect();
+    GridAreas(rows)
+  }
+}
+
+/// The cells a child occupies.
+#[derive(Debug, Clone, PartialEq)]
+struct GridCell {
+  rows: Range<usize>,
+  columns: Range<usize>,
+}
+
+impl Render for Grid {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let (ctx, children) = ctx.split_children();
+    let children: Vec<_> = children
+      .map(|c| {
+        let item = ctx
+          .query_of_widget::<GridItem>(c)
+          .map(|item| item.clone())
+          .unwrap_or_default();
+        (c, item)
+      })
+      .collect();
+
+    let cells = self.place_items(children.iter().map(|(_, item)| item));
+    let row_cnt = cells
+      .iter()
+      .map(|cell| cell.rows.end)
+      .chain([self.rows.len(), self.areas.row_count()])
+      .max()
+      .unwrap_or(0);
+    let col_cnt = cells
+      .iter()
+      .map(|cell| cell.columns.end)
+      .chain([self.columns.len(), self.areas.column_count()])
+      .max()
+      .unwrap_or(0);
+
+    let mut columns: Vec<_> = (0..col_cnt)
+      .map(|i| {
+        TrackSize::new(
+          self
+            .columns
+            .get(i)
+            .copied()
+            .unwrap_or(self.auto_columns),
+        )
+      })
+      .collect();
+    let mut rows: Vec<_> = (0..row_cnt)
+      .map(|i| {
+        TrackSize::new(
+          self
+            .rows
+            .get(i)
+            .copied()
+            .unwrap_or(self.auto_rows),
+        )
+      })
+      .collect();
+
+    // Size the columns by the width of the children that care about their
+    // content.
+    let contributions = children
+      .iter()
+      .zip(cells.iter())
+      .filter(|(_, cell)| {
+        columns[cell.columns.clone()]
+          .iter()
+          .any(|t| t.content)
+      })
+      .map(|((c, _), cell)| {
+        let size = ctx.perform_child_layout(*c, BoxClamp::default());
+        (cell.columns.clone(), size.width)
+      })
+      .collect();
+    size_tracks(&mut columns, self.column_gap, clamp.max.width, contributions);
+    let col_offsets = track_offsets(&columns, self.column_gap);
+
+    // Size the rows by the height of the children in their columns.
+    let contributions = children
+      .iter()
+      .zip(cells.iter())
+      .filter(|(_, cell)| rows[cell.rows.clone()].iter().any(|t| t.content))
+      .map(|((c, item), cell)| {
+        let width = span_size(&columns, &col_offsets, &cell.columns);
+        let h_align = item.justify_self.unwrap_or(self.justify_items);
+        let clamp = item_clamp(h_align, VAlign::Top, Size::new(width, f32::INFINITY));
+        let size = ctx.perform_child_layout(*c, clamp);
+        (cell.rows.clone(), size.height)
+      })
+      .collect();
+    size_tracks(&mut rows, self.row_gap, clamp.max.height, contributions);
+    let row_offsets = track_offsets(&rows, self.row_gap);
+
+    for ((c, item), cell) in children.iter().zip(cells.iter()) {
+      let h_align = item.justify_self.unwrap_or(self.justify_items);
+      let v_align = item.align_self.unwrap_or(self.align_items);
+      let width = span_size(&columns, &col_offsets, &cell.columns);
+      let height = span_size(&rows, &row_offsets, &cell.rows);
+      let clamp = item_clamp(h_align, v_align, Size::new(width, height));
+      let size = ctx.perform_child_layout(*c, clamp);
+
+      let x = match h_align {
+        HAlign::Left | HAlign::Stretch => 0.,
+        HAlign::Center => (width - size.width) / 2.,
+        HAlign::Right => width - size.width,
+      };
+      let y = match v_align {
+        VAlign::Top | VAlign::Stretch => 0.,
+        VAlign::Center => (height - size.height) / 2.,
+        VAlign::Bottom => height - size.height,
+      };
+      let pos = Point::new(col_offsets[cell.columns.start] + x, row_offsets[cell.rows.start] + y);
+      ctx.update_position(*c, pos);
+    }
+
+    let width = total_size(&columns, self.column_gap);
+    let height = total_size(&rows, self.row_gap);
+    clamp.clamp(Size::new(width, height))
+  }
+
+  #[inline]
+  fn size_affected_by_child(&self) -> bool { true }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+impl Grid {
+  /// Resolve the cells of the items. The items in a named area or with both
+  /// the row and the column are placed first, then the items with only the
+  /// row, and at last, the others are placed into the first free cells in
+  /// order.
+  fn place_items<'a>(&self, items: impl Iterator<Item = &'a GridItem>) -> Vec<GridCell> {
+    let items: Vec<_> = items.collect();
+    let mut cells: Vec<Option<GridCell>> = items
+      .iter()
+      .map(|item| {
+        if let Some(name) = item.area.as_ref() {
+          if let Some((rows, columns)) = self.areas.area(name) {
+            return Some(GridCell { rows, columns });
+          }
+          warn!("The grid area `{name}` is not defined, the child is placed automatically.");
+        }
+        let (row, column) = (item.row?, item.column?);
+        Some(GridCell {
+          rows: row..row + item.row_span.max(1),
+          columns: column..column + item.column_span.max(1),
+        })
+      })
+      .collect();
+
+    let col_cnt = cells
+      .iter()
+      .flatten()
+      .map(|cell| cell.columns.end)
+      .chain(
+        items
+          .iter()
+          .filter_map(|item| item.column.map(|c| c + item.column_span.max(1))),
+      )
+      .chain([self.columns.len(), self.areas.column_count(), 1])
+      .max()
+      .unwrap_or(1);
+
+    let mut occupied = Occupied::new(col_cnt);
+    cells
+      .iter()
+      .flatten()
+      .for_each(|cell| occupied.mark(cell));
+
+    // The items locked in a row.
+    for (item, cell) in items.iter().zip(cells.iter_mut()) {
+      if let (None, Some(row)) = (&cell, item.row) {
+        let col_span = item.column_span.clamp(1, col_cnt);
+        let rows = row..row + item.row_span.max(1);
+        let column = (0..=col_cnt - col_span)
+          .find(|c| occupied.is_free(&rows, &(*c..c + col_span)))
+          .unwrap_or(0);
+        let new_cell = GridCell { rows, columns: column..column + col_span };
+        occupied.mark(&new_cell);
+        *cell = Some(new_cell);
+      }
+    }
+
+    // The others are placed after the last automatically placed item.
+    let (mut row, mut column) = (0, 0);
+    for (item, cell) in items.iter().zip(cells.iter_mut()) {
+      if cell.is_some() {
+        continue;
+      }
+      let row_span = item.row_span.max(1);
+      let col_span = item.column_span.clamp(1, col_cnt);
+      let new_cell = if let Some(c) = item.column {
+        let columns = c..c + col_span;
+        let r = (0..)
+          .find(|r| occupied.is_free(&(*r..r + row_span), &columns))
+          .unwrap();
+        GridCell { rows: r..r + row_span, columns }
+      } else {
+        loop {
+          if column + col_span > col_cnt {
+            column = 0;
+            row += 1;
+          }
+          let cell = GridCell { rows: row..row + row_span, columns: column..column + col_span };
+          if occupied.is_free(&cell.rows, &cell.columns) {
+            column += col_span;
+            break cell;
+          }
+          column += 1;
+        }
+      };
+      occupied.mark(&new_cell);
+      *cell = Some(new_cell);
+    }
+
+    cells.into_iter().flatten().collect()
+  }
+}
+
+/// The cells that are already occupied by the children.
+struct Occupied {
+  columns: usize,
+  cells: Vec<bool>,
+}
+
+impl Occupied {
+  fn new(columns: usize) -> Self { Self { columns, cells: vec![] } }
+
+  fn is_free(&self, rows: &Range<usize>, columns: &Range<usize>) -> bool {
+    rows.clone().all(|r| {
+      columns.clone().all(|c| {
+        c >= self.columns
+          || !self
+            .cells
+            .get(r * self.columns + c)
+            .copied()
+            .unwrap_or(false)
+      })
+    })
+  }
+
+  fn mark(&mut self, cell: &GridCell) {
+    let len = cell.rows.end * self.columns;
+    if self.cells.len() < len {
+      self.cells.resize(len, false);
+    }
+    for r in cell.rows.clone() {
+      for c in cell.columns.clone().filter(|c| *c < self.columns) {
+        self.cells[r * self.columns + c] = true;
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrackSize {
+  size: f32,
+  max: TrackMax,
+  /// Whether the track grows to fit its content.
+  content: bool,
+}
+
+impl TrackSize {
+  fn new(track: GridTrack) -> Self {
+    match track {
+      GridTrack::Fixed(size) => Self { size, max: TrackMax::Fixed(size), content: false },
+      GridTrack::Fr(fr) => Self { size: 0., max: TrackMax::Fr(fr), content: true },
+      GridTrack::Auto => Self { size: 0., max: TrackMax::Auto, content: true },
+      GridTrack::MinMax(min, max) => Self { size: min, max, content: max == TrackMax::Auto },
+    }
+  }
+
+  fn fr(&self) -> Option<f32> {
+    match self.max {
+      TrackMax::Fr(fr) if fr > 0. && fr.is_finite() => Some(fr),
+      _ => None,
+    }
+  }
+}
+
+/// Size the tracks of one axis:
+///
+/// 1. Grow the content tracks to fit the children in them, the children
+///    spanning fewer tracks go first.
+/// 2. Grow the tracks with a fixed maximum to their maximum by the free space.
+/// 3. Share the remaining space to the flexible tracks by their factors, but
+///    keep them not smaller than their content.
+fn size_tracks(
+  tracks: &mut [TrackSize], gap: f32, available: f32, mut contributions: Vec<(Range<usize>, f32)>,
+) {
+  contributions.sort_by_key(|(range, _)| range.len());
+  for (range, size) in contributions {
+    let gaps = gap * (range.len() - 1) as f32;
+    let spanned = &mut tracks[range];
+    let cnt = spanned.iter().filter(|t| t.content).count();
+    let extra = size - gaps - spanned.iter().map(|t| t.size).sum::<f32>();
+    if cnt > 0 && extra > 0. {
+      let per = extra / cnt as f32;
+      spanned
+        .iter_mut()
+        .filter(|t| t.content)
+        .for_each(|t| t.size += per);
+    }
+  }
+
+  let gaps = gap * tracks.len().saturating_sub(1) as f32;
+  let mut free = available - gaps - tracks.iter().map(|t| t.size).sum::<f32>();
+  loop {
+    let growable = tracks
+      .iter()
+      .filter(|t| matches!(t.max, TrackMax::Fixed(max) if max > t.size))
+      .count();
+    if growable == 0 || free <= 0. {
+      break;
+    }
+    let per = free / growable as f32;
+    for t in tracks.iter_mut() {
+      if let TrackMax::Fixed(max) = t.max {
+        if max > t.size {
+          let grow = per.min(max - t.size);
+          t.size = if grow < per { max } else { t.size + grow };
+          free -= grow;
+        }
+      }
+    }
+  }
+
+  if tracks.iter().all(|t| t.fr().is_none()) {
+    return;
+  }
+  let unit = if available.is_finite() {
+    // The tracks whose share is smaller than their content are treated as
+    // inflexible, until the share of every flexible track fits its content.
+    let mut inflexible: Vec<bool> = tracks.iter().map(|t| t.fr().is_none()).collect();
+    loop {
+      let used: f32 = tracks
+        .iter()
+        .zip(&inflexible)
+        .filter(|(_, inflexible)| **inflexible)
+        .map(|(t, _)| t.size)
+        .sum();
+      let total_fr: f32 = tracks
+        .iter()
+        .zip(&inflexible)
+        .filter(|(_, inflexible)| !**inflexible)
+        .filter_map(|(t, _)| t.fr())
+        .sum();
+      if total_fr <= 0. {
+        break 0.;
+      }
+      let unit = (available - gaps - used).max(0.) / total_fr;
+      let mut changed = false;
+      for (t, inflexible) in tracks.iter().zip(inflexible.iter_mut()) {
+        if let Some(fr) = t.fr().filter(|_| !*inflexible) {
+          if unit * fr < t.size {
+            *inflexible = true;
+            changed = true;
+          }
+        }
+      }
+      if !changed {
+        break unit;
+      }
+    }
+  } else {
+    tracks
+      .iter()
+      .filter_map(|t| t.fr().map(|fr| t.size / fr))
+      .fold(0., f32::max)
+  };
+
+  for t in tracks.iter_mut() {
+    if let Some(fr) = t.fr() {
+      t.size = t.size.max(unit * fr);
+    }
+  }
+}
+
+fn track_offsets(tracks: &[TrackSize], gap: f32) -> Vec<f32> {
+  let mut offset = 0.;
+  tracks
+    .iter()
+    .map(|t| {
+      let start = offset;
+      offset += t.size + gap;
+      start
+    })
+    .collect()
+}
+
+fn span_size(tracks: &[TrackSize], offsets: &[f32], range: &Range<usize>) -> f32 {
+  let last = range.end - 1;
+  offsets[last] + tracks[last].size - offsets[range.start]
+}
+
+fn total_size(tracks: &[TrackSize], gap: f32) -> f32 {
+  let gaps = gap * tracks.len().saturating_sub(1) as f32;
+  tracks.iter().map(|t| t.size).sum::<f32>() + gaps
+}
+
+fn item_clamp(h_align: HAlign, v_align: VAlign, size: Size) -> BoxClamp {
+  let mut clamp = BoxClamp::max_size(size);
+  if h_align == HAlign::Stretch && size.width.is_finite() {
+    clamp.min.width = size.width;
+  }
+  if v_align == VAlign::Stretch && size.height.is_finite() {
+    clamp.min.height = size.height;
+  }
+  clamp
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use ribir_dev_helper::*;
+
+  use super::*;
+  use crate::prelude::*;
+
+  widget_layout_test!(
+    fixed_and_fr_tracks,
+    WidgetTester::new(fn_widget! {
+      let size = Size::new(20., 20.);
+      @Grid {
+        columns: vec![GridTrack::Fixed(100.), GridTrack::Fr(1.), GridTrack::Fr(2.)],
+        column_gap: 10.,
+        row_gap: 5.,
+        @SizedBox { size }
+        @SizedBox { size }
+        @SizedBox { size }
+        @SizedBox { size }
+      }
+    })
+    .with_wnd_size(Size::new(420., 400.)),
+    LayoutCase::default().with_size(Size::new(420., 45.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 100., 20.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(110., 0., 100., 20.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(220., 0., 200., 20.)),
+    LayoutCase::new(&[0, 3]).with_rect(ribir_geom::rect(0., 25., 100., 20.))
+  );
+
+  widget_layout_test!(
+    auto_tracks_with_span,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        columns: vec![GridTrack::Auto, GridTrack::Auto],
+        justify_items: HAlign::Left,
+        @SizedBox { size: Size::new(50., 10.) }
+        @SizedBox { size: Size::new(30., 20.) }
+        @GridItem {
+          column_span: 2,
+          @SizedBox { size: Size::new(100., 10.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(400., 400.)),
+    LayoutCase::default().with_size(Size::new(100., 30.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 50., 20.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(60., 0., 30., 20.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(0., 20., 100., 10.))
+  );
+
+  widget_layout_test!(
+    named_areas,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        columns: vec![GridTrack::Fixed(100.), GridTrack::Fr(1.)],
+        rows: vec![GridTrack::Fixed(50.), GridTrack::Fr(1.)],
+        areas: ["header header", "side main"],
+        @GridItem { area: "main", @Void {} }
+        @GridItem { area: "header", @Void {} }
+        @GridItem { area: "side", @Void {} }
+      }
+    })
+    .with_wnd_size(Size::new(400., 300.)),
+    LayoutCase::default().with_size(Size::new(400., 300.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(100., 50., 300., 250.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(0., 0., 400., 50.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(0., 50., 100., 250.))
+  );
+
+  widget_layout_test!(
+    explicit_placement_and_alignment,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        columns: vec![GridTrack::Fixed(100.), GridTrack::Fixed(100.)],
+        rows: vec![GridTrack::Fixed(100.), GridTrack::Fixed(100.)],
+        @GridItem {
+          row: 1,
+          column: 1,
+          justify_self: HAlign::Center,
+          align_self: VAlign::Bottom,
+          @SizedBox { size: Size::new(20., 20.) }
+        }
+        // Auto placed into the first free cell.
+        @SizedBox { size: Size::new(20., 20.) }
+        @GridItem {
+          row: 1,
+          @SizedBox { size: Size::new(20., 20.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(400., 400.)),
+    LayoutCase::default().with_size(Size::new(200., 200.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(140., 180., 20., 20.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(0., 0., 100., 100.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(0., 100., 100., 100.))
+  );
+
+  widget_layout_test!(
+    min_max_tracks,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        columns: vec![
+          GridTrack::MinMax(50., TrackMax::Fixed(150.)),
+          GridTrack::MinMax(100., TrackMax::Fr(1.)),
+        ],
+        @Void {}
+        @Void {}
+      }
+    })
+    .with_wnd_size(Size::new(300., 100.)),
+    LayoutCase::new(&[0, 0]).with_size(Size::new(150., 0.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(150., 0., 150., 0.))
+  );
+
+  #[test]
+  fn fr_not_smaller_than_content() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Grid {
+          columns: vec![GridTrack::Fr(1.), GridTrack::Fr(1.)],
+          @SizedBox { size: Size::new(150., 10.) }
+          @Void {}
+        }
+      },
+      Size::new(200., 100.),
+    );
+    wnd.draw_frame();
+    LayoutCase::expect_size(&wnd, &[0, 0], Size::new(150., 10.));
+    LayoutCase::expect_size(&wnd, &[0, 1], Size::new(50., 10.));
+  }
+
+  #[test]
+  fn grid_areas() {
+    let areas = GridAreas::from(["a a .", "b c c"]);
+    assert_eq!(areas.area("a"), Some((0..1, 0..2)));
+    assert_eq!(areas.area("c"), Some((1..2, 1..3)));
+    assert_eq!(areas.area("d"), None);
+    assert_eq!(areas.row_count(), 2);
+    assert_eq!(areas.column_count(), 3);
+  }
+}
@@ -0,0 +1,108 @@
+use ribir_core::prelude::*;
+
+/// A widget that sizes its child to the child's max intrinsic width.
+///
+/// It's useful when a child would expand to the available width, but you want
+/// it to be as wide as its content. For example, a column of buttons with
+/// `Align::Stretch` in an `IntrinsicWidth` makes all the buttons as wide as the
+/// widest one, like the cells of a table column.
+///
+/// The intrinsic width is queried before the layout, so it's more expensive
+/// than a normal layout, avoid using it in a deep tree.
+#[derive(SingleChild, Declare, Default)]
+pub struct IntrinsicWidth;
+
+/// A widget that sizes its child to the child's max intrinsic height.
+///
+/// It's the vertical counterpart of the [`IntrinsicWidth`], for example, a row
+/// of items with `Align::Stretch` in an `IntrinsicHeight` makes all the items
+/// as tall as the tallest one.
+#[derive(SingleChild, Declare, Default)]
+pub struct IntrinsicHeight;
+
+impl Render for IntrinsicWidth {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Some(child) = ctx.single_child() else { return clamp.min };
+    let height = clamp.max.height;
+    let width = ctx.child_intrinsic_size(child, IntrinsicSize::MaxWidth, height);
+    let width = width.clamp(clamp.min.width, clamp.max.width);
+    ctx.perform_child_layout(child, clamp.with_fixed_width(width))
+  }
+}
+
+impl Render for IntrinsicHeight {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Some(child) = ctx.single_child() else { return clamp.min };
+    let width = clamp.max.width;
+    let height = ctx.child_intrinsic_size(child, IntrinsicSize::MaxHeight, width);
+    let height = height.clamp(clamp.min.height, clamp.max.height);
+    ctx.perform_child_layout(child, clamp.with_fixed_height(height))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::test_helper::*;
+  use ribir_dev_helper::*;
+
+  use super::*;
+  use crate::prelude::*;
+
+  widget_layout_test!(
+    stretch_to_widest,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        @Column {
+          align_items: Align::Stretch,
+          @SizedBox { size: Size::new(50., 10.) }
+          @SizedBox { size: Size::new(100., 10.) }
+        }
+      }
+    }),
+    LayoutCase::default().with_size(Size::new(100., 20.)),
+    LayoutCase::new(&[0, 0, 0]).with_size(Size::new(100., 10.)),
+    LayoutCase::new(&[0, 0, 1]).with_size(Size::new(100., 10.))
+  );
+
+  widget_layout_test!(
+    flex_main_axis,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        @Flex {
+          item_gap: 5.,
+          @SizedBox { size: Size::new(10., 10.) }
+          @SizedBox { size: Size::new(20., 10.) }
+        }
+      }
+    }),
+    LayoutCase::default().with_size(Size::new(35., 10.))
+  );
+
+  widget_layout_test!(
+    stretch_to_tallest,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicHeight {
+        @Flex {
+          align_items: Align::Stretch,
+          @SizedBox { size: Size::new(10., 10.) }
+          @SizedBox { size: Size::new(10., 30.) }
+        }
+      }
+    }),
+    LayoutCase::default().with_size(Size::new(20., 30.)),
+    LayoutCase::new(&[0, 0, 0]).with_size(Size::new(10., 30.))
+  );
+
+  widget_layout_test!(
+    stack_ignore_in_parent,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        @Stack {
+          @SizedBox { size: Size::new(40., 10.) }
+          @InParentLayout { @SizedBox { size: Size::new(100., 10.) } }
+        }
+      }
+    }),
+    LayoutCase::default().with_size(Size::new(40., 10.))
+  );
+}
@@ -86,6 +86,58 @@ impl Render for Flex {
 
   #[inline]
   fn paint(&self, _: &mut PaintingCtx) {}
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MinWidth, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MaxWidth, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MinHeight, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MaxHeight, width, ctx)
+  }
+}
+
+impl Flex {
+  /// The main axis is the sum of the children and the gaps, and the cross axis
+  /// is the largest child. A wrapping flex only knows its cross axis after it
+  /// breaks the lines, so it's measured by performing the layout.
+  fn intrinsic(&self, kind: IntrinsicSize, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    let is_main = match kind {
+      IntrinsicSize::MinWidth | IntrinsicSize::MaxWidth => self.direction.is_horizontal(),
+      IntrinsicSize::MinHeight | IntrinsicSize::MaxHeight => self.direction.is_vertical(),
+    };
+    if !is_main && self.wrap {
+      return kind.measure(self, extent, ctx);
+    }
+
+    // Only the stretched children know their cross axis before the layout.
+    let stretch = self.align_items == Align::Stretch;
+    let child_extent = if is_main && stretch { extent } else { f32::INFINITY };
+    let (ctx, children) = ctx.split_children();
+    let (mut sum, mut max, mut cnt) = (0., 0f32, 0);
+    for c in children {
+      let size = ctx.child_intrinsic_size(c, kind, child_extent);
+      sum += size;
+      max = max.max(size);
+      cnt += 1;
+    }
+
+    let is_min = matches!(kind, IntrinsicSize::MinWidth | IntrinsicSize::MinHeight);
+    if !is_main || (self.wrap && is_min) {
+      max
+    } else if cnt > 0 {
+      sum + self.item_gap * (cnt - 1) as f32
+    } else {
+      0.
+    }
+  }
 }
 
 struct FlexLayouter {
@@ -113,6 +165,9 @@ impl FlexLayouter {
       let container = dir.container_main(&clamp, self.main_size());
       self.flex_layout(container, main_max, child_clamp, ctx);
     }
+    if self.align_items == Align::Baseline && dir.is_horizontal() {
+      self.collect_baselines(ctx);
+    }
 
     let expect = self.finally_size(main_max);
     let real = clamp.clamp(expect);
@@ -188,6 +243,7 @@ impl FlexLayouter {
         cross_pos: 0.,
         size,
         defer_layout: expanded.is_some_and(|e| e.defer_alloc),
+        baseline: None,
       };
       self.current_line.items_info.push(info);
     }
@@ -228,6 +284,16 @@ impl FlexLayouter {
     });
   }
 
+  fn collect_baselines(&mut self, ctx: &mut LayoutCtx) {
+    let (ctx, mut children) = ctx.split_children();
+    self.lines.iter_mut().for_each(|line| {
+      line.items_info.iter_mut().for_each(|info| {
+        let child = children.next().unwrap();
+        info.baseline = ctx.child_baseline(child, BaselineKind::First);
+      })
+    });
+  }
+
   fn finally_size(&self, main_max: f32) -> Size {
     let main = if main_max.is_finite() && self.justify_content.is_space_layout() {
       main_max
@@ -264,9 +330,13 @@ impl FlexLayouter {
       }
 
       let line_cross = line.max_cross(dir);
+      let max_baseline = line.max_baseline();
       line.for_each_item(reverse, |item| {
         let (item_main, item_cross) = dir.main_cross_of(item.size);
-        let item_cross_offset = align_items.align_value(item_cross, line_cross);
+        let item_cross_offset = match item.baseline {
+          Some(b) => max_baseline - b,
+          None => align_items.align_value(item_cross, line_cross),
+        };
 
         item.cross_pos = cross + item_cross_offset + cross_axis_gap;
         item.main_pos = main;
@@ -319,6 +389,8 @@ struct FlexLayoutInfo {
   size: Size,
   flex: Option<f32>,
   defer_layout: bool,
+  /// The first baseline of the item, only collected for the baseline alignment.
+  baseline: Option<f32>,
 }
 
 impl MainLineInfo {
@@ -370,10 +442,19 @@ impl MainLineInfo {
   }
 
   fn max_cross(&self, dir: Direction) -> f32 {
+    let max_baseline = self.max_baseline();
+    self.items_info.iter().fold(0., |acc, info| {
+      let offset = info.baseline.map_or(0., |b| max_baseline - b);
+      acc.max(offset + dir.cross_of(info.size))
+    })
+  }
+
+  fn max_baseline(&self) -> f32 {
     self
       .items_info
       .iter()
-      .fold(0., |acc, info| acc.max(dir.cross_of(info.size)))
+      .filter_map(|info| info.baseline)
+      .fold(0., f32::max)
   }
 
   fn for_each_item(&mut self, reverse: bool, mut f: impl FnMut(&mut FlexLayoutInfo)) {
@@ -760,4 +841,58 @@ mod tests {
     .with_wnd_size(Size::splat(300.)),
     LayoutCase::new(&[0, 0, 1]).with_size(Size::new(50., 140.)),
   );
+
+  /// A box with a fixed baseline.
+  #[derive(Declare)]
+  struct MockBaseline {
+    size: Size,
+    baseline: f32,
+  }
+
+  impl Render for MockBaseline {
+    fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.clamp(self.size) }
+
+    fn baseline(&self, _: BaselineKind, _: &mut LayoutCtx) -> Option<f32> { Some(self.baseline) }
+  }
+
+  widget_layout_test!(
+    baseline_cross_align,
+    WidgetTester::new(fn_widget! {
+      @Flex {
+        align_items: Align::Baseline,
+        @MockBaseline { size: Size::new(10., 30.), baseline: 20. }
+        @MockBaseline { size: Size::new(10., 10.), baseline: 5. }
+        @SizedBox { size: Size::new(10., 10.) }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(30., 30.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 10., 30.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(10., 15., 10., 10.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(20., 0., 10., 10.))
+  );
+
+  widget_layout_test!(
+    nested_baseline_align,
+    WidgetTester::new(fn_widget! {
+      @Row {
+        align_items: Align::Baseline,
+        @MockBaseline {
+          padding: EdgeInsets::only_top(4.),
+          size: Size::new(10., 10.),
+          baseline: 4.,
+        }
+        @Flex {
+          align_items: Align::Baseline,
+          @MockBaseline { size: Size::new(10., 10.), baseline: 5. }
+          @MockBaseline { size: Size::new(10., 30.), baseline: 20. }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(30., 30.)),
+    // The baseline of the padding box is 8, and the baseline of the flex is 20.
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 12., 10., 14.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(10., 0., 20., 30.))
+  );
 }
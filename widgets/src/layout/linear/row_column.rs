@@ -103,11 +103,39 @@ fn perform_linear_layout(
   let main_container = dir.container_main(&clamp, main);
   let (mut main_pos, step) = justify_content.item_offset_and_step(main_container - main, child_cnt);
 
+  // The children are aligned by their baselines, and the baseline of every
+  // child is placed at the lowest baseline of the children.
+  let baselines: Option<Vec<_>> =
+    (align_items == Align::Baseline && dir.is_horizontal()).then(|| {
+      let (ctx, children) = ctx.split_children();
+      children
+        .map(|c| ctx.child_baseline(c, BaselineKind::First))
+        .collect()
+    });
+  let max_baseline = baselines
+    .iter()
+    .flatten()
+    .flatten()
+    .fold(0f32, |max, b| max.max(*b));
+  if let Some(baselines) = baselines.as_ref() {
+    let (ctx, children) = ctx.split_children();
+    cross = children
+      .zip(baselines)
+      .fold(0f32, |cross, (c, b)| {
+        let height = ctx.widget_box_size(c).unwrap().height;
+        cross.max(b.map_or(0., |b| max_baseline - b) + height)
+      });
+  }
+
   let (ctx, children) = ctx.split_children();
   let cross = dir.cross_clamp(cross, &clamp);
-  for child in children {
+  for (idx, child) in children.enumerate() {
     let child_size = ctx.widget_box_size(child).unwrap();
-    let cross_pos = align_items.align_value(dir.cross_of(child_size), cross);
+    let baseline = baselines.as_ref().and_then(|b| b[idx]);
+    let cross_pos = match baseline {
+      Some(b) => max_baseline - b,
+      None => align_items.align_value(dir.cross_of(child_size), cross),
+    };
 
     ctx.update_position(child, dir.to_point(main_pos, cross_pos));
     main_pos += dir.main_of(child_size) + step;
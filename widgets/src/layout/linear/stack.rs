@@ -106,6 +106,37 @@ impl Render for Stack {
     }
     stack_size
   }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MinWidth, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MaxWidth, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MinHeight, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.intrinsic(IntrinsicSize::MaxHeight, width, ctx)
+  }
+}
+
+impl Stack {
+  /// The largest intrinsic size of the children, the in-parent children follow
+  /// the stack size, so they don't affect it.
+  fn intrinsic(&self, kind: IntrinsicSize, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    let (ctx, children) = ctx.split_children();
+    let mut max = 0f32;
+    for c in children {
+      if ctx.query_of_widget::<InParentLayout>(c).is_none() {
+        max = max.max(ctx.child_intrinsic_size(c, kind, extent));
+      }
+    }
+    max
+  }
 }
 
 impl<'c> ComposeChild<'c> for InParentLayout {
@@ -1,7 +1,11 @@
 pub mod avatar;
 pub mod buttons;
+pub mod calendar;
 pub mod checkbox;
+pub mod combobox;
 pub mod common_widget;
+pub mod data_table;
+pub mod date_picker;
 pub mod divider;
 pub mod grid_view;
 pub mod icon;
@@ -9,21 +13,31 @@ pub mod input;
 pub mod label;
 pub mod layout;
 pub mod list;
+pub mod markdown;
 pub mod menu;
 pub mod path;
 pub mod progress;
 pub mod radio;
+pub mod refresh;
 pub mod router;
 pub mod scrollbar;
+pub mod select;
 pub mod select_region;
+pub mod selection_area;
 pub mod slider;
+pub mod split_pane;
+pub mod sticky;
 pub mod tabs;
+pub mod time_picker;
+pub mod tree_view;
 
 pub mod transform_box;
 pub mod prelude {
   pub use super::{
-    avatar::*, buttons::*, checkbox::*, common_widget::*, divider::*, grid_view::*, icon::*,
-    input::*, label::*, layout::*, list::*, menu::*, path::*, progress::*, radio::*, router::*,
-    scrollbar::*, select_region::*, slider::*, tabs::*, transform_box::*,
+    avatar::*, buttons::*, calendar::*, checkbox::*, combobox::*, common_widget::*, data_table::*,
+    date_picker::*, divider::*, grid_view::*, icon::*, input::*, label::*, layout::*, list::*,
+    markdown::*, menu::*, path::*, progress::*, radio::*, refresh::*, router::*, scrollbar::*,
+    select::*, select_region::*, selection_area::*, slider::*, split_pane::*, sticky::*, tabs::*,
+    time_picker::*, transform_box::*, tree_view::*,
   };
 }
@@ -0,0 +1,633 @@
+use std::{ops::Range, rc::Rc};
+
+use pulldown_cmark::{Alignment, Event, Options, Parser, Tag, TagEnd};
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the markdown widget"]
+  MARKDOWN,
+  #[doc = "Class name for the block quote of the markdown"]
+  MARKDOWN_QUOTE,
+  #[doc = "Class name for the code block of the markdown"]
+  MARKDOWN_CODE_BLOCK,
+  #[doc = "Class name for the inline code of the markdown"]
+  MARKDOWN_INLINE_CODE,
+  #[doc = "Class name for the links of the markdown"]
+  MARKDOWN_LINK,
+  #[doc = "Class name for the table of the markdown"]
+  MARKDOWN_TABLE,
+}
+
+/// The callback to handle the tap on a link, it receives the url of the link.
+pub type LinkHandler = Rc<dyn Fn(&str)>;
+
+/// The callback to load the image of an url, the alt text is displayed if it
+/// returns `None`.
+pub type ImageLoader = Rc<dyn Fn(&str) -> Option<Resource<PixelImage>>>;
+
+/// A widget that renders the CommonMark text.
+///
+/// It supports the headings, the emphasis, the lists, the block quotes, the
+/// code blocks, the tables, the links and the images. The headings use the
+/// `headline_*` and `title_*` text styles of the [`TypographyTheme`], and the
+/// others use the `body_medium` one.
+///
+/// A tap on a link calls the `on_link_tap` callback, or goes to the url by
+/// [`Location::goto`] if there is no callback.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @Markdown {
+///     content: "# Release notes\n\n- **Faster** layout\n- [Details](/notes/1)",
+///     on_link_tap: |url: &str| println!("open {url}"),
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct Markdown {
+  /// The markdown text to render.
+  pub content: CowArc<str>,
+  #[declare(custom, default)]
+  on_link_tap: Option<LinkHandler>,
+  #[declare(custom, default)]
+  image_loader: Option<ImageLoader>,
+}
+
+impl MarkdownDeclarer {
+  /// Set the callback to handle the tap on a link.
+  pub fn on_link_tap(&mut self, f: impl Fn(&str) + 'static) -> &mut Self {
+    let f: LinkHandler = Rc::new(f);
+    self.on_link_tap = Some(PipeValue::Value(Some(f)));
+    self
+  }
+
+  /// Set the callback to load the images.
+  pub fn with_image_loader(
+    &mut self, f: impl Fn(&str) -> Option<Resource<PixelImage>> + 'static,
+  ) -> &mut Self {
+    let f: ImageLoader = Rc::new(f);
+    self.image_loader = Some(PipeValue::Value(Some(f)));
+    self
+  }
+}
+
+impl Compose for Markdown {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      @Flex {
+        class: MARKDOWN,
+        direction: Direction::Vertical,
+        item_gap: 12.,
+        @pipe! {
+          let this = $read(this);
+          let render = MdRender {
+            on_link_tap: this.on_link_tap.clone(),
+            image_loader: this.image_loader.clone(),
+          };
+          parse_markdown(&this.content)
+            .into_iter()
+            .map(move |block| render.block(block))
+            .collect::<Vec<_>>()
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MdBlock {
+  Heading(usize, Vec<MdInline>),
+  Paragraph(Vec<MdInline>),
+  Quote(Vec<MdBlock>),
+  List { start: Option<u64>, items: Vec<Vec<MdBlock>> },
+  Code(String),
+  Table { aligns: Vec<Alignment>, head: Vec<Vec<MdInline>>, rows: Vec<Vec<Vec<MdInline>>> },
+  Rule,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MdInline {
+  Text { text: String, style: InlineStyle, link: Option<CowArc<str>> },
+  Image { url: CowArc<str>, alt: String },
+  Break,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct InlineStyle {
+  strong: bool,
+  emphasis: bool,
+  code: bool,
+}
+
+fn parse_markdown(content: &str) -> Vec<MdBlock> {
+  parse_blocks(&mut Parser::new_ext(content, Options::ENABLE_TABLES))
+}
+
+/// Parse the blocks until the end of the container block.
+fn parse_blocks<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> Vec<MdBlock> {
+  let mut blocks = vec![];
+  // The inline content not in a paragraph, e.g. the item of a tight list.
+  let mut inlines = InlineBuilder::default();
+  while let Some(event) = events.next() {
+    let Err(event) = inlines.push(event) else { continue };
+    if matches!(event, Event::Start(_) | Event::End(_) | Event::Rule) && !inlines.is_empty() {
+      blocks.push(MdBlock::Paragraph(inlines.take()));
+    }
+    match event {
+      Event::Start(Tag::Paragraph) => blocks.push(MdBlock::Paragraph(parse_inlines(events))),
+      Event::Start(Tag::Heading { level, .. }) => {
+        blocks.push(MdBlock::Heading(level as usize, parse_inlines(events)))
+      }
+      Event::Start(Tag::BlockQuote(_)) => blocks.push(MdBlock::Quote(parse_blocks(events))),
+      Event::Start(Tag::CodeBlock(_)) => {
+        let mut code = String::new();
+        for e in events.by_ref() {
+          match e {
+            Event::Text(t) => code.push_str(&t),
+            Event::End(_) => break,
+            _ => {}
+          }
+        }
+        let len = code.trim_end_matches('\n').len();
+        code.truncate(len);
+        blocks.push(MdBlock::Code(code));
+      }
+      Event::Start(Tag::List(start)) => {
+        let mut items = vec![];
+        while let Some(Event::Start(Tag::Item)) = events.next() {
+          items.push(parse_blocks(events));
+        }
+        blocks.push(MdBlock::List { start, items });
+      }
+      Event::Start(Tag::Table(aligns)) => {
+        let (mut head, mut rows) = (vec![], vec![]);
+        while let Some(Event::Start(tag)) = events.next() {
+          let mut row = vec![];
+          while let Some(Event::Start(Tag::TableCell)) = events.next() {
+            row.push(parse_inlines(events));
+          }
+          if matches!(tag, Tag::TableHead) {
+            head = row;
+          } else {
+            rows.push(row);
+          }
+        }
+        blocks.push(MdBlock::Table { aligns, head, rows });
+      }
+      // Keep the content of the other containers, e.g. the html blocks.
+      Event::Start(_) => blocks.extend(parse_blocks(events)),
+      Event::End(_) => break,
+      Event::Rule => blocks.push(MdBlock::Rule),
+      _ => {}
+    }
+  }
+  if !inlines.is_empty() {
+    blocks.push(MdBlock::Paragraph(inlines.take()));
+  }
+  blocks
+}
+
+/// Parse the inline content until the end of the leaf block.
+fn parse_inlines<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> Vec<MdInline> {
+  let mut inlines = InlineBuilder::default();
+  for event in events.by_ref() {
+    if let Err(Event::End(_)) = inlines.push(event) {
+      break;
+    }
+  }
+  inlines.take()
+}
+
+#[derive(Default)]
+struct InlineBuilder {
+  inlines: Vec<MdInline>,
+  strong: usize,
+  emphasis: usize,
+  links: Vec<CowArc<str>>,
+  image: Option<(CowArc<str>, String)>,
+}
+
+impl InlineBuilder {
+  /// Push an inline event, return the event back if it's not an inline one.
+  fn push<'a>(&mut self, event: Event<'a>) -> Result<(), Event<'a>> {
+    match event {
+      Event::Start(Tag::Strong) => self.strong += 1,
+      Event::End(TagEnd::Strong) => self.strong = self.strong.saturating_sub(1),
+      Event::Start(Tag::Emphasis) => self.emphasis += 1,
+      Event::End(TagEnd::Emphasis) => self.emphasis = self.emphasis.saturating_sub(1),
+      Event::Start(Tag::Link { dest_url, .. }) => self.links.push(dest_url.to_string().into()),
+      Event::End(TagEnd::Link) => {
+        self.links.pop();
+      }
+      Event::Start(Tag::Image { dest_url, .. }) => {
+        self.image = Some((dest_url.to_string().into(), String::new()))
+      }
+      Event::End(TagEnd::Image) => {
+        if let Some((url, alt)) = self.image.take() {
+          self.inlines.push(MdInline::Image { url, alt });
+        }
+      }
+      Event::Text(t) | Event::Html(t) | Event::InlineHtml(t) => self.text(&t, false),
+      Event::Code(t) => self.text(&t, true),
+      Event::SoftBreak => self.text(" ", false),
+      Event::HardBreak => self.inlines.push(MdInline::Break),
+      event => return Err(event),
+    }
+    Ok(())
+  }
+
+  fn text(&mut self, t: &str, code: bool) {
+    if let Some((_, alt)) = self.image.as_mut() {
+      alt.push_str(t);
+      return;
+    }
+
+    let style = InlineStyle { strong: self.strong > 0, emphasis: self.emphasis > 0, code };
+    let link = self.links.last().cloned();
+    match self.inlines.last_mut() {
+      Some(MdInline::Text { text, style: s, link: l }) if !code && *s == style && *l == link => {
+        text.push_str(t)
+      }
+      _ => self
+        .inlines
+        .push(MdInline::Text { text: t.to_string(), style, link }),
+    }
+  }
+
+  fn is_empty(&self) -> bool { self.inlines.is_empty() }
+
+  fn take(&mut self) -> Vec<MdInline> { std::mem::take(&mut self.inlines) }
+}
+
+struct MdRender {
+  on_link_tap: Option<LinkHandler>,
+  image_loader: Option<ImageLoader>,
+}
+
+impl MdRender {
+  fn block(&self, block: MdBlock) -> Widget<'static> {
+    let typography = TypographyTheme::of(BuildCtx::get()).clone();
+    let body = typography.body_medium.text;
+    match block {
+      MdBlock::Heading(level, inlines) => {
+        let theme = match level {
+          1 => typography.headline_large,
+          2 => typography.headline_medium,
+          3 => typography.headline_small,
+          4 => typography.title_large,
+          5 => typography.title_medium,
+          _ => typography.title_small,
+        };
+        self.inlines(inlines, &theme.text)
+      }
+      MdBlock::Paragraph(inlines) => self.inlines(inlines, &body),
+      MdBlock::Quote(blocks) => {
+        let mut quote = FatObj::new(self.blocks(blocks));
+        rdl! { @(quote) { class: MARKDOWN_QUOTE } }.into_widget()
+      }
+      MdBlock::List { start, items } => {
+        let items = items.into_iter().enumerate().map(|(i, item)| {
+          let marker = match start {
+            Some(start) => format!("{}.", start + i as u64),
+            None => "•".to_string(),
+          };
+          let item = self.blocks(item);
+          rdl! {
+            @Flex {
+              item_gap: 8.,
+              @Text { text: marker, text_style: body.clone() }
+              @Expanded { @ { item } }
+            }
+          }
+          .into_widget()
+        });
+        rdl! {
+          @Flex {
+            direction: Direction::Vertical,
+            item_gap: 4.,
+            @ { items.collect::<Vec<_>>() }
+          }
+        }
+        .into_widget()
+      }
+      MdBlock::Code(code) => {
+        let mut style = body;
+        style.font_face.families = Box::new([FontFamily::Monospace]);
+        rdl! { @Text { class: MARKDOWN_CODE_BLOCK, text: code, text_style: style } }.into_widget()
+      }
+      MdBlock::Table { aligns, head, rows } => {
+        let mut strong = body.clone();
+        strong.font_face.weight = FontWeight::BOLD;
+        let columns = head
+          .len()
+          .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        let cells = std::iter::once((head, &strong))
+          .chain(rows.into_iter().map(|row| (row, &body)))
+          .flat_map(|(row, style)| {
+            let aligns = &aligns;
+            row.into_iter().enumerate().map(move |(i, cell)| {
+              let justify_self = match aligns.get(i) {
+                Some(Alignment::Center) => HAlign::Center,
+                Some(Alignment::Right) => HAlign::Right,
+                _ => HAlign::Left,
+              };
+              let cell = self.inlines(cell, style);
+              rdl! { @GridItem { justify_self: Some(justify_self), @ { cell } } }.into_widget()
+            })
+          })
+          .collect::<Vec<_>>();
+        rdl! {
+          @Grid {
+            class: MARKDOWN_TABLE,
+            columns: vec![GridTrack::Auto; columns],
+            column_gap: 16.,
+            row_gap: 8.,
+            @ { cells }
+          }
+        }
+        .into_widget()
+      }
+      MdBlock::Rule => Divider::declarer().finish().into_widget(),
+    }
+  }
+
+  fn blocks(&self, blocks: Vec<MdBlock>) -> Widget<'static> {
+    let blocks = blocks
+      .into_iter()
+      .map(|b| self.block(b))
+      .collect::<Vec<_>>();
+    rdl! {
+      @Flex {
+        direction: Direction::Vertical,
+        item_gap: 8.,
+        @ { blocks }
+      }
+    }
+    .into_widget()
+  }
+
+  /// The inline content is laid out in a wrapped flex, split at the line
+  /// break opportunities of the text, and a hard break starts a new flex.
+  fn inlines(&self, inlines: Vec<MdInline>, style: &TextStyle) -> Widget<'static> {
+    let mut lines = inlines
+      .split(|i| matches!(i, MdInline::Break))
+      .map(|line| self.line(line, style))
+      .collect::<Vec<_>>();
+    if lines.len() == 1 {
+      lines.pop().unwrap()
+    } else {
+      rdl! { @Flex { direction: Direction::Vertical, @ { lines } } }.into_widget()
+    }
+  }
+
+  fn line(&self, inlines: &[MdInline], style: &TextStyle) -> Widget<'static> {
+    // A plain line is a single text, so it wraps as a normal text.
+    if let [
+      MdInline::Text {
+        text,
+        style: InlineStyle { strong: false, emphasis: false, code: false },
+        link: None,
+      },
+    ] = inlines
+    {
+      let text = text.clone();
+      return rdl! { @Text { text, text_style: style.clone() } }.into_widget();
+    }
+
+    let (line, segments) = line_segments(inlines, style.word_break);
+    let segments = segments
+      .into_iter()
+      .map(|pieces| {
+        let mut pieces = pieces
+          .into_iter()
+          .map(|(idx, range)| self.piece(&inlines[idx], &line[range], style))
+          .collect::<Vec<_>>();
+        if pieces.len() == 1 {
+          pieces.pop().unwrap()
+        } else {
+          // The pieces of different styles between two break opportunities are
+          // kept in a line.
+          rdl! { @Flex { align_items: Align::Baseline, @ { pieces } } }.into_widget()
+        }
+      })
+      .collect::<Vec<_>>();
+    rdl! { @Flex { wrap: true, align_items: Align::Baseline, @ { segments } } }.into_widget()
+  }
+
+  /// The widget of a piece of the `inline`, the `text` is the piece of its
+  /// text.
+  fn piece(&self, inline: &MdInline, text: &str, style: &TextStyle) -> Widget<'static> {
+    match inline {
+      MdInline::Text { style: inline_style, link, .. } => {
+        let mut style = style.clone();
+        if inline_style.strong {
+          style.font_face.weight = FontWeight::BOLD;
+        }
+        if inline_style.emphasis {
+          style.font_face.style = FontStyle::Italic;
+        }
+        if inline_style.code {
+          style.font_face.families = Box::new([FontFamily::Monospace]);
+          let text = text.to_string();
+          return rdl! {
+            @Text { class: MARKDOWN_INLINE_CODE, text, text_style: style }
+          }
+          .into_widget();
+        }
+        self.word(text.to_string(), &style, link.clone())
+      }
+      MdInline::Image { url, alt } => {
+        let img = self.image_loader.as_ref().and_then(|f| f(url));
+        match img {
+          Some(img) => img.into_widget(),
+          None => {
+            let alt = alt.clone();
+            rdl! { @Text { text: alt, text_style: style.clone() } }.into_widget()
+          }
+        }
+      }
+      MdInline::Break => Void.into_widget(),
+    }
+  }
+
+  fn word(&self, word: String, style: &TextStyle, link: Option<CowArc<str>>) -> Widget<'static> {
+    let style = style.clone();
+    let Some(url) = link else {
+      return rdl! { @Text { text: word, text_style: style } }.into_widget();
+    };
+    let handler = self.on_link_tap.clone();
+    rdl! {
+      @Text {
+        class: MARKDOWN_LINK,
+        text: word,
+        text_style: style,
+        cursor: CursorIcon::Pointer,
+        on_tap: move |e| open_link(&url, handler.as_ref(), e),
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The pieces of the inlines between two line break opportunities, every piece
+/// is the index of its inline and its byte range in the line text.
+type Segment = Vec<(usize, Range<usize>)>;
+
+/// Split the inlines of a line at the line break opportunities of the whole
+/// line, so a word across the styles isn't broken, and the CJK text breaks
+/// between the characters. Return the text of the line and the segments
+/// between the opportunities. An image is an object replacement character in
+/// the line, and an inline code is never split.
+fn line_segments(inlines: &[MdInline], word_break: WordBreak) -> (String, Vec<Segment>) {
+  let mut line = String::new();
+  let ranges: Vec<_> = inlines
+    .iter()
+    .map(|inline| {
+      let start = line.len();
+      match inline {
+        MdInline::Text { text, .. } => line.push_str(text),
+        _ => line.push('\u{FFFC}'),
+      }
+      start..line.len()
+    })
+    .collect();
+  let mut breaks = line_break::line_break_opportunities(&line, word_break);
+  breaks.push(line.len());
+
+  let mut segments = vec![];
+  let mut pieces = vec![];
+  for (idx, (inline, range)) in inlines.iter().zip(ranges).enumerate() {
+    let splittable = matches!(inline, MdInline::Text { style, .. } if !style.code);
+    let mut start = range.start;
+    let cuts = breaks
+      .iter()
+      .copied()
+      .filter(|b| splittable && range.start < *b && *b < range.end)
+      .chain(std::iter::once(range.end));
+    for end in cuts {
+      if start < end {
+        pieces.push((idx, start..end));
+      }
+      if breaks.binary_search(&end).is_ok() && !pieces.is_empty() {
+        segments.push(std::mem::take(&mut pieces));
+      }
+      start = end;
+    }
+  }
+  (line, segments)
+}
+
+fn open_link(url: &str, handler: Option<&LinkHandler>, e: &PointerEvent) {
+  if let Some(handler) = handler {
+    handler(url);
+  } else if Provider::write_of::<Location>(e).is_none() {
+    log::warn!("[Markdown]: no `Location` to open the link `{url}`.");
+  } else if let Err(err) = Location::goto(url, e) {
+    log::warn!("[Markdown]: failed to open the link `{url}`: {err}");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::RefCell;
+
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn text(text: &str, style: InlineStyle, link: Option<&str>) -> MdInline {
+    MdInline::Text { text: text.into(), style, link: link.map(|l| l.to_string().into()) }
+  }
+
+  #[test]
+  fn parse_blocks() {
+    let plain = InlineStyle::default();
+    let md = "# Title\n\nSome **bold** and `code`.\n\n- one\n- [two](/two)\n\n> quote\n\n```\nlet \
+              a = 1;\n```\n\n| A | B |\n|:--|--:|\n| 1 | 2 |\n\n![logo](logo.png)\n\n---";
+    assert_eq!(
+      parse_markdown(md),
+      vec![
+        MdBlock::Heading(1, vec![text("Title", plain, None)]),
+        MdBlock::Paragraph(vec![
+          text("Some ", plain, None),
+          text("bold", InlineStyle { strong: true, ..plain }, None),
+          text(" and ", plain, None),
+          text("code", InlineStyle { code: true, ..plain }, None),
+          text(".", plain, None),
+        ]),
+        MdBlock::List {
+          start: None,
+          items: vec![
+            vec![MdBlock::Paragraph(vec![text("one", plain, None)])],
+            vec![MdBlock::Paragraph(vec![text("two", plain, Some("/two"))])],
+          ],
+        },
+        MdBlock::Quote(vec![MdBlock::Paragraph(vec![text("quote", plain, None)])]),
+        MdBlock::Code("let a = 1;".into()),
+        MdBlock::Table {
+          aligns: vec![Alignment::Left, Alignment::Right],
+          head: vec![vec![text("A", plain, None)], vec![text("B", plain, None)]],
+          rows: vec![vec![vec![text("1", plain, None)], vec![text("2", plain, None)]]],
+        },
+        MdBlock::Paragraph(vec![MdInline::Image { url: "logo.png".into(), alt: "logo".into() }]),
+        MdBlock::Rule,
+      ]
+    );
+  }
+
+  #[test]
+  fn split_line_at_break_opportunities() {
+    let plain = InlineStyle::default();
+    let strong = InlineStyle { strong: true, ..plain };
+    let code = InlineStyle { code: true, ..plain };
+    let inlines = [
+      text("Some ", plain, None),
+      text("bold", strong, None),
+      text(". 你好", plain, None),
+      text("a b", code, None),
+    ];
+    let (line, segments) = line_segments(&inlines, WordBreak::Normal);
+    let segments: Vec<Vec<_>> = segments
+      .into_iter()
+      .map(|pieces| {
+        pieces
+          .into_iter()
+          .map(|(_, rg)| &line[rg])
+          .collect()
+      })
+      .collect();
+    assert_eq!(segments, [vec!["Some "], vec!["bold", ". "], vec!["你"], vec!["好"], vec!["a b"]]);
+  }
+
+  #[test]
+  fn tap_link() {
+    reset_test_env!();
+    let tapped = Rc::new(RefCell::new(String::new()));
+    let c_tapped = tapped.clone();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let c_tapped = c_tapped.clone();
+        @Markdown {
+          content: "[home](/home) page",
+          on_link_tap: move |url: &str| *c_tapped.borrow_mut() = url.to_string(),
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    wnd.process_cursor_move(Point::new(2., 2.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    assert_eq!(&*tapped.borrow(), "/home");
+  }
+}
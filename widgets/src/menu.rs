@@ -146,6 +146,13 @@ impl MenuControl {
     self.show_map(anchor_around(Rect::new(pos, Size::zero())), wnd);
   }
 
+  /// Show the menu as a dropdown below the target rect, the target rect is
+  /// relative to the window. If there is not enough space below the target,
+  /// the menu will be placed above it.
+  pub fn show_below(&self, target: Rect, wnd: &Sc<Window>) {
+    self.show_map(anchor_below(target), wnd);
+  }
+
   pub fn show_map<F>(&self, mut f: F, wnd: &Sc<Window>)
   where
     F: FnMut(Widget<'static>) -> Widget<'static> + 'static,
@@ -256,7 +263,8 @@ impl MenuControl {
     true
   }
 
-  fn selected(&self) -> Option<usize> { self.0.borrow().selected }
+  /// Return the index of the selected item.
+  pub fn selected(&self) -> Option<usize> { self.0.borrow().selected }
 
   fn inner_show(&self, gen: GenWidget, parent: Option<ParentMenuInfo>, wnd: &Sc<Window>) {
     let handle = self.clone();
@@ -377,6 +385,41 @@ fn anchor_around(target: Rect) -> impl FnMut(Widget<'static>) -> Widget<'static>
   }
 }
 
+pub(crate) fn anchor_below(target: Rect) -> impl FnMut(Widget<'static>) -> Widget<'static> {
+  move |w: Widget<'static>| -> Widget<'static> {
+    fn_widget! {
+      let mut w = FatObj::new(w);
+      @(w) {
+        global_anchor_x: GlobalAnchorX::custom(move |host, wnd| {
+          let host_id = host.get().unwrap();
+          let wnd_size = wnd.size();
+          if let Some(size) = wnd.widget_size(host_id) {
+            if target.min_x() + size.width < wnd_size.width {
+              return Ok(target.min_x())
+            } else {
+              return Ok((0_f32).max(wnd_size.width - size.width))
+            }
+          }
+          Ok(target.min_x())
+        }),
+        global_anchor_y: GlobalAnchorY::custom(move |host, wnd| {
+          let host_id = host.get().unwrap();
+          let wnd_size = wnd.size();
+          if let Some(size) = wnd.widget_size(host_id) {
+            if target.max_y() + size.height < wnd_size.height {
+              return Ok(target.max_y())
+            } else {
+              return Ok((0_f32).max(target.min_y() - size.height))
+            }
+          }
+          Ok(target.max_y())
+        })
+      }
+    }
+    .into_widget()
+  }
+}
+
 pub struct MenuHintText(TextValue);
 impl MenuHintText {
   pub fn new<K: ?Sized>(child: impl RInto<TextValue, K>) -> Self { MenuHintText(child.r_into()) }
@@ -0,0 +1,568 @@
+use std::{future::Future, pin::Pin, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class for the indicator of the [`RefreshIndicator`]
+  REFRESH_INDICATOR,
+  /// Class for the footer of the [`InfiniteScroll`] shown while loading
+  LOAD_MORE_FOOTER,
+}
+
+/// The future of a refresh or a page loading, the loading ends when it
+/// resolves.
+pub type LoadFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// The callback to start a refresh or a page loading.
+pub type LoadCallback = Rc<dyn Fn() -> LoadFuture>;
+
+/// A widget that wraps its child in a vertical `ScrollableWidget`, and
+/// refreshes the content when the user pulls the content down beyond its top.
+///
+/// A progress indicator follows the pull, and the `refresher` callback is
+/// called when the content is released after being pulled over the
+/// `trigger_distance`. The indicator keeps spinning until the future of the
+/// callback resolves, the future runs by `AppCtx::spawn_local`.
+///
+/// The inner `ScrollableWidget` uses the [`BouncingScrollPhysics`] so the
+/// content can be pulled beyond its top, and it's provided to the descendants
+/// like the [`Scrollbar`]. Use [`RefreshIndicator::refresh`] to refresh
+/// without the pull, for example, by a refresh button on desktop.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let items = Stateful::new(vec![1, 2, 3]);
+///   @RefreshIndicator {
+///     refresher: move || {
+///       let items = $writer(items);
+///       async move {
+///         // fetch the latest items from the server.
+///         items.write().insert(0, 0);
+///       }
+///     },
+///     @ {
+///       pipe!($read(items).clone()).map(|items| fn_widget! {
+///         @Column {
+///           @ { items.into_iter().map(|i| @Text { text: i.to_string() }) }
+///         }
+///       })
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct RefreshIndicator {
+  /// The distance the content should be pulled beyond its top to trigger a
+  /// refresh.
+  #[declare(default = 64.)]
+  trigger_distance: f32,
+  /// The callback to refresh the content.
+  #[declare(custom, default)]
+  refresher: Option<LoadCallback>,
+  #[declare(skip)]
+  pull: f32,
+  /// The pull is over the trigger distance and the content isn't released.
+  #[declare(skip)]
+  armed: bool,
+  #[declare(skip)]
+  refreshing: bool,
+}
+
+impl RefreshIndicatorDeclarer {
+  /// Set the callback to refresh the content, the refresh ends when its future
+  /// resolves.
+  pub fn with_refresher<F, Fut>(&mut self, f: F) -> &mut Self
+  where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+  {
+    let f: LoadCallback = Rc::new(move || Box::pin(f()));
+    self.refresher = Some(PipeValue::Value(Some(f)));
+    self
+  }
+}
+
+impl RefreshIndicator {
+  /// Return if the refresh is running.
+  pub fn is_refreshing(&self) -> bool { self.refreshing }
+
+  /// The distance the content is pulled beyond its top.
+  pub fn pull_distance(&self) -> f32 { self.pull }
+
+  /// The progress of the pull to trigger a refresh, between `0.` and `1.`.
+  pub fn pull_progress(&self) -> f32 {
+    if self.trigger_distance > 0. { (self.pull / self.trigger_distance).min(1.) } else { 1. }
+  }
+
+  /// Start a refresh if it's not running.
+  pub fn refresh(this: &impl StateWriter<Value = Self>) {
+    let mut w = this.write();
+    let Some(refresher) = w.refresher.clone() else { return };
+    if w.refreshing {
+      return;
+    }
+    w.refreshing = true;
+    drop(w);
+
+    let fut = refresher();
+    let this = this.clone_writer();
+    AppCtx::spawn_local(async move {
+      fut.await;
+      this.write().refreshing = false;
+    });
+  }
+
+  fn update_pull(this: &impl StateWriter<Value = Self>, pull: f32, dragging: bool) {
+    let (armed, refreshing) = {
+      let r = this.read();
+      (r.armed, r.refreshing)
+    };
+    if this.read().pull != pull {
+      this.write().pull = pull;
+    }
+    if refreshing {
+      return;
+    }
+    if dragging {
+      let over = pull >= this.read().trigger_distance;
+      if armed != over {
+        this.write().armed = over;
+      }
+    } else if armed {
+      this.write().armed = false;
+      Self::refresh(this);
+    }
+  }
+}
+
+impl<'c> ComposeChild<'c> for RefreshIndicator {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let scroll = Stateful::new(ScrollableWidget::default());
+      scroll
+        .write()
+        .set_physics(BouncingScrollPhysics::default());
+
+      // Only the user drag arms the refresh, and its release triggers it, so the
+      // content scrolled by the program never refreshes.
+      let u = watch!(($read(scroll).get_scroll_pos().y, $read(scroll).is_dragging()))
+        .subscribe(move |(y, dragging)| {
+          RefreshIndicator::update_pull(&this, (-y).max(0.), dragging)
+        });
+
+      let mut indicator = @SpinnerProgress {
+        value: pipe! {
+          let this = $read(this);
+          (!this.refreshing).then(|| this.pull_progress())
+        },
+      };
+      let indicator = @Class {
+        class: REFRESH_INDICATOR,
+        @(indicator) {
+          h_align: HAlign::Center,
+          visible: pipe!($read(this).pull > 0. || $read(this).refreshing),
+          anchor: pipe! {
+            let this = $read(this);
+            let gap = if this.refreshing { this.trigger_distance } else { this.pull };
+            let height = *$read(indicator.layout_height());
+            Anchor::top((gap - height) / 2.)
+          },
+        }
+      };
+
+      providers! {
+        providers: [Provider::value_of_writer(scroll.clone_writer(), None)],
+        @Stack {
+          fit: StackFit::Passthrough,
+          on_disposed: move |_| u.unsubscribe(),
+          @(scroll) { @ { child } }
+          @InParentLayout { @ { indicator } }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// A widget that loads the next page of the content when the closest
+/// `ScrollableWidget` is scrolled near its end.
+///
+/// The `loader` callback is called when the distance to the end of the
+/// scrollable content is less than the `threshold`, a loading footer is shown
+/// after the child until the future of the callback resolves. Set `has_more`
+/// to `false` when all the pages are loaded.
+///
+/// The `InfiniteScroll` should be the content of a `ScrollableWidget`, or be
+/// inside the content. It also loads the pages until the content fills the
+/// view, and loads again after a loading ends or `has_more` turns to `true` if
+/// the end is still near.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let items = Stateful::new(vec![]);
+///   @ScrollableWidget {
+///     @InfiniteScroll {
+///       threshold: 200.,
+///       has_more: pipe!($read(items).len() < 100),
+///       loader: move || {
+///         let items = $writer(items);
+///         async move {
+///           // fetch the next page from the server.
+///           let len = items.read().len();
+///           items.write().extend(len..len + 20);
+///         }
+///       },
+///       @ {
+///         pipe!($read(items).clone()).map(|items| fn_widget! {
+///           @Column {
+///             @ { items.into_iter().map(|i| @Text { text: i.to_string() }) }
+///           }
+///         })
+///       }
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct InfiniteScroll {
+  /// The distance to the end of the scrollable content that starts loading
+  /// the next page.
+  #[declare(default = 200.)]
+  threshold: f32,
+  /// Whether there are more pages to load.
+  #[declare(default = true)]
+  has_more: bool,
+  /// The callback to load the next page.
+  #[declare(custom, default)]
+  loader: Option<LoadCallback>,
+  #[declare(skip)]
+  loading: bool,
+}
+
+impl InfiniteScrollDeclarer {
+  /// Set the callback to load the next page, the loading ends when its future
+  /// resolves.
+  pub fn with_loader<F, Fut>(&mut self, f: F) -> &mut Self
+  where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+  {
+    let f: LoadCallback = Rc::new(move || Box::pin(f()));
+    self.loader = Some(PipeValue::Value(Some(f)));
+    self
+  }
+}
+
+impl InfiniteScroll {
+  /// Return if the next page is loading.
+  pub fn is_loading(&self) -> bool { self.loading }
+
+  /// Load the next page if it's not loading and there are more pages.
+  pub fn load_more(this: &impl StateWriter<Value = Self>) {
+    let mut w = this.write();
+    let Some(loader) = w.loader.clone() else { return };
+    if w.loading || !w.has_more {
+      return;
+    }
+    w.loading = true;
+    drop(w);
+
+    let fut = loader();
+    let this = this.clone_writer();
+    AppCtx::spawn_local(async move {
+      fut.await;
+      this.write().loading = false;
+    });
+  }
+}
+
+impl<'c> ComposeChild<'c> for InfiniteScroll {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let wnd = BuildCtx::get().window();
+      let u = ScrollableWidget::boxed_writer_of(BuildCtx::get()).map(|scroll| {
+        let (c_scroll, c_this) = (scroll.clone_writer(), this.clone_writer());
+        let check = Rc::new(move || {
+          let rest = {
+            let scroll = c_scroll.read();
+            let (pos, max) = (scroll.get_scroll_pos(), scroll.max_scrollable());
+            if scroll.scrollable == Scrollable::X { max.x - pos.x } else { max.y - pos.y }
+          };
+          if rest <= c_this.read().threshold {
+            InfiniteScroll::load_more(&c_this);
+          }
+        });
+
+        let c_check = check.clone();
+        let u_scroll = watch!(($read(scroll).get_scroll_pos(), $read(scroll).max_scrollable()))
+          .subscribe(move |_| c_check());
+        // The end may still be near after a page is loaded or when more pages are
+        // available, check again after the layout updates the scroll metrics.
+        let u_state = watch!(($read(this).has_more, $read(this).loading))
+          .distinct_until_changed()
+          .filter(|(has_more, loading)| *has_more && !*loading)
+          .subscribe(move |_| {
+            let check = check.clone();
+            wnd.once_layout_ready(move || check());
+          });
+        (u_scroll, u_state)
+      });
+
+      let footer = pipe!($read(this).loading).map(move |loading| {
+        loading.then(|| fn_widget! {
+          @Class {
+            class: LOAD_MORE_FOOTER,
+            @SpinnerProgress { value: None }
+          }
+        })
+      });
+
+      @Column {
+        on_disposed: move |_| {
+          if let Some((u_scroll, u_state)) = u {
+            u_scroll.unsubscribe();
+            u_state.unsubscribe();
+          }
+        },
+        @ { child }
+        @ { footer }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    task::{Poll, Waker},
+  };
+
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  /// A gate that blocks the loading futures until it's opened.
+  #[derive(Clone, Default)]
+  struct Gate(Rc<(Cell<bool>, RefCell<Vec<Waker>>)>);
+
+  impl Gate {
+    fn wait(&self) -> impl Future<Output = ()> {
+      let gate = self.clone();
+      poll_fn(move |cx| {
+        if gate.0.0.get() {
+          Poll::Ready(())
+        } else {
+          gate.0.1.borrow_mut().push(cx.waker().clone());
+          Poll::Pending
+        }
+      })
+    }
+
+    fn open(&self) {
+      self.0.0.set(true);
+      self
+        .0
+        .1
+        .borrow_mut()
+        .drain(..)
+        .for_each(Waker::wake);
+      AppCtx::run_until_stalled();
+    }
+
+    fn close(&self) { self.0.0.set(false); }
+  }
+
+  #[test]
+  fn pull_to_refresh() {
+    reset_test_env!();
+
+    let gate = Gate::default();
+    let refreshed = Rc::new(Cell::new(0));
+    let indicator = Stateful::new(RefreshIndicator {
+      trigger_distance: 50.,
+      refresher: Some({
+        let (gate, refreshed) = (gate.clone(), refreshed.clone());
+        Rc::new(move || {
+          refreshed.set(refreshed.get() + 1);
+          Box::pin(gate.wait())
+        })
+      }),
+      pull: 0.,
+      armed: false,
+      refreshing: false,
+    });
+    let scroll: Rc<RefCell<Option<ScrollableProvider>>> = Rc::default();
+    let (c_indicator, c_scroll) = (indicator.clone_writer(), scroll.clone());
+    let w = fn_widget! {
+      @(c_indicator.clone_writer()) {
+        @ {
+          let c_scroll = c_scroll.clone();
+          fn_widget! {
+            *c_scroll.borrow_mut() = ScrollableWidget::boxed_writer_of(BuildCtx::get());
+            @MockBox { size: Size::new(100., 500.) }
+          }
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    let scroll = scroll.borrow_mut().take().unwrap();
+
+    // The pull is not enough to refresh.
+    scroll.write().drag_by(Vector::new(0., -40.));
+    wnd.draw_frame();
+    assert_eq!(indicator.read().pull_distance(), 20.);
+    scroll.write().release();
+    wnd.draw_frame();
+    assert!(!indicator.read().is_refreshing());
+
+    scroll.write().drag_by(Vector::new(0., -200.));
+    wnd.draw_frame();
+    assert!(indicator.read().pull_progress() >= 1.);
+    assert!(!indicator.read().is_refreshing());
+    scroll.write().release();
+    wnd.draw_frame();
+    assert!(indicator.read().is_refreshing());
+    assert_eq!(refreshed.get(), 1);
+
+    gate.open();
+    assert!(!indicator.read().is_refreshing());
+    assert_eq!(refreshed.get(), 1);
+
+    // The content scrolled by the program isn't a release.
+    scroll.write().drag_by(Vector::new(0., -200.));
+    wnd.draw_frame();
+    scroll.write().scroll_to(Point::zero());
+    wnd.draw_frame();
+    assert!(scroll.read().is_moving());
+    assert!(!indicator.read().is_refreshing());
+    assert_eq!(refreshed.get(), 1);
+  }
+
+  #[test]
+  fn load_more_at_end() {
+    reset_test_env!();
+
+    let gate = Gate::default();
+    let pages = Stateful::new(1);
+    let c_pages = pages.clone_writer();
+    let c_gate = gate.clone();
+    let infinite = Stateful::new(InfiniteScroll {
+      threshold: 50.,
+      has_more: true,
+      loader: Some(Rc::new(move || {
+        let (pages, gate) = (c_pages.clone_writer(), c_gate.clone());
+        Box::pin(async move {
+          gate.wait().await;
+          *pages.write() += 1;
+        })
+      })),
+      loading: false,
+    });
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let (c_infinite, c_pages, c_scroll) =
+      (infinite.clone_writer(), pages.clone_writer(), scroll.clone_writer());
+    let w = fn_widget! {
+      @(c_scroll.clone_writer()) {
+        @(c_infinite.clone_writer()) {
+          @MockBox { size: pipe!(Size::new(100., *$read(c_pages) as f32 * 100.)) }
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    // The content reaches its end without scrolling.
+    assert!(infinite.read().is_loading());
+
+    gate.open();
+    wnd.draw_frame();
+    assert_eq!(*pages.read(), 2);
+    assert!(!infinite.read().is_loading());
+
+    // Far from the end.
+    scroll.write().jump_to(Point::new(0., 40.));
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert_eq!(*pages.read(), 2);
+
+    scroll.write().jump_to(Point::new(0., 100.));
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert_eq!(*pages.read(), 3);
+  }
+
+  #[test]
+  fn load_again_without_new_content() {
+    reset_test_env!();
+
+    let gate = Gate::default();
+    let loaded = Rc::new(Cell::new(0));
+    let c_gate = gate.clone();
+    let c_loaded = loaded.clone();
+    let infinite = Stateful::new(InfiniteScroll {
+      threshold: 50.,
+      has_more: true,
+      loader: Some(Rc::new(move || {
+        let (loaded, gate) = (c_loaded.clone(), c_gate.clone());
+        Box::pin(async move {
+          gate.wait().await;
+          gate.close();
+          loaded.set(loaded.get() + 1);
+        })
+      })),
+      loading: false,
+    });
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let (c_infinite, c_scroll) = (infinite.clone_writer(), scroll.clone_writer());
+    let w = fn_widget! {
+      @(c_scroll.clone_writer()) {
+        @(c_infinite.clone_writer()) {
+          @MockBox { size: Size::new(100., 100.) }
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    assert!(infinite.read().is_loading());
+
+    // The page added no content, but the end is still near.
+    infinite.write().has_more = false;
+    gate.open();
+    wnd.draw_frame();
+    assert_eq!(loaded.get(), 1);
+    assert!(!infinite.read().is_loading());
+
+    infinite.write().has_more = true;
+    wnd.draw_frame();
+    assert!(infinite.read().is_loading());
+
+    gate.open();
+    wnd.draw_frame();
+    assert_eq!(loaded.get(), 2);
+    // Loads again after the loading ends, because the end is still near.
+    assert!(infinite.read().is_loading());
+
+    infinite.write().has_more = false;
+    gate.open();
+    assert_eq!(loaded.get(), 3);
+  }
+}
@@ -0,0 +1,311 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class name for the field of the select widget
+  SELECT,
+  /// Class name for the text of the selected options in the field
+  SELECT_LABEL,
+  /// Class name for the placeholder text when no option is selected
+  SELECT_PLACEHOLDER,
+  /// Class name for the dropdown indicator of the field
+  SELECT_INDICATOR,
+  /// Class name for the check icon of an option in the popup
+  SELECT_OPTION_CHECK,
+}
+
+/// A form control that lets the user choose one or more options from a
+/// dropdown popup.
+///
+/// The popup is a [`Menu`] anchored below the field, so it is styled by the
+/// `MENU` classes of the theme, and the field itself is styled by the
+/// `SELECT` classes. Tap the field or press `ArrowDown`, `Enter` or `Space`
+/// when it is focused to open the popup.
+///
+/// In single mode, choosing an option replaces the selection and closes the
+/// popup. In multi mode, choosing an option toggles it and the popup stays
+/// open.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let select = @Select {
+///     options: vec!["Apple".into(), "Banana".into(), "Cherry".into()],
+///     placeholder: "Pick a fruit",
+///   };
+///   watch!($read(select).selected().to_vec())
+///     .subscribe(|selected| println!("selected: {selected:?}"));
+///   select
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct Select {
+  /// The labels of the options that can be chosen.
+  #[declare(default)]
+  options: Vec<CowArc<str>>,
+  /// Whether more than one option can be selected at the same time.
+  #[declare(default)]
+  multi: bool,
+  /// The indices of the selected options.
+  #[declare(default)]
+  selected: Vec<usize>,
+  /// The text displayed in the field when no option is selected.
+  #[declare(default)]
+  placeholder: CowArc<str>,
+}
+
+impl Select {
+  /// Return the labels of the options.
+  pub fn options(&self) -> &[CowArc<str>] { &self.options }
+
+  /// Replace the options, the selection will be cleared.
+  pub fn set_options(&mut self, options: Vec<CowArc<str>>) {
+    self.options = options;
+    self.selected.clear();
+  }
+
+  /// Return the indices of the selected options, in the order they were
+  /// selected.
+  pub fn selected(&self) -> &[usize] { &self.selected }
+
+  /// Return the labels of the selected options.
+  pub fn selected_labels(&self) -> impl Iterator<Item = &CowArc<str>> {
+    self
+      .selected
+      .iter()
+      .filter_map(|idx| self.options.get(*idx))
+  }
+
+  /// Return true if the option at `idx` is selected.
+  pub fn is_selected(&self, idx: usize) -> bool { self.selected.contains(&idx) }
+
+  /// Return true if the select allows multiple options to be selected.
+  pub fn is_multi(&self) -> bool { self.multi }
+
+  /// Choose the option at `idx` as the user does it in the popup. In single
+  /// mode the option becomes the only selected one, in multi mode the option
+  /// is toggled.
+  pub fn choose(&mut self, idx: usize) {
+    if idx >= self.options.len() {
+      return;
+    }
+    if !self.multi {
+      self.selected.clear();
+      self.selected.push(idx);
+    } else if let Some(pos) = self.selected.iter().position(|i| *i == idx) {
+      self.selected.remove(pos);
+    } else {
+      self.selected.push(idx);
+    }
+  }
+
+  /// Deselect all the options.
+  pub fn clear(&mut self) { self.selected.clear(); }
+
+  fn display_text(&self) -> CowArc<str> {
+    if self.selected.is_empty() {
+      self.placeholder.clone()
+    } else {
+      let labels = self
+        .selected_labels()
+        .map(|l| l.as_ref())
+        .collect::<Vec<_>>();
+      labels.join(", ").into()
+    }
+  }
+
+  fn option_menu(this: impl StateWriter<Value = Self>) -> MenuControl {
+    MenuControl::new(fn_widget! {
+      let options = $read(this).options.clone();
+      @Menu {
+        on_tap: move |e| {
+          let menu = Provider::of::<MenuControl>(e).unwrap();
+          if let Some(idx) = menu.selected() {
+            Select::choose_in_menu(&$writer(this), idx, &menu, &e.window());
+          }
+        },
+        on_key_down: move |e| {
+          let menu = Provider::of::<MenuControl>(e).unwrap();
+          if matches!(e.key(), VirtualKey::Named(NamedKey::Enter | NamedKey::Space)) {
+            if let Some(idx) = menu.selected() {
+              Select::choose_in_menu(&$writer(this), idx, &menu, &e.window());
+            }
+          }
+        },
+        @ {
+          options.into_iter().enumerate().map(move |(idx, label)| {
+            let check = @Icon {
+              class: SELECT_OPTION_CHECK,
+              opacity: distinct_pipe!(if $read(this).is_selected(idx) { 1. } else { 0. }),
+              @ { svgs::DONE }
+            };
+            @MenuItem {
+              @Leading::new(check.into_widget())
+              @ { label }
+            }
+          })
+        }
+      }
+    })
+  }
+
+  fn choose_in_menu(
+    this: &impl StateWriter<Value = Self>, idx: usize, menu: &MenuControl, wnd: &Sc<Window>,
+  ) {
+    this.write().choose(idx);
+    if !this.read().multi {
+      menu.close(wnd);
+    }
+  }
+}
+
+fn show_popup(menu: &MenuControl, field: WidgetId, wnd: &Sc<Window>) {
+  if menu.is_show() {
+    return;
+  }
+  let pos = wnd.map_to_global(Point::zero(), field);
+  let size = wnd.widget_size(field).unwrap_or_default();
+  menu.show_below(Rect::new(pos, size), wnd);
+}
+
+impl Compose for Select {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let menu = Select::option_menu(this.clone_writer());
+      @Flex {
+        class: SELECT,
+        tab_index: 0_i16,
+        align_items: Align::Center,
+        on_tap: {
+          let menu = menu.clone();
+          move |e| show_popup(&menu, e.current_target(), &e.window())
+        },
+        on_key_down: {
+          let menu = menu.clone();
+          move |e| {
+            if matches!(
+              e.key(),
+              VirtualKey::Named(NamedKey::ArrowDown | NamedKey::Enter | NamedKey::Space)
+            ) {
+              show_popup(&menu, e.current_target(), &e.window());
+            }
+          }
+        },
+        on_disposed: move |e| if menu.is_show() {
+          menu.close(&e.window());
+        },
+        @Expanded {
+          flex: 1.,
+          @Text {
+            text: pipe!($read(this).display_text()),
+            class: distinct_pipe!(
+              if $read(this).selected.is_empty() { SELECT_PLACEHOLDER } else { SELECT_LABEL }
+            ),
+          }
+        }
+        @Icon {
+          class: SELECT_INDICATOR,
+          @ { svgs::ARROW_DROP_DOWN }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  #[test]
+  fn single_choose() {
+    reset_test_env!();
+    let mut select = Select {
+      options: vec!["a".into(), "b".into(), "c".into()],
+      multi: false,
+      selected: vec![],
+      placeholder: "".into(),
+    };
+    select.choose(1);
+    select.choose(2);
+    assert_eq!(select.selected(), &[2]);
+    select.choose(5);
+    assert_eq!(select.selected(), &[2]);
+    assert_eq!(&*select.display_text(), "c");
+  }
+
+  #[test]
+  fn multi_choose() {
+    reset_test_env!();
+    let mut select = Select {
+      options: vec!["a".into(), "b".into(), "c".into()],
+      multi: true,
+      selected: vec![],
+      placeholder: "".into(),
+    };
+    select.choose(2);
+    select.choose(0);
+    assert_eq!(&*select.display_text(), "c, a");
+    select.choose(2);
+    assert_eq!(select.selected(), &[0]);
+  }
+
+  #[test]
+  fn tap_to_open_and_choose() {
+    reset_test_env!();
+    let (selected, w_selected) = split_value(vec![]);
+    let w = fn_widget! {
+      let select = @Select {
+        options: vec!["Item 1".into(), "Item 2".into()],
+        placeholder: "choose",
+      };
+      watch!($read(select).selected().to_vec())
+        .subscribe(move |v| *$write(w_selected) = v);
+      @SizedBox {
+        size: Size::new(100., 24.),
+        @ { select }
+      }
+    };
+
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    wnd.process_cursor_move(Point::new(10., 10.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+
+    // the popup takes the focus, select the second option by keyboard.
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(KeyCode::ArrowDown),
+      VirtualKey::Named(NamedKey::ArrowDown),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(KeyCode::ArrowDown),
+      VirtualKey::Named(NamedKey::ArrowDown),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(KeyCode::Enter),
+      VirtualKey::Named(NamedKey::Enter),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+    assert_eq!(*selected.read(), vec![1]);
+  }
+}
@@ -0,0 +1,485 @@
+use std::ops::Range;
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+/// A widget that makes the text of all the [`Text`] descendants selectable as
+/// a whole, like a chat transcript or an article.
+///
+/// Drag with the pointer to select across multiple texts, extend the selection
+/// with `Shift` and the arrow keys, and copy it with `Ctrl+C`/`Cmd+C`. The
+/// texts are read top to bottom, then left to right, and the copied text of
+/// different `Text` widgets is separated by a line break. The highlight of the
+/// selection uses the [`TEXT_SELECTION`] class.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @SelectionArea {
+///     @Column {
+///       @Text { text: "Hello" }
+///       @Text { text: "world" }
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare, Default)]
+pub struct SelectionArea {
+  #[declare(skip)]
+  texts: TextCollector,
+  #[declare(skip)]
+  selection: Option<AreaSelection>,
+  #[declare(skip)]
+  highlights: Vec<Rect>,
+}
+
+/// A caret in one of the texts of a [`SelectionArea`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextCaret {
+  /// The `Text` widget that the caret is in.
+  pub text: WidgetId,
+  /// The byte index of the cluster in the text.
+  pub cluster: usize,
+}
+
+/// The selection of a [`SelectionArea`], from the anchor caret to the active
+/// caret that moves when extending.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AreaSelection {
+  pub from: TextCaret,
+  pub to: TextCaret,
+}
+
+impl SelectionArea {
+  pub fn selection(&self) -> Option<AreaSelection> { self.selection }
+
+  /// The selected text, the texts of different widgets are separated by a line
+  /// break.
+  pub fn selected_text(&self, wnd: &Window) -> String {
+    let texts = self.ordered_texts(wnd);
+    self
+      .selected_ranges(&texts)
+      .into_iter()
+      .map(|(idx, rg)| texts[idx].text.substr(rg).to_string())
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  /// The texts laid out in the area, in the reading order: top to bottom, and
+  /// the texts in the same row from left to right.
+  fn ordered_texts(&self, wnd: &Window) -> Vec<CollectedText> {
+    self.texts.retain(|t| wnd.is_valid_widget(t.id));
+    let global_rect = |t: &CollectedText| {
+      let pos = wnd.map_to_global(Point::zero(), t.id);
+      Rect::new(pos, wnd.widget_size(t.id).unwrap_or_default())
+    };
+    let mut texts: Vec<_> = self
+      .texts
+      .texts()
+      .iter()
+      .map(|t| (global_rect(t), t.clone()))
+      .collect();
+    texts.sort_by(|(a, _), (b, _)| a.min_y().total_cmp(&b.min_y()));
+
+    let mut ordered = Vec::with_capacity(texts.len());
+    let mut row: Vec<(Rect, CollectedText)> = vec![];
+    let mut row_bottom = f32::MIN;
+    for (rect, text) in texts {
+      if rect.min_y() >= row_bottom {
+        row.sort_by(|(a, _), (b, _)| a.min_x().total_cmp(&b.min_x()));
+        ordered.extend(row.drain(..).map(|(_, t)| t));
+        row_bottom = rect.max_y();
+      } else {
+        row_bottom = row_bottom.max(rect.max_y());
+      }
+      row.push((rect, text));
+    }
+    row.sort_by(|(a, _), (b, _)| a.min_x().total_cmp(&b.min_x()));
+    ordered.extend(row.into_iter().map(|(_, t)| t));
+    ordered
+  }
+
+  /// The caret of the text nearest to the global position.
+  fn caret_at(texts: &[CollectedText], pos: Point, wnd: &Window) -> Option<TextCaret> {
+    let mut nearest = (f32::MAX, None);
+    for t in texts {
+      let local = wnd.map_from_global(pos, t.id);
+      let rect = Rect::from_size(wnd.widget_size(t.id).unwrap_or_default());
+      let dx = (rect.min_x() - local.x)
+        .max(local.x - rect.max_x())
+        .max(0.);
+      let dy = (rect.min_y() - local.y)
+        .max(local.y - rect.max_y())
+        .max(0.);
+      let distance = dx * dx + dy * dy;
+      if distance < nearest.0 {
+        let cluster = t.glyphs.caret_position_from_pos(local).cluster;
+        nearest = (distance, Some(TextCaret { text: t.id, cluster }));
+      }
+    }
+    nearest.1
+  }
+
+  /// The selected cluster range of every text, the text is the index of
+  /// `texts`.
+  fn selected_ranges(&self, texts: &[CollectedText]) -> Vec<(usize, Range<usize>)> {
+    let Some(sel) = self.selection else { return vec![] };
+    let index = |caret: TextCaret| {
+      let idx = texts.iter().position(|t| t.id == caret.text)?;
+      Some((idx, caret.cluster.min(texts[idx].text.len())))
+    };
+    let (Some(from), Some(to)) = (index(sel.from), index(sel.to)) else { return vec![] };
+    let (start, end) = if from <= to { (from, to) } else { (to, from) };
+
+    (start.0..=end.0)
+      .map(|idx| {
+        let begin = if idx == start.0 { start.1 } else { 0 };
+        let end = if idx == end.0 { end.1 } else { texts[idx].text.len() };
+        (idx, begin..end)
+      })
+      .collect()
+  }
+
+  /// The rectangles of the selected glyphs, in the coordinate of `area`.
+  fn highlight_rects(&self, wnd: &Window, area: WidgetId) -> Vec<Rect> {
+    let texts = self.ordered_texts(wnd);
+    self
+      .selected_ranges(&texts)
+      .into_iter()
+      .flat_map(|(idx, rg)| {
+        let t = &texts[idx];
+        t.glyphs
+          .select_range(&rg)
+          .into_iter()
+          .map(move |rc| {
+            let origin = wnd.map_from_global(wnd.map_to_global(rc.origin, t.id), area);
+            Rect::new(origin, rc.size)
+          })
+      })
+      .collect()
+  }
+
+  fn set_selection(&mut self, selection: Option<AreaSelection>, wnd: &Window, area: WidgetId) {
+    self.selection = selection;
+    self.highlights = self.highlight_rects(wnd, area);
+  }
+
+  fn select_with_key(&self, event: &KeyboardEvent) -> Option<AreaSelection> {
+    let texts = self.ordered_texts(&event.window());
+    if event.with_command_key() && event.key_code() == &PhysicalKey::Code(KeyCode::KeyA) {
+      let (first, last) = (texts.first()?, texts.last()?);
+      return Some(AreaSelection {
+        from: TextCaret { text: first.id, cluster: 0 },
+        to: TextCaret { text: last.id, cluster: last.text.len() },
+      });
+    }
+
+    let sel = self.selection?;
+    let idx = texts.iter().position(|t| t.id == sel.to.text)?;
+    let glyphs = &texts[idx].glyphs;
+    let len = texts[idx].text.len();
+    let caret = CaretPosition { cluster: sel.to.cluster, position: None };
+    let row = glyphs.caret_position(caret).0;
+    let in_text = |caret: CaretPosition| TextCaret { text: sel.to.text, cluster: caret.cluster };
+    let prev_end = || {
+      let prev = texts.get(idx.checked_sub(1)?)?;
+      Some(TextCaret { text: prev.id, cluster: prev.text.len() })
+    };
+    let next_begin = || {
+      texts
+        .get(idx + 1)
+        .map(|t| TextCaret { text: t.id, cluster: 0 })
+    };
+
+    let new_caret = match event.key() {
+      VirtualKey::Named(NamedKey::ArrowLeft) if caret.cluster == 0 => prev_end()?,
+      VirtualKey::Named(NamedKey::ArrowLeft) => in_text(glyphs.prev(caret)),
+      VirtualKey::Named(NamedKey::ArrowRight) if caret.cluster >= len => next_begin()?,
+      VirtualKey::Named(NamedKey::ArrowRight) => in_text(glyphs.next(caret)),
+      VirtualKey::Named(NamedKey::ArrowUp) if row == 0 => prev_end()?,
+      VirtualKey::Named(NamedKey::ArrowUp) => in_text(glyphs.up(caret)),
+      VirtualKey::Named(NamedKey::ArrowDown) if row + 1 >= glyphs.glyph_row_count() => {
+        next_begin()?
+      }
+      VirtualKey::Named(NamedKey::ArrowDown) => in_text(glyphs.down(caret)),
+      VirtualKey::Named(NamedKey::Home) => in_text(glyphs.line_begin(caret)),
+      VirtualKey::Named(NamedKey::End) => in_text(glyphs.line_end(caret)),
+      _ => return None,
+    };
+
+    let from = if event.with_shift_key() { sel.from } else { new_caret };
+    Some(AreaSelection { from, to: new_caret })
+  }
+
+  fn copy(&self, wnd: &Window) {
+    let text = self.selected_text(wnd);
+    if !text.is_empty() {
+      let clipboard = AppCtx::clipboard();
+      let _ = clipboard.borrow_mut().clear();
+      let _ = clipboard.borrow_mut().write_text(&text);
+    }
+  }
+}
+
+impl<'c> ComposeChild<'c> for SelectionArea {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let collector = $read(this).texts.clone();
+      @PointerSelectRegion {
+        tab_index: 0_i16,
+        on_custom_concrete_event: move |e: &mut PointerSelectEvent| {
+          if let PointerSelectData::Move { from, to } | PointerSelectData::End { from, to } =
+            e.data()
+          {
+            let (wnd, area) = (e.window(), e.current_target());
+            let texts = $read(this).ordered_texts(&wnd);
+            let from = SelectionArea::caret_at(&texts, wnd.map_to_global(*from, area), &wnd);
+            let to = SelectionArea::caret_at(&texts, wnd.map_to_global(*to, area), &wnd);
+            if let (Some(from), Some(to)) = (from, to) {
+              $write(this).set_selection(Some(AreaSelection { from, to }), &wnd, area);
+            }
+          }
+        },
+        on_pointer_down: move |e| {
+          let (wnd, area) = (e.window(), e.current_target());
+          let texts = $read(this).ordered_texts(&wnd);
+          let caret = SelectionArea::caret_at(&texts, e.global_pos(), &wnd);
+          let old = $read(this).selection;
+          let selection = match (caret, old) {
+            (Some(to), Some(old)) if e.with_shift_key() => {
+              Some(AreaSelection { from: old.from, to })
+            }
+            (caret, _) => caret.map(|c| AreaSelection { from: c, to: c }),
+          };
+          $write(this).set_selection(selection, &wnd, area);
+        },
+        on_double_tap: move |e| {
+          let (wnd, area) = (e.window(), e.current_target());
+          let texts = $read(this).ordered_texts(&wnd);
+          let Some(caret) = SelectionArea::caret_at(&texts, e.global_pos(), &wnd) else {
+            return;
+          };
+          let Some(t) = texts.iter().find(|t| t.id == caret.text) else { return };
+          let rg = t.text.select_token(caret.cluster);
+          let selection = AreaSelection {
+            from: TextCaret { text: t.id, cluster: rg.start },
+            to: TextCaret { text: t.id, cluster: rg.end },
+          };
+          $write(this).set_selection(Some(selection), &wnd, area);
+        },
+        on_key_down: move |e| {
+          let wnd = e.window();
+          if e.with_command_key() && e.key_code() == &PhysicalKey::Code(KeyCode::KeyC) {
+            $read(this).copy(&wnd);
+            return;
+          }
+          let selection = $read(this).select_with_key(e);
+          if selection.is_some() {
+            $write(this).set_selection(selection, &wnd, e.current_target());
+            e.stop_propagation();
+          }
+        },
+        on_performed_layout: move |e| {
+          let rects = $read(this).highlight_rects(&e.window(), e.current_target());
+          if $read(this).highlights != rects {
+            $write(this).highlights = rects;
+          }
+        },
+        @Stack {
+          @NoAffectedParentSize {
+            @Stack {
+              @pipe! {
+                $read(this).highlights.clone().into_iter().map(move |rc| {
+                  @Container {
+                    class: TEXT_SELECTION,
+                    anchor: Anchor::from_point(rc.origin),
+                    size: rc.size,
+                  }
+                })
+              }
+            }
+          }
+          @Providers {
+            providers: [Provider::new(collector)],
+            @ { child }
+          }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{borrow::Cow, cell::RefCell, io::Error, rc::Rc};
+
+  use ribir_core::{clipboard::Clipboard, reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  fn area_window(area: &Stateful<SelectionArea>) -> TestWindow {
+    let area = area.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @(area.clone_writer()) {
+          @SizedBox {
+            size: Size::new(200., 200.),
+            @Column {
+              @Text { text: "Hello" }
+              @Text { text: "world" }
+            }
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    // Place the caret at the start of the first text and focus the area.
+    wnd.process_cursor_move(Point::new(0., 2.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    wnd
+  }
+
+  fn press(wnd: &TestWindow, code: KeyCode, key: VirtualKey) {
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(code),
+      key,
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  fn command_key() -> ModifiersState {
+    if cfg!(target_os = "macos") { ModifiersState::SUPER } else { ModifiersState::CONTROL }
+  }
+
+  #[test]
+  fn select_across_texts() {
+    reset_test_env!();
+    let area = Stateful::new(SelectionArea::default());
+    let c_area = area.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @(c_area.clone_writer()) {
+          @SizedBox {
+            size: Size::new(200., 200.),
+            @Column {
+              @Text { text: "Hello" }
+              @Text { text: "world\nagain" }
+            }
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    wnd.process_cursor_move(Point::new(0., 2.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    wnd.process_cursor_move(Point::new(199., 199.));
+    wnd.draw_frame();
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+
+    assert_eq!(area.read().selected_text(&wnd), "Hello\nworld\nagain");
+    assert_eq!(area.read().highlights.len(), 3);
+
+    wnd.process_cursor_move(Point::new(0., 2.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    assert_eq!(area.read().selected_text(&wnd), "");
+  }
+
+  #[test]
+  fn shift_arrow_across_texts() {
+    reset_test_env!();
+    let area = Stateful::new(SelectionArea::default());
+    let wnd = area_window(&area);
+
+    wnd.process_modifiers_changed(ModifiersState::SHIFT);
+    press(&wnd, KeyCode::ArrowDown, VirtualKey::Named(NamedKey::ArrowDown));
+    assert_eq!(area.read().selected_text(&wnd), "Hello\n");
+    press(&wnd, KeyCode::ArrowRight, VirtualKey::Named(NamedKey::ArrowRight));
+    press(&wnd, KeyCode::ArrowRight, VirtualKey::Named(NamedKey::ArrowRight));
+    assert_eq!(area.read().selected_text(&wnd), "Hello\nwo");
+
+    // Move back across the boundary to the end of the first text.
+    for _ in 0..3 {
+      press(&wnd, KeyCode::ArrowLeft, VirtualKey::Named(NamedKey::ArrowLeft));
+    }
+    assert_eq!(area.read().selected_text(&wnd), "Hello");
+  }
+
+  #[test]
+  fn select_all_texts() {
+    reset_test_env!();
+    let area = Stateful::new(SelectionArea::default());
+    let wnd = area_window(&area);
+
+    wnd.process_modifiers_changed(command_key());
+    press(&wnd, KeyCode::KeyA, VirtualKey::Character("a".into()));
+    assert_eq!(area.read().selected_text(&wnd), "Hello\nworld");
+  }
+
+  #[derive(Default, Clone)]
+  struct TextClipboard(Rc<RefCell<String>>);
+
+  impl Clipboard for TextClipboard {
+    fn read_text(&mut self) -> Result<String, Error> { Ok(self.0.borrow().clone()) }
+
+    fn write_text(&mut self, text: &str) -> Result<(), Error> {
+      *self.0.borrow_mut() = text.to_string();
+      Ok(())
+    }
+
+    fn read_img(&mut self) -> Result<PixelImage, Error> {
+      Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn write_img(&mut self, _: &PixelImage) -> Result<(), Error> {
+      Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn read(&mut self, _: &str) -> Result<Cow<'_, [u8]>, Error> {
+      Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn write(&mut self, _: &str, _: &[u8]) -> Result<(), Error> {
+      Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn clear(&mut self) -> Result<(), Error> {
+      self.0.borrow_mut().clear();
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn copy_joins_texts_by_line_break() {
+    reset_test_env!();
+    let clipboard = TextClipboard::default();
+    AppCtx::set_clipboard(Box::new(clipboard.clone()));
+    let area = Stateful::new(SelectionArea::default());
+    let wnd = area_window(&area);
+
+    wnd.process_modifiers_changed(command_key());
+    press(&wnd, KeyCode::KeyA, VirtualKey::Character("a".into()));
+    press(&wnd, KeyCode::KeyC, VirtualKey::Character("c".into()));
+    assert_eq!(*clipboard.0.borrow(), "Hello\nworld");
+  }
+}
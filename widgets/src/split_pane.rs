@@ -0,0 +1,635 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Root container class for the split pane
+  SPLIT_PANE,
+  /// Class for every pane of the split pane
+  SPLIT_PANE_PANE,
+  /// Class for the dividers of a horizontal split pane, they are vertical bars
+  SPLIT_PANE_H_DIVIDER,
+  /// Class for the dividers of a vertical split pane, they are horizontal bars
+  SPLIT_PANE_V_DIVIDER,
+}
+
+/// A pane of the [`SplitPane`] with its size limits in pixels along the split
+/// direction.
+#[derive(Template)]
+pub struct Pane<'w> {
+  /// The minimum size of the pane, the pane can only be smaller when it's
+  /// collapsed.
+  #[template(field = 0f32)]
+  min: f32,
+  /// The maximum size of the pane.
+  #[template(field = f32::INFINITY)]
+  max: f32,
+  child: Widget<'w>,
+}
+
+/// A container that splits its space into panes by the draggable dividers
+/// between them, for IDE-style layouts.
+///
+/// The space of the panes is shared by their ratios, and the size of every
+/// pane is kept within its `min` and `max` when the split pane is laid out, so
+/// the limits hold for the initial ratios and when the split pane is resized.
+/// A pane that hits its limit gives the rest of the space to the other panes.
+///
+/// The dividers also respect the limits when the user:
+/// - drags a divider.
+/// - presses the arrow keys along the split direction when a divider is
+///   focused, `Home` and `End` move the divider as far as possible.
+///
+/// Double tapping a divider, or pressing `Enter` when it's focused, collapses
+/// the pane before it to zero, and restores it the next time.
+///
+/// The ratios can be read by [`SplitPane::ratios`] to persist, and restored
+/// by the `ratios` field when the split pane is created.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let mut split = @SplitPane { ratios: vec![0.2, 0.8] };
+///   watch!($read(split).ratios().to_vec()).subscribe(|ratios| println!("{ratios:?}"));
+///   @(split) {
+///     @Pane {
+///       min: 120.,
+///       @Text { text: "Explorer" }
+///     }
+///     @Pane {
+///       min: 200.,
+///       @Text { text: "Editor" }
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct SplitPane {
+  /// The direction the panes are placed in.
+  #[declare(default)]
+  direction: Direction,
+  /// The ratios of the panes to share the space, the panes share the space
+  /// evenly if the count doesn't match the panes.
+  #[declare(default)]
+  ratios: Vec<f32>,
+  /// The pixels a divider moves by an arrow key.
+  #[declare(default = 16.)]
+  keyboard_step: f32,
+  #[declare(skip)]
+  limits: Vec<(f32, f32)>,
+  /// The ratios of the collapsed panes before they collapsed.
+  #[declare(skip)]
+  collapsed: Vec<Option<f32>>,
+  /// The pixels of the space shared by the panes.
+  #[declare(skip)]
+  space: f32,
+  #[declare(skip)]
+  divider_extent: f32,
+}
+
+impl SplitPane {
+  pub fn direction(&self) -> Direction { self.direction }
+
+  /// Return the ratios of the panes, their sum is 1.
+  pub fn ratios(&self) -> &[f32] { &self.ratios }
+
+  /// Set the ratios of the panes, the ratios are normalized to sum to 1, and
+  /// the panes share the space evenly if the count doesn't match the panes.
+  /// The collapsed panes are expanded by their new ratios.
+  pub fn set_ratios(&mut self, ratios: Vec<f32>) {
+    self.ratios = ratios;
+    // The count is checked when the panes are composed.
+    if self.limits.is_empty() {
+      self.normalize();
+    } else {
+      self.fit_panes();
+    }
+  }
+
+  /// Return the sizes of the panes in pixels, they are the ratios of the space
+  /// kept within the limits of the panes.
+  pub fn sizes(&self) -> Vec<f32> { resolve_sizes(&self.pane_specs(), self.space) }
+
+  /// Return true if the pane is collapsed.
+  pub fn is_collapsed(&self, pane: usize) -> bool { self.collapsed[pane].is_some() }
+
+  /// Move the divider by `delta` pixels, the divider `idx` is between the
+  /// pane `idx` and `idx + 1`. Return the pixels the divider actually moved.
+  pub fn move_divider(&mut self, idx: usize, delta: f32) -> f32 {
+    if self.space <= 0. {
+      return 0.;
+    }
+    let mut sizes = self.sizes();
+    let (before, after) = (sizes[idx], sizes[idx + 1]);
+    let (min_before, max_before) = self.limits[idx];
+    let (min_after, max_after) = self.limits[idx + 1];
+    // The range the divider can move within, a pane smaller than its minimum
+    // size (e.g. collapsed) is allowed to stay there.
+    let lower = (min_before.min(before) - before).max(after - max_after.max(after));
+    let upper = (max_before.max(before) - before).min(after - min_after.min(after));
+    let delta = delta.clamp(lower.min(0.), upper.max(0.));
+    if delta != 0. {
+      sizes[idx] = before + delta;
+      sizes[idx + 1] = after - delta;
+      self.ratios = sizes.iter().map(|s| s / self.space).collect();
+      self.collapsed[idx] = None;
+      self.collapsed[idx + 1] = None;
+    }
+    delta
+  }
+
+  /// Collapse the pane before the divider to zero, or restore it if it's
+  /// collapsed.
+  pub fn toggle_collapse(&mut self, idx: usize) {
+    // Start from the sizes the panes are laid out with, the layout keeps the
+    // space given to the neighbour within its limits.
+    if self.space > 0. {
+      self.ratios = self
+        .sizes()
+        .iter()
+        .map(|s| s / self.space)
+        .collect();
+    }
+    let (pane, next) = (idx, idx + 1);
+    if let Some(ratio) = self.collapsed[pane].take() {
+      let ratio = ratio.min(self.ratios[pane] + self.ratios[next]);
+      self.ratios[next] -= ratio - self.ratios[pane];
+      self.ratios[pane] = ratio;
+    } else if self.ratios[pane] > 0. {
+      self.collapsed[pane] = Some(self.ratios[pane]);
+      self.ratios[next] += self.ratios[pane];
+      self.ratios[pane] = 0.;
+    }
+  }
+
+  fn pane_specs(&self) -> Vec<PaneSpec> {
+    self
+      .ratios
+      .iter()
+      .zip(&self.limits)
+      .zip(&self.collapsed)
+      .map(|((&ratio, &(min, max)), collapsed)| PaneSpec {
+        ratio,
+        min,
+        max,
+        collapsed: collapsed.is_some(),
+      })
+      .collect()
+  }
+
+  fn init_panes(&mut self, limits: Vec<(f32, f32)>) {
+    self.limits = limits;
+    self.fit_panes();
+  }
+
+  /// Fit the ratios to the count of the panes, no pane is collapsed then.
+  fn fit_panes(&mut self) {
+    let cnt = self.limits.len();
+    if self.ratios.len() != cnt {
+      self.ratios = vec![1.; cnt];
+    }
+    self.normalize();
+    self.collapsed = vec![None; cnt];
+  }
+
+  fn normalize(&mut self) {
+    let sum: f32 = self.ratios.iter().sum();
+    if sum > 0. {
+      self.ratios.iter_mut().for_each(|r| *r /= sum);
+    } else if !self.ratios.is_empty() {
+      let even = 1. / self.ratios.len() as f32;
+      self.ratios.iter_mut().for_each(|r| *r = even);
+    }
+  }
+
+  fn handle_key(&mut self, idx: usize, key: &VirtualKey) -> bool {
+    let (backward, forward) = match self.direction {
+      Direction::Horizontal => (NamedKey::ArrowLeft, NamedKey::ArrowRight),
+      Direction::Vertical => (NamedKey::ArrowUp, NamedKey::ArrowDown),
+    };
+    match key {
+      VirtualKey::Named(k) if *k == backward => self.move_divider(idx, -self.keyboard_step),
+      VirtualKey::Named(k) if *k == forward => self.move_divider(idx, self.keyboard_step),
+      VirtualKey::Named(NamedKey::Home) => self.move_divider(idx, f32::NEG_INFINITY),
+      VirtualKey::Named(NamedKey::End) => self.move_divider(idx, f32::INFINITY),
+      VirtualKey::Named(NamedKey::Enter) => {
+        self.toggle_collapse(idx);
+        0.
+      }
+      _ => return false,
+    };
+    true
+  }
+}
+
+impl<'c> ComposeChild<'c> for SplitPane {
+  type Child = Vec<Pane<'c>>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let limits = child.iter().map(|p| (p.min, p.max)).collect();
+    this.silent().init_panes(limits);
+    fn_widget! {
+      let direction = $read(this).direction;
+      let cnt = child.len();
+      let mut children = vec![];
+      for (idx, pane) in child.into_iter().enumerate() {
+        if idx > 0 {
+          children.push(divider(&this, idx - 1));
+        }
+        let pane = @Class { class: SPLIT_PANE_PANE, @ { pane.child } };
+        children.push(pane.into_widget());
+      }
+
+      @SplitLayout {
+        class: SPLIT_PANE,
+        direction,
+        panes: distinct_pipe!($read(this).pane_specs()),
+        on_performed_layout: move |e| {
+          let size = e.box_size().unwrap_or_default();
+          let main = match direction {
+            Direction::Horizontal => size.width,
+            Direction::Vertical => size.height,
+          };
+          let this = $writer(this);
+          let mut this = this.silent();
+          this.space = (main - this.divider_extent * cnt.saturating_sub(1) as f32).max(0.);
+        },
+        @ { children }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The ratio and the limits of a pane to lay out.
+#[derive(Clone, PartialEq)]
+struct PaneSpec {
+  ratio: f32,
+  min: f32,
+  max: f32,
+  collapsed: bool,
+}
+
+/// Share the `space` by the ratios of the panes and keep every pane within its
+/// limits. A pane violating its limit is frozen at the limit and the rest of
+/// the space is shared by the other panes again, like the flexible items of
+/// CSS. A collapsed pane is always zero.
+fn resolve_sizes(panes: &[PaneSpec], space: f32) -> Vec<f32> {
+  let mut sizes = vec![0.; panes.len()];
+  let mut frozen: Vec<bool> = panes.iter().map(|p| p.collapsed).collect();
+  loop {
+    let unfrozen: Vec<usize> = (0..panes.len()).filter(|i| !frozen[*i]).collect();
+    if unfrozen.is_empty() {
+      break;
+    }
+    let used: f32 = (0..panes.len())
+      .filter(|i| frozen[*i])
+      .map(|i| sizes[i])
+      .sum();
+    let remain = (space - used).max(0.);
+    let ratio_sum: f32 = unfrozen.iter().map(|i| panes[*i].ratio).sum();
+    let mut violation = 0.;
+    let clamped: Vec<(usize, f32)> = unfrozen
+      .iter()
+      .map(|&i| {
+        let share = if ratio_sum > 0. {
+          remain * panes[i].ratio / ratio_sum
+        } else {
+          remain / unfrozen.len() as f32
+        };
+        sizes[i] = share.min(panes[i].max).max(panes[i].min);
+        violation += sizes[i] - share;
+        (i, sizes[i] - share)
+      })
+      .collect();
+    if violation == 0. {
+      break;
+    }
+    // Freeze the panes grown to their minimum if the space is overused, or
+    // the panes shrunk to their maximum if some space is left.
+    clamped
+      .into_iter()
+      .filter(|(_, v)| *v * violation > 0.)
+      .for_each(|(i, _)| frozen[i] = true);
+  }
+  sizes
+}
+
+/// Lay out the panes and the dividers between them along the direction. The
+/// dividers take their own size, and the panes share the rest space by
+/// [`resolve_sizes`].
+#[derive(MultiChild, Declare)]
+struct SplitLayout {
+  direction: Direction,
+  panes: Vec<PaneSpec>,
+}
+
+impl Render for SplitLayout {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let dir = self.direction;
+    let main_max = dir.max_of(&clamp);
+    let main = if main_max.is_finite() { main_max } else { dir.min_of(&clamp) };
+    let cross_max = dir.cross_max_of(&clamp);
+    let cross_min = if cross_max.is_finite() { cross_max } else { dir.cross_min_of(&clamp) };
+
+    let (ctx, children) = ctx.split_children();
+    let children: Vec<_> = children.collect();
+    let mut cross = cross_min;
+    // The dividers are the odd children between the panes.
+    let mut divider_sizes = vec![];
+    for c in children.iter().skip(1).step_by(2) {
+      let clamp = BoxClamp {
+        min: dir.to_size(0., cross_min),
+        max: dir.to_size(f32::INFINITY, cross_max),
+      };
+      let (m, c) = dir.main_cross_of(ctx.perform_child_layout(*c, clamp));
+      cross = cross.max(c);
+      divider_sizes.push(m);
+    }
+
+    let space = (main - divider_sizes.iter().sum::<f32>()).max(0.);
+    let sizes = resolve_sizes(&self.panes, space);
+    let mut offset = 0.;
+    for (i, c) in children.iter().enumerate() {
+      let size = if i % 2 == 0 {
+        let m = sizes.get(i / 2).copied().unwrap_or_default();
+        let clamp = BoxClamp { min: dir.to_size(m, cross_min), max: dir.to_size(m, cross_max) };
+        let (m, c) = dir.main_cross_of(ctx.perform_child_layout(*c, clamp));
+        cross = cross.max(c);
+        m
+      } else {
+        divider_sizes[i / 2]
+      };
+      ctx.update_position(*c, dir.to_point(offset, 0.));
+      offset += size;
+    }
+
+    clamp.clamp(dir.to_size(offset, cross))
+  }
+
+  #[inline]
+  fn size_affected_by_child(&self) -> bool { true }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+fn divider<'c>(this: &impl StateWriter<Value = SplitPane>, idx: usize) -> Widget<'c> {
+  let this = this.clone_writer();
+  fn_widget! {
+    let direction = $read(this).direction;
+    let (class, cursor) = match direction {
+      Direction::Horizontal => (SPLIT_PANE_H_DIVIDER, CursorIcon::ColResize),
+      Direction::Vertical => (SPLIT_PANE_V_DIVIDER, CursorIcon::RowResize),
+    };
+    let main_of = move |p: Point| match direction {
+      Direction::Horizontal => p.x,
+      Direction::Vertical => p.y,
+    };
+    // The pointer grab, the start position and the ratios when the drag starts.
+    let drag = Stateful::new(None);
+
+    @Void {
+      class,
+      cursor,
+      tab_index: 0_i16,
+      on_pointer_down: move |e| {
+        if let Some(handle) = GrabPointer::grab(e.current_target(), &e.window()) {
+          let ratios = $read(this).ratios.clone();
+          *$write(drag) = Some((handle, main_of(e.global_pos()), ratios));
+        }
+      },
+      on_pointer_move: move |e| {
+        if let Some((_, start, ratios)) = $read(drag).as_ref() {
+          let mut this = $write(this);
+          this.ratios.clone_from(ratios);
+          this.move_divider(idx, main_of(e.global_pos()) - start);
+        }
+      },
+      on_pointer_up: move |_| {
+        $write(drag).take();
+      },
+      on_double_tap: move |_| $write(this).toggle_collapse(idx),
+      on_key_down: move |e| {
+        if $write(this).handle_key(idx, e.key()) {
+          e.stop_propagation();
+        }
+      },
+      on_performed_layout: move |e| {
+        let size = e.box_size().unwrap_or_default();
+        let extent = match direction {
+          Direction::Horizontal => size.width,
+          Direction::Vertical => size.height,
+        };
+        $writer(this).silent().divider_extent = extent;
+      },
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  fn split(ratios: Vec<f32>, limits: Vec<(f32, f32)>) -> SplitPane {
+    let mut split = SplitPane {
+      direction: Direction::Horizontal,
+      ratios,
+      keyboard_step: 10.,
+      limits: vec![],
+      collapsed: vec![],
+      space: 300.,
+      divider_extent: 0.,
+    };
+    split.init_panes(limits);
+    split
+  }
+
+  #[test]
+  fn move_divider_respects_limits() {
+    let mut split = split(vec![1., 2.], vec![(50., 150.), (100., f32::INFINITY)]);
+    assert_eq!(split.sizes(), [100., 200.]);
+
+    assert_eq!(split.move_divider(0, 30.), 30.);
+    assert_eq!(split.sizes(), [130., 170.]);
+    // The first pane can't be larger than 150.
+    assert_eq!(split.move_divider(0, 100.), 20.);
+    // The first pane can't be smaller than 50.
+    assert_eq!(split.move_divider(0, -200.), -100.);
+    assert_eq!(split.sizes(), [50., 250.]);
+
+    let mut split = split_three();
+    // The second pane can't be smaller than 100.
+    assert_eq!(split.move_divider(0, 50.), 0.);
+    assert_eq!(split.move_divider(1, -50.), 0.);
+  }
+
+  #[track_caller]
+  fn assert_sizes<const N: usize>(split: &SplitPane, expect: [f32; N]) {
+    let sizes = split.sizes();
+    assert!(
+      sizes
+        .iter()
+        .zip(expect)
+        .all(|(a, b)| (a - b).abs() < 0.01),
+      "{sizes:?} != {expect:?}"
+    );
+  }
+
+  fn split_three() -> SplitPane {
+    split(vec![], vec![(0., f32::INFINITY), (100., 100.), (0., 500.)])
+  }
+
+  #[test]
+  fn collapse_and_restore() {
+    let mut split = split(vec![1., 3.], vec![(50., f32::INFINITY), (0., f32::INFINITY)]);
+    split.toggle_collapse(0);
+    assert!(split.is_collapsed(0));
+    assert_eq!(split.sizes(), [0., 300.]);
+    split.toggle_collapse(0);
+    assert!(!split.is_collapsed(0));
+    assert_eq!(split.sizes(), [75., 225.]);
+
+    // A collapsed pane can be dragged open from zero.
+    split.toggle_collapse(0);
+    assert_eq!(split.move_divider(0, 10.), 10.);
+    assert!(!split.is_collapsed(0));
+  }
+
+  #[test]
+  fn set_mismatched_ratios() {
+    let mut split = split_three_max();
+    split.toggle_collapse(0);
+    split.set_ratios(vec![1., 3.]);
+    assert!(!split.is_collapsed(0));
+    assert_sizes(&split, [100., 100., 100.]);
+
+    assert_eq!(split.move_divider(1, 20.), 20.);
+    assert_sizes(&split, [100., 120., 80.]);
+    split.toggle_collapse(1);
+    assert_sizes(&split, [100., 0., 200.]);
+
+    split.set_ratios(vec![1., 1., 2.]);
+    assert_sizes(&split, [75., 75., 150.]);
+  }
+
+  #[test]
+  fn layout_keeps_limits() {
+    // The initial ratios give the second pane less than its minimum.
+    let split = split(vec![0.9, 0.1], vec![(0., f32::INFINITY), (100., f32::INFINITY)]);
+    assert_eq!(split.sizes(), [200., 100.]);
+
+    // The collapsed pane gives its space to the next pane, but the next pane
+    // can't grow over its maximum, the rest goes to the last pane.
+    let mut split = split_three_max();
+    split.toggle_collapse(0);
+    assert_eq!(split.sizes(), [0., 120., 180.]);
+    // The restored pane takes its space back from the next pane.
+    split.toggle_collapse(0);
+    assert_sizes(&split, [100., 20., 180.]);
+  }
+
+  fn split_three_max() -> SplitPane {
+    split(vec![], vec![(0., f32::INFINITY), (0., 120.), (0., f32::INFINITY)])
+  }
+
+  #[test]
+  fn resize_keeps_limits() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      @SplitPane {
+        @Pane {
+          max: 150.,
+          @Void {}
+        }
+        @Pane {
+          min: 100.,
+          @Void {}
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 100.));
+    wnd.draw_frame();
+    let pane_width = |idx| wnd.layout_info_by_path(&[0, idx]).unwrap().size.unwrap().width;
+    let divider = wnd.layout_info_by_path(&[0, 1]).unwrap().size.unwrap().width;
+    assert_eq!(pane_width(0), 100. - divider);
+    assert_eq!(pane_width(2), 100.);
+
+    wnd.request_resize(Size::new(600., 100.));
+    wnd.draw_frame();
+    assert_eq!(pane_width(0), 150.);
+    assert_eq!(pane_width(2), 450. - divider);
+
+    wnd.request_resize(Size::new(150., 100.));
+    wnd.draw_frame();
+    assert_eq!(pane_width(0), 50. - divider);
+    assert_eq!(pane_width(2), 100.);
+  }
+
+  #[test]
+  fn keyboard_resize() {
+    reset_test_env!();
+
+    let split = Stateful::new(SplitPane {
+      direction: Direction::Horizontal,
+      ratios: vec![],
+      keyboard_step: 10.,
+      limits: vec![],
+      collapsed: vec![],
+      space: 0.,
+      divider_extent: 0.,
+    });
+    let c_split = split.clone_writer();
+    let w = fn_widget! {
+      @(c_split.clone_writer()) {
+        @Pane { @Void {} }
+        @Pane {
+          min: 50.,
+          @Void {}
+        }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(200., 100.));
+    wnd.draw_frame();
+    // The divider takes the space that its class gives it.
+    let space = split.read().sizes().iter().sum::<f32>();
+    let divider = 200. - space;
+    let half = space / 2.;
+    assert_sizes(&split.read(), [half, half]);
+
+    let key = |code: KeyCode, key: NamedKey| {
+      wnd.process_keyboard_event(
+        PhysicalKey::Code(code),
+        VirtualKey::Named(key),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+      wnd.draw_frame();
+    };
+    // Move the focus to the divider.
+    key(KeyCode::Tab, NamedKey::Tab);
+    key(KeyCode::ArrowRight, NamedKey::ArrowRight);
+    assert_sizes(&split.read(), [half + 10., half - 10.]);
+    assert_eq!(wnd.layout_info_by_path(&[0, 2]).unwrap().pos.x, half + 10. + divider);
+
+    key(KeyCode::End, NamedKey::End);
+    assert_sizes(&split.read(), [space - 50., 50.]);
+    key(KeyCode::Enter, NamedKey::Enter);
+    assert!(split.read().is_collapsed(0));
+    assert_sizes(&split.read(), [0., space]);
+    key(KeyCode::ArrowLeft, NamedKey::ArrowLeft);
+    assert_sizes(&split.read(), [0., space]);
+    key(KeyCode::Enter, NamedKey::Enter);
+    assert_sizes(&split.read(), [space - 50., 50.]);
+  }
+}
@@ -0,0 +1,312 @@
+use std::{cell::Cell, rc::Rc};
+
+use ribir_core::prelude::*;
+
+/// The header of the [`Sticky`], it's pinned to the top of the viewport when
+/// its section scrolls through the viewport.
+#[derive(Template)]
+pub struct StickyHeader<'w>(Widget<'w>);
+
+/// The children of the [`Sticky`], a header and the content of the section.
+#[derive(Template)]
+pub struct StickyChildren<'w> {
+  header: StickyHeader<'w>,
+  content: Widget<'w>,
+}
+
+/// A section of the scrollable content with a header that sticks to the top of
+/// the viewport, until the end of the section pushes it away.
+///
+/// If the `collapsed_extent` is set, the header shrinks to it when its section
+/// scrolls, this is the way to make a collapsing app bar that stays at the top
+/// of the page.
+///
+/// The header sticks to the closest [`ScrollableWidget`], it behaves like a
+/// normal header if there is no scrollable widget.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @ScrollableWidget {
+///     @Column {
+///       @Sticky {
+///         collapsed_extent: 64.,
+///         @StickyHeader { @Container { size: Size::new(360., 160.) } }
+///         @Text { text: "The page content." }
+///       }
+///       @Sticky {
+///         @StickyHeader { @Text { text: "Section A" } }
+///         @Container { size: Size::new(360., 1000.) }
+///       }
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare, Default)]
+pub struct Sticky {
+  /// The extent the header shrinks to when its section scrolls, the header
+  /// keeps its size if it's `None`.
+  #[declare(default)]
+  collapsed_extent: Option<f32>,
+  #[declare(skip)]
+  pin: f32,
+  #[declare(skip)]
+  header_extent: f32,
+}
+
+impl Sticky {
+  /// The extent the header shrinks to when its section scrolls.
+  pub fn collapsed_extent(&self) -> Option<f32> { self.collapsed_extent }
+
+  /// Set the extent the header shrinks to when its section scrolls, or `None`
+  /// to keep the size of the header.
+  pub fn set_collapsed_extent(&mut self, extent: Option<f32>) { self.collapsed_extent = extent; }
+
+  /// The distance the section scrolled beyond the top of the viewport, the
+  /// header is pinned by this offset in the section. It stops growing when
+  /// the header reaches the end of the section.
+  pub fn pin_offset(&self) -> f32 { self.pin }
+
+  /// Return if the header is pinned to the top of the viewport.
+  pub fn is_pinned(&self) -> bool { self.pin > 0. }
+
+  /// The progress of the header collapsing, from `0.` for the expanded header
+  /// to `1.` for the header collapsed to the `collapsed_extent`.
+  pub fn collapse_progress(&self) -> f32 {
+    let Some(collapsed) = self.collapsed_extent else { return 0. };
+    let range = self.header_extent - collapsed;
+    if range > 0. { (self.pin / range).clamp(0., 1.) } else { 0. }
+  }
+}
+
+impl<'c> ComposeChild<'c> for Sticky {
+  type Child = StickyChildren<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let StickyChildren { header, content } = child;
+    fn_widget! {
+      let layout = Stateful::new(StickyLayout {
+        pin: 0.,
+        collapsed_extent: $read(this).collapsed_extent,
+        header_extent: Cell::new(0.),
+        max_pin: Cell::new(0.),
+      });
+      let u_collapsed = watch!($read(this).collapsed_extent)
+        .distinct_until_changed()
+        .subscribe(move |v| $write(layout).collapsed_extent = v);
+      let scroll = ScrollableWidget::boxed_writer_of(BuildCtx::get());
+      // The top of the section in the content of the scrollable widget.
+      let top = Rc::new(Cell::new(0.));
+
+      let update_pin = {
+        let (this, layout, top) = (this.clone_writer(), layout.clone_writer(), top.clone());
+        Rc::new(move |scroll_y: f32| {
+          // The pin stays the same when the section is out of the viewport, so
+          // the other sections aren't laid out again as the content scrolls.
+          let pin = (scroll_y - top.get()).clamp(0., $read(layout).max_pin.get());
+          if $read(layout).pin != pin {
+            $write(layout).pin = pin;
+          }
+          if $read(this).pin != pin {
+            $write(this).pin = pin;
+          }
+        })
+      };
+      let u = scroll.as_ref().map(|scroll| {
+        let update_pin = update_pin.clone();
+        watch!($read(scroll).get_scroll_pos().y).subscribe(move |y| update_pin(y))
+      });
+
+      @(FatObj::new(layout.clone_writer())) {
+        on_performed_layout: move |e| {
+          let extent = $read(layout).header_extent.get();
+          if $read(this).header_extent != extent {
+            $write(this).header_extent = extent;
+          }
+          let Some(scroll) = scroll.as_ref() else { return };
+          let scroll = scroll.read();
+          let pos = scroll.map_to_content(Point::zero(), e.current_target(), &e.window());
+          if let Some(pos) = pos {
+            top.set(pos.y);
+            update_pin(scroll.get_scroll_pos().y);
+          }
+        },
+        on_disposed: move |_| {
+          u_collapsed.unsubscribe();
+          if let Some(u) = u {
+            u.unsubscribe();
+          }
+        },
+        @ { content }
+        @ { header.0 }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// Place the header above the content and pin it by the scrolled offset, the
+/// header is the last child to paint over the content.
+#[derive(MultiChild)]
+struct StickyLayout {
+  pin: f32,
+  collapsed_extent: Option<f32>,
+  header_extent: Cell<f32>,
+  /// The offset that the header reaches the end of the section.
+  max_pin: Cell<f32>,
+}
+
+impl Render for StickyLayout {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let (ctx, children) = ctx.split_children();
+    let children: Vec<_> = children.collect();
+    let [content, header] = children[..] else {
+      unreachable!("The sticky layout always has a content and a header.");
+    };
+
+    let child_clamp = BoxClamp {
+      min: Size::new(clamp.min.width, 0.),
+      max: Size::new(clamp.max.width, f32::INFINITY),
+    };
+    let mut header_size = ctx.perform_child_layout(header, child_clamp);
+    let extent = header_size.height;
+    self.header_extent.set(extent);
+    if let Some(collapsed) = self.collapsed_extent {
+      let height = (extent - self.pin.max(0.)).max(collapsed.min(extent));
+      if height < extent {
+        let clamp = BoxClamp::fixed_size(Size::new(header_size.width, height));
+        header_size = ctx.perform_child_layout(header, clamp);
+      }
+    }
+
+    let content_size = ctx.perform_child_layout(content, child_clamp);
+    ctx.update_position(content, Point::new(0., extent));
+
+    let height = extent + content_size.height;
+    let min_extent = self.collapsed_extent.map_or(extent, |c| c.min(extent));
+    self.max_pin.set((height - min_extent).max(0.));
+    let y = self.pin.min(height - header_size.height).max(0.);
+    ctx.update_position(header, Point::new(0., y));
+
+    let width = header_size.width.max(content_size.width);
+    clamp.clamp(Size::new(width, height))
+  }
+
+  #[inline]
+  fn size_affected_by_child(&self) -> bool { true }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+  use crate::prelude::*;
+
+  fn sticky(collapsed_extent: Option<f32>) -> Stateful<Sticky> {
+    let mut sticky = Sticky::default();
+    sticky.set_collapsed_extent(collapsed_extent);
+    Stateful::new(sticky)
+  }
+
+  fn sticky_page(
+    scroll: &Stateful<ScrollableWidget>, sticky: &Stateful<Sticky>,
+    header: &Stateful<Option<WidgetId>>,
+  ) -> TestWindow {
+    let (scroll, sticky, header) =
+      (scroll.clone_writer(), sticky.clone_writer(), header.clone_writer());
+    let w = fn_widget! {
+      @(scroll.clone_writer()) {
+        @Column {
+          @Container { size: Size::new(100., 50.) }
+          @(sticky.clone_writer()) {
+            @StickyHeader {
+              @Container {
+                size: Size::new(100., 40.),
+                on_mounted: move |e| *$writer(header).silent() = Some(e.current_target()),
+              }
+            }
+            @Container { size: Size::new(100., 200.) }
+          }
+          @Container { size: Size::new(100., 500.) }
+        }
+      }
+    };
+    TestWindow::new_with_size(w, Size::new(100., 100.))
+  }
+
+  fn header_rect(wnd: &TestWindow, header: &Stateful<Option<WidgetId>>) -> Rect {
+    let id = header.read().unwrap();
+    Rect::new(wnd.map_to_global(Point::zero(), id), wnd.widget_size(id).unwrap())
+  }
+
+  #[test]
+  fn pin_header() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let sticky = sticky(None);
+    let header = Stateful::new(None);
+    let wnd = sticky_page(&scroll, &sticky, &header);
+    wnd.draw_frame();
+    assert_eq!(header_rect(&wnd, &header).origin, Point::new(0., 50.));
+    assert!(!sticky.read().is_pinned());
+
+    scroll.write().jump_to(Point::new(0., 100.));
+    wnd.draw_frame();
+    assert!(sticky.read().is_pinned());
+    assert_eq!(sticky.read().pin_offset(), 50.);
+    assert_eq!(header_rect(&wnd, &header).origin, Point::new(0., 0.));
+
+    // The end of the section pushes the header away.
+    scroll.write().jump_to(Point::new(0., 280.));
+    wnd.draw_frame();
+    assert_eq!(header_rect(&wnd, &header).origin, Point::new(0., -30.));
+    assert_eq!(sticky.read().pin_offset(), 200.);
+
+    // The section out of the viewport isn't updated by the scrolling.
+    let writes = Rc::new(Cell::new(0));
+    let c_writes = writes.clone();
+    let u = sticky
+      .modifies()
+      .subscribe(move |_| c_writes.set(c_writes.get() + 1));
+    scroll.write().jump_to(Point::new(0., 350.));
+    wnd.draw_frame();
+    assert_eq!(writes.get(), 0);
+    assert_eq!(sticky.read().pin_offset(), 200.);
+    u.unsubscribe();
+  }
+
+  #[test]
+  fn collapse_header() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let sticky = sticky(Some(10.));
+    let header = Stateful::new(None);
+    let wnd = sticky_page(&scroll, &sticky, &header);
+    wnd.draw_frame();
+    assert_eq!(header_rect(&wnd, &header).size, Size::new(100., 40.));
+
+    scroll.write().jump_to(Point::new(0., 65.));
+    wnd.draw_frame();
+    assert_eq!(header_rect(&wnd, &header).size, Size::new(100., 25.));
+    assert_eq!(sticky.read().collapse_progress(), 0.5);
+
+    scroll.write().jump_to(Point::new(0., 150.));
+    wnd.draw_frame();
+    assert_eq!(header_rect(&wnd, &header).size, Size::new(100., 10.));
+    assert_eq!(sticky.read().collapse_progress(), 1.);
+
+    sticky.write().set_collapsed_extent(Some(20.));
+    wnd.draw_frame();
+    assert_eq!(header_rect(&wnd, &header).size, Size::new(100., 20.));
+  }
+}
@@ -0,0 +1,625 @@
+use std::f32::consts::TAU;
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class name for the whole time picker
+  TIME_PICKER,
+  /// Class name for the header that displays the selected time
+  TIME_PICKER_HEADER,
+  /// Class name for the hour or minute field in the header
+  TIME_PICKER_FIELD,
+  /// Class name for the hour or minute field that the dial is editing
+  TIME_PICKER_FIELD_SELECTED,
+  /// Class name for the separator between the hour and minute fields
+  TIME_PICKER_SEPARATOR,
+  /// Class name for the AM/PM switch of the 12-hour cycle
+  TIME_PICKER_PERIOD,
+  /// Class name for the AM/PM item that is not selected
+  TIME_PICKER_PERIOD_ITEM,
+  /// Class name for the AM/PM item that is selected
+  TIME_PICKER_PERIOD_ITEM_SELECTED,
+  /// Class name for the clock dial
+  TIME_PICKER_DIAL,
+  /// Class name for a number on the clock dial
+  TIME_PICKER_DIAL_LABEL,
+  /// Class name for the selected number on the clock dial
+  TIME_PICKER_DIAL_LABEL_SELECTED,
+  /// Class name for the button that switches between the dial and input modes
+  TIME_PICKER_MODE_TOGGLE,
+}
+
+/// The hour cycle of a [`TimePicker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HourCycle {
+  /// Hours from 1 to 12 with an AM/PM switch.
+  H12,
+  /// Hours from 0 to 23.
+  #[default]
+  H24,
+}
+
+/// How the user edits the time of a [`TimePicker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TimePickerMode {
+  /// Pick the hour and the minute on a clock dial.
+  #[default]
+  Dial,
+  /// Type the hour and the minute in text fields.
+  Input,
+}
+
+/// The part of the time that the dial of a [`TimePicker`] is editing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TimeUnit {
+  #[default]
+  Hour,
+  Minute,
+}
+
+/// The labels of the [`TimePicker`]. Provide it by a [`Provider`] to
+/// translate them, the default is the English labels.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = providers! {
+///   providers: [Provider::new(TimePickerLocale::zh_cn())],
+///   @TimePicker { hour_cycle: HourCycle::H12 }
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimePickerLocale {
+  /// The label of the AM item of the 12-hour cycle.
+  pub am: CowArc<str>,
+  /// The label of the PM item of the 12-hour cycle.
+  pub pm: CowArc<str>,
+  /// The label of the toggle that switches to the input mode.
+  pub input_mode: CowArc<str>,
+  /// The label of the toggle that switches to the dial mode.
+  pub dial_mode: CowArc<str>,
+}
+
+impl TimePickerLocale {
+  /// Return the locale provided by the context, or the default locale.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    Provider::of::<Self>(ctx).map_or_else(Self::default, |l| l.clone())
+  }
+
+  /// English labels.
+  pub fn en_us() -> Self {
+    Self { am: "AM".into(), pm: "PM".into(), input_mode: "Input".into(), dial_mode: "Dial".into() }
+  }
+
+  /// Simplified Chinese labels.
+  pub fn zh_cn() -> Self {
+    Self {
+      am: "上午".into(),
+      pm: "下午".into(),
+      input_mode: "输入".into(),
+      dial_mode: "表盘".into(),
+    }
+  }
+}
+
+impl Default for TimePickerLocale {
+  fn default() -> Self { Self::en_us() }
+}
+
+/// A time picker that lets the user pick the hour and the minute on a clock
+/// dial, or type them in text fields.
+///
+/// In the dial mode, tap or drag on the dial to pick a value. Picking an hour
+/// switches the dial to the minutes. When the picker is focused, `ArrowUp`/
+/// `ArrowDown` change the value of the editing unit by one, and
+/// `ArrowLeft`/`ArrowRight` switch the editing unit.
+///
+/// In the 24-hour cycle, the hours from 0 to 11 are on the outer ring of the
+/// dial, and the hours from 12 to 23 are on the inner ring.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let picker = @TimePicker { hour: 9, minute: 30, hour_cycle: HourCycle::H12 };
+///   watch!(($read(picker).hour(), $read(picker).minute()))
+///     .subscribe(|(h, m)| println!("{h:02}:{m:02}"));
+///   picker
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct TimePicker {
+  /// The hour of the day, from 0 to 23.
+  #[declare(default)]
+  hour: u8,
+  /// The minute of the hour, from 0 to 59.
+  #[declare(default)]
+  minute: u8,
+  #[declare(default)]
+  hour_cycle: HourCycle,
+  #[declare(default)]
+  mode: TimePickerMode,
+  #[declare(skip)]
+  unit: TimeUnit,
+}
+
+const DIAL_SIZE: f32 = 256.;
+const DIAL_LABEL_SIZE: f32 = 48.;
+const OUTER_RADIUS: f32 = 104.;
+const INNER_RADIUS: f32 = 68.;
+
+impl TimePicker {
+  /// The hour of the day, from 0 to 23.
+  pub fn hour(&self) -> u8 { self.hour }
+
+  pub fn minute(&self) -> u8 { self.minute }
+
+  /// Set the time, the values out of range are wrapped.
+  pub fn set_time(&mut self, hour: u8, minute: u8) {
+    self.hour = hour % 24;
+    self.minute = minute % 60;
+  }
+
+  pub fn hour_cycle(&self) -> HourCycle { self.hour_cycle }
+
+  pub fn set_hour_cycle(&mut self, hour_cycle: HourCycle) { self.hour_cycle = hour_cycle; }
+
+  /// Return true if the time is after noon.
+  pub fn is_pm(&self) -> bool { self.hour >= 12 }
+
+  /// Switch the time between AM and PM, the hour on the clock is kept.
+  pub fn set_pm(&mut self, pm: bool) { self.hour = self.hour % 12 + if pm { 12 } else { 0 }; }
+
+  pub fn mode(&self) -> TimePickerMode { self.mode }
+
+  /// Switch between the dial and input modes.
+  pub fn toggle_mode(&mut self) {
+    self.mode = match self.mode {
+      TimePickerMode::Dial => TimePickerMode::Input,
+      TimePickerMode::Input => TimePickerMode::Dial,
+    };
+  }
+
+  /// Return the unit that the dial is editing.
+  pub fn unit(&self) -> TimeUnit { self.unit }
+
+  pub fn set_unit(&mut self, unit: TimeUnit) { self.unit = unit; }
+
+  /// Change the value of the editing unit by `delta`, wrapped around the
+  /// clock.
+  pub fn step(&mut self, delta: i32) {
+    match self.unit {
+      TimeUnit::Hour => self.hour = (self.hour as i32 + delta).rem_euclid(24) as u8,
+      TimeUnit::Minute => self.minute = (self.minute as i32 + delta).rem_euclid(60) as u8,
+    }
+  }
+
+  /// Return the hour as displayed in the hour cycle.
+  pub fn hour_label(&self) -> String {
+    match self.hour_cycle {
+      HourCycle::H12 => format!("{}", Self::hour_on_clock(self.hour)),
+      HourCycle::H24 => format!("{:02}", self.hour),
+    }
+  }
+
+  pub fn minute_label(&self) -> String { format!("{:02}", self.minute) }
+
+  /// Set the value of `unit` from the text that the user typed, the text is
+  /// ignored if it's not a valid value in the hour cycle.
+  fn input_value(&mut self, unit: TimeUnit, text: &str) {
+    let Ok(v) = text.trim().parse::<u8>() else { return };
+    match (unit, self.hour_cycle) {
+      (TimeUnit::Hour, HourCycle::H12) if (1..=12).contains(&v) => {
+        self.hour = v % 12 + if self.is_pm() { 12 } else { 0 };
+      }
+      (TimeUnit::Hour, HourCycle::H24) if v < 24 => self.hour = v,
+      (TimeUnit::Minute, _) if v < 60 => self.minute = v,
+      _ => {}
+    }
+  }
+
+  fn hour_on_clock(hour: u8) -> u8 {
+    match hour % 12 {
+      0 => 12,
+      h => h,
+    }
+  }
+
+  /// Return the value and the center of the labels on the dial of the
+  /// editing unit.
+  fn dial_items(&self) -> Vec<(u8, Point)> {
+    let ring = |radius: f32| {
+      (0..12).map(move |i| {
+        let angle = i as f32 * TAU / 12.;
+        let c = DIAL_SIZE / 2.;
+        Point::new(c + radius * angle.sin(), c - radius * angle.cos())
+      })
+    };
+    match (self.unit, self.hour_cycle) {
+      (TimeUnit::Minute, _) => ring(OUTER_RADIUS)
+        .enumerate()
+        .map(|(i, p)| (i as u8 * 5, p))
+        .collect(),
+      (TimeUnit::Hour, HourCycle::H12) => {
+        let pm = if self.is_pm() { 12 } else { 0 };
+        ring(OUTER_RADIUS)
+          .enumerate()
+          .map(|(i, p)| (i as u8 + pm, p))
+          .collect()
+      }
+      (TimeUnit::Hour, HourCycle::H24) => ring(OUTER_RADIUS)
+        .enumerate()
+        .map(|(i, p)| (i as u8, p))
+        .chain(
+          ring(INNER_RADIUS)
+            .enumerate()
+            .map(|(i, p)| (i as u8 + 12, p)),
+        )
+        .collect(),
+    }
+  }
+
+  fn dial_label(&self, value: u8) -> String {
+    match (self.unit, self.hour_cycle) {
+      (TimeUnit::Hour, HourCycle::H12) => Self::hour_on_clock(value).to_string(),
+      (TimeUnit::Hour, HourCycle::H24) if value < 12 => format!("{value:02}"),
+      (TimeUnit::Hour, HourCycle::H24) => value.to_string(),
+      (TimeUnit::Minute, _) => format!("{value:02}"),
+    }
+  }
+
+  fn dial_value(&self) -> u8 {
+    match self.unit {
+      TimeUnit::Hour => self.hour,
+      TimeUnit::Minute => self.minute,
+    }
+  }
+
+  /// Pick the value of the editing unit at the position relative to the
+  /// top-left of the dial.
+  fn pick_on_dial(&mut self, pos: Point) {
+    let v = pos - Point::splat(DIAL_SIZE / 2.);
+    let angle = v.x.atan2(-v.y).rem_euclid(TAU);
+    match self.unit {
+      TimeUnit::Hour => {
+        let idx = (angle / (TAU / 12.)).round() as u8 % 12;
+        self.hour = match self.hour_cycle {
+          HourCycle::H12 => idx + if self.is_pm() { 12 } else { 0 },
+          HourCycle::H24 if v.length() < (OUTER_RADIUS + INNER_RADIUS) / 2. => idx + 12,
+          HourCycle::H24 => idx,
+        };
+      }
+      TimeUnit::Minute => self.minute = (angle / (TAU / 60.)).round() as u8 % 60,
+    }
+  }
+
+  fn handle_key(&mut self, key: &VirtualKey) -> bool {
+    if self.mode != TimePickerMode::Dial {
+      return false;
+    }
+    match key {
+      VirtualKey::Named(NamedKey::ArrowUp) => self.step(1),
+      VirtualKey::Named(NamedKey::ArrowDown) => self.step(-1),
+      VirtualKey::Named(NamedKey::ArrowLeft) => self.unit = TimeUnit::Hour,
+      VirtualKey::Named(NamedKey::ArrowRight) => self.unit = TimeUnit::Minute,
+      _ => return false,
+    }
+    true
+  }
+}
+
+impl Compose for TimePicker {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let locale = TimePickerLocale::of(BuildCtx::get());
+      let period = locale.clone();
+      @Column {
+        class: TIME_PICKER,
+        tab_index: 0_i16,
+        align_items: Align::Center,
+        on_key_down: move |e| {
+          if $write(this).handle_key(e.key()) {
+            e.stop_propagation();
+          }
+        },
+        @Row {
+          class: TIME_PICKER_HEADER,
+          align_items: Align::Center,
+          @ { time_field(&this, TimeUnit::Hour) }
+          @Text { class: TIME_PICKER_SEPARATOR, text: ":" }
+          @ { time_field(&this, TimeUnit::Minute) }
+          @ {
+            let picker = this.clone_writer();
+            distinct_pipe!($read(this).hour_cycle).map(move |cycle| {
+              (cycle == HourCycle::H12).then(|| period_switch(&picker, period.clone()))
+            })
+          }
+        }
+        @ {
+          distinct_pipe!($read(this).mode).map(move |mode| {
+            (mode == TimePickerMode::Dial).then(|| dial(&this))
+          })
+        }
+        @Text {
+          class: TIME_PICKER_MODE_TOGGLE,
+          text: distinct_pipe!(match $read(this).mode {
+            TimePickerMode::Dial => locale.input_mode.clone(),
+            TimePickerMode::Input => locale.dial_mode.clone(),
+          }),
+          on_tap: move |_| $write(this).toggle_mode(),
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn time_field(this: &impl StateWriter<Value = TimePicker>, unit: TimeUnit) -> Widget<'static> {
+  let this = this.clone_writer();
+  let label = move |picker: &TimePicker| match unit {
+    TimeUnit::Hour => picker.hour_label(),
+    TimeUnit::Minute => picker.minute_label(),
+  };
+  fn_widget! {
+    distinct_pipe!($read(this).mode).map(move |mode| match mode {
+      TimePickerMode::Dial => @Text {
+        class: distinct_pipe!(if $read(this).unit == unit {
+          TIME_PICKER_FIELD_SELECTED
+        } else {
+          TIME_PICKER_FIELD
+        }),
+        text: pipe!(label(&$read(this))),
+        on_tap: move |_| $write(this).set_unit(unit),
+      }
+      .into_widget(),
+      TimePickerMode::Input => {
+        let mut input = @Input {};
+        $write(input).set_text(&label(&$read(this)));
+        let u = watch!($read(input).text().clone())
+          .subscribe(move |text| $write(this).input_value(unit, &text));
+        // Follow the value written from outside, but keep the text that the
+        // user is typing if it already means the same value.
+        let sync = watch!(label(&$read(this)))
+          .distinct_until_changed()
+          .subscribe(move |label| {
+            let typed = $read(input).text().trim().parse::<u8>().ok();
+            if typed != label.parse().ok() {
+              $write(input).set_text(&label);
+            }
+          });
+        @(input) {
+          class: TIME_PICKER_FIELD,
+          on_disposed: move |_| {
+            u.unsubscribe();
+            sync.unsubscribe();
+          },
+        }
+        .into_widget()
+      }
+    })
+  }
+  .into_widget()
+}
+
+fn period_switch(
+  this: &impl StateWriter<Value = TimePicker>, locale: TimePickerLocale,
+) -> Widget<'static> {
+  let this = this.clone_writer();
+  let item = move |pm: bool, label: CowArc<str>| {
+    fn_widget! {
+      @Text {
+        class: distinct_pipe!(if $read(this).is_pm() == pm {
+          TIME_PICKER_PERIOD_ITEM_SELECTED
+        } else {
+          TIME_PICKER_PERIOD_ITEM
+        }),
+        text: label,
+        on_tap: move |_| $write(this).set_pm(pm),
+      }
+    }
+  };
+  fn_widget! {
+    @Column {
+      class: TIME_PICKER_PERIOD,
+      @ { item(false, locale.am) }
+      @ { item(true, locale.pm) }
+    }
+  }
+  .into_widget()
+}
+
+fn dial(this: &impl StateWriter<Value = TimePicker>) -> Widget<'static> {
+  let this = this.clone_writer();
+  fn_widget! {
+    @Stack {
+      class: TIME_PICKER_DIAL,
+      on_tap: move |e| {
+        let mut picker = $write(this);
+        picker.pick_on_dial(e.position());
+        if picker.unit == TimeUnit::Hour {
+          picker.unit = TimeUnit::Minute;
+        }
+      },
+      on_pointer_move: move |e| {
+        if e.mouse_buttons() == MouseButtons::PRIMARY {
+          $write(this).pick_on_dial(e.position());
+        }
+      },
+      @SizedBox { size: Size::splat(DIAL_SIZE) }
+      @ {
+        distinct_pipe!(($read(this).unit, $read(this).is_pm())).map(move |_| {
+          let items = $read(this).dial_items();
+          items.into_iter().map(move |(value, center)| {
+            let half = DIAL_LABEL_SIZE / 2.;
+            @SizedBox {
+              size: Size::splat(DIAL_LABEL_SIZE),
+              anchor: Anchor::left_top(center.x - half, center.y - half),
+              class: distinct_pipe!(if $read(this).dial_value() == value {
+                TIME_PICKER_DIAL_LABEL_SELECTED
+              } else {
+                TIME_PICKER_DIAL_LABEL
+              }),
+              @Text {
+                h_align: HAlign::Center,
+                v_align: VAlign::Center,
+                text: $read(this).dial_label(value),
+              }
+            }
+          })
+        })
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, rc::Rc};
+
+  use ribir_core::{reset_test_env, test_helper::*};
+  use smallvec::smallvec;
+  use winit::event::ElementState;
+
+  use super::*;
+
+  fn picker(hour: u8, minute: u8, hour_cycle: HourCycle) -> TimePicker {
+    TimePicker { hour, minute, hour_cycle, mode: TimePickerMode::Dial, unit: TimeUnit::Hour }
+  }
+
+  #[test]
+  fn twelve_hour_cycle() {
+    let mut p = picker(0, 5, HourCycle::H12);
+    assert_eq!(p.hour_label(), "12");
+    assert!(!p.is_pm());
+    p.set_pm(true);
+    assert_eq!(p.hour(), 12);
+    assert_eq!(p.hour_label(), "12");
+
+    p.input_value(TimeUnit::Hour, "3");
+    assert_eq!(p.hour(), 15);
+    p.input_value(TimeUnit::Hour, "13");
+    assert_eq!(p.hour(), 15);
+    p.input_value(TimeUnit::Minute, "07");
+    assert_eq!(p.minute_label(), "07");
+  }
+
+  #[test]
+  fn pick_on_dial() {
+    let c = DIAL_SIZE / 2.;
+    let mut p = picker(0, 0, HourCycle::H24);
+    // 3 o'clock on the outer ring.
+    p.pick_on_dial(Point::new(c + OUTER_RADIUS, c));
+    assert_eq!(p.hour(), 3);
+    // 6 o'clock on the inner ring.
+    p.pick_on_dial(Point::new(c, c + INNER_RADIUS));
+    assert_eq!(p.hour(), 18);
+
+    let mut p = picker(14, 0, HourCycle::H12);
+    p.pick_on_dial(Point::new(c - OUTER_RADIUS, c));
+    assert_eq!(p.hour(), 21);
+
+    p.set_unit(TimeUnit::Minute);
+    p.pick_on_dial(Point::new(c + OUTER_RADIUS, c + 1.));
+    assert_eq!(p.minute(), 15);
+    p.step(-16);
+    assert_eq!(p.minute(), 59);
+  }
+
+  #[test]
+  fn tap_dial_switch_to_minute() {
+    reset_test_env!();
+    let picker = Stateful::new(picker(0, 0, HourCycle::H24));
+    let c_picker = picker.clone_writer();
+    let w = fn_widget! { c_picker.clone_writer() };
+    let wnd = TestWindow::new_with_size(w, Size::new(400., 600.));
+    wnd.draw_frame();
+
+    let dial = wnd
+      .layout_info_by_path(&[0, 1])
+      .map(|info| info.pos)
+      .unwrap();
+    wnd.process_cursor_move(dial + Vector::new(DIAL_SIZE / 2. + OUTER_RADIUS, DIAL_SIZE / 2.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+
+    assert_eq!(picker.read().hour(), 3);
+    assert_eq!(picker.read().unit(), TimeUnit::Minute);
+  }
+
+  #[test]
+  fn switch_hour_cycle() {
+    reset_test_env!();
+    // Count the alive period switches by their class, the theme may wrap them.
+    let shown = Rc::new(Cell::new(0));
+    let picker = Stateful::new(picker(9, 0, HourCycle::H24));
+    let (c_picker, c_shown) = (picker.clone_writer(), shown.clone());
+    let w = fn_widget! {
+      let period = Class::provider(TIME_PICKER_PERIOD, |w| {
+        let shown = Provider::of::<Rc<Cell<i32>>>(BuildCtx::get())
+          .unwrap()
+          .clone();
+        shown.set(shown.get() + 1);
+        let mut w = FatObj::new(w);
+        w.on_disposed(move |_| shown.set(shown.get() - 1));
+        w.into_widget()
+      });
+      let (shown, picker) = (Provider::new(c_shown.clone()), c_picker.clone_writer());
+      providers! {
+        providers: smallvec![period, shown],
+        @ { picker }
+      }
+    };
+    let wnd = TestWindow::new_with_size(w, Size::new(400., 600.));
+    wnd.draw_frame();
+    assert_eq!(shown.get(), 0);
+
+    picker.write().set_hour_cycle(HourCycle::H12);
+    wnd.draw_frame();
+    assert_eq!(shown.get(), 1);
+
+    picker.write().set_hour_cycle(HourCycle::H24);
+    wnd.draw_frame();
+    assert_eq!(shown.get(), 0);
+  }
+
+  #[test]
+  fn follow_hour_in_input_mode() {
+    reset_test_env!();
+    let mut p = picker(9, 0, HourCycle::H12);
+    p.toggle_mode();
+    let picker = Stateful::new(p);
+    let c_picker = picker.clone_writer();
+    let w = fn_widget! { c_picker.clone_writer() };
+    let wnd = TestWindow::new_with_size(w, Size::new(400., 600.));
+    wnd.draw_frame();
+
+    // Focus the picker, and then the hour input.
+    for _ in 0..2 {
+      wnd.process_keyboard_event(
+        PhysicalKey::Code(KeyCode::Tab),
+        VirtualKey::Named(NamedKey::Tab),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+      wnd.draw_frame();
+    }
+
+    picker.write().set_time(2, 0);
+    wnd.draw_frame();
+    // The input shows "2" now, typing "1" before it makes "12".
+    wnd.process_receive_chars("1".into());
+    wnd.draw_frame();
+    assert_eq!(picker.read().hour(), 0);
+  }
+}
@@ -0,0 +1,1108 @@
+use std::{future::Future, pin::Pin, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  /// Root container class for the tree view
+  TREE_VIEW,
+  /// Class for every row of the tree view, the row is a `ListItem` within it
+  TREE_VIEW_ITEM,
+  /// Class for the row that has the keyboard focus
+  TREE_VIEW_ITEM_FOCUSED,
+  /// Class for the row that a dragged row will be dropped before
+  TREE_VIEW_ITEM_DROP_BEFORE,
+  /// Class for the row that a dragged row will be dropped after
+  TREE_VIEW_ITEM_DROP_AFTER,
+  /// Class for the expand/collapse indicator of a collapsed node
+  TREE_VIEW_EXPANDER,
+  /// Class for the expand/collapse indicator of an expanded node
+  TREE_VIEW_EXPANDER_EXPANDED,
+  /// Class for the container of the children of an expanded node
+  TREE_VIEW_GROUP,
+  /// Class for the indentation guide line of a group
+  TREE_VIEW_GUIDE,
+  /// Class for the indicator displayed while the children are loading
+  TREE_VIEW_LOADING,
+}
+
+/// The future that loads the children of a [`TreeNode`] asynchronously.
+pub type TreeChildrenFuture = Pin<Box<dyn Future<Output = Vec<TreeNode>>>>;
+
+/// The children of a [`TreeNode`].
+#[derive(Clone, Default)]
+pub enum TreeChildren {
+  /// The node is a leaf.
+  #[default]
+  None,
+  /// The children are known when the tree is built.
+  Loaded(Vec<TreeNode>),
+  /// The children are loaded the first time the node is expanded.
+  Lazy(Rc<dyn Fn() -> Vec<TreeNode>>),
+  /// The children are loaded asynchronously the first time the node is
+  /// expanded, the node displays a loading indicator until the future is
+  /// ready.
+  Async(Rc<dyn Fn() -> TreeChildrenFuture>),
+}
+
+/// The data of a node to build a [`TreeView`].
+///
+/// A node is displayed as a `ListItem` with its label as the headline, so it
+/// themes consistently with the [`List`].
+#[derive(Clone)]
+pub struct TreeNode {
+  label: CowArc<str>,
+  supporting: Option<CowArc<str>>,
+  icon: Option<NamedSvg>,
+  children: TreeChildren,
+}
+
+/// The identity of a node in a [`TreeView`], it's stable as long as the node
+/// is in the tree. The identity of a removed node may be reused by a new node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TreeNodeId(usize);
+
+/// The nodes of a [`TreeView`], convert from a `Vec<TreeNode>` of the roots.
+#[derive(Default)]
+pub struct TreeNodes {
+  nodes: Vec<NodeInfo>,
+  roots: Vec<TreeNodeId>,
+  /// The slots of the removed nodes to reuse.
+  free: Vec<TreeNodeId>,
+}
+
+/// Where a dragged node is dropped relative to the target node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropPosition {
+  Before,
+  After,
+}
+
+/// A hierarchical list for file explorers and outline panels.
+///
+/// Every node is displayed as a [`ListItem`] row with an indentation by its
+/// depth and an expander if it has children. The children of a node can be
+/// loaded lazily, either synchronously or asynchronously, the first time the
+/// node is expanded. The selection follows the [`ListSelectMode`] like the
+/// [`List`].
+///
+/// The tree view follows the WAI-ARIA tree pattern when it's focused:
+/// - `ArrowDown`/`ArrowUp` move the focus to the next/previous visible node.
+/// - `ArrowRight` expands a collapsed node, or moves the focus to the first
+///   child of an expanded node.
+/// - `ArrowLeft` collapses an expanded node, or moves the focus to the parent.
+/// - `Home`/`End` move the focus to the first/last visible node.
+/// - `Enter`/`Space` select the focused node.
+/// - `*` expands all the siblings of the focused node.
+///
+/// If `reorderable` is true, the user can drag a row and drop it before or
+/// after another row to move the node.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let tree = @TreeView {
+///     select_mode: ListSelectMode::Single,
+///     nodes: vec![
+///       TreeNode::new("src").with_children(vec![
+///         TreeNode::new("lib.rs"),
+///         TreeNode::new("main.rs"),
+///       ]),
+///       TreeNode::new("target").with_lazy_children(|| vec![TreeNode::new("debug")]),
+///       TreeNode::new("Cargo.toml"),
+///     ],
+///   };
+///   watch!($read(tree).selected().collect::<Vec<_>>())
+///     .subscribe(|selected| println!("selected: {selected:?}"));
+///   tree
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct TreeView {
+  /// The nodes of the tree.
+  #[declare(default)]
+  nodes: TreeNodes,
+  /// The selection mode of the nodes, see [`ListSelectMode`].
+  #[declare(default)]
+  select_mode: ListSelectMode,
+  /// The indentation width of each level.
+  #[declare(default = 24.)]
+  indent: f32,
+  /// Whether the user can drag a node to reorder it.
+  #[declare(default)]
+  reorderable: bool,
+  #[declare(skip)]
+  focused: Option<TreeNodeId>,
+  #[declare(skip)]
+  dragging: Option<TreeNodeId>,
+  #[declare(skip)]
+  drop_target: Option<(TreeNodeId, DropPosition)>,
+}
+
+struct NodeInfo {
+  label: CowArc<str>,
+  supporting: Option<CowArc<str>>,
+  icon: Option<NamedSvg>,
+  parent: Option<TreeNodeId>,
+  depth: usize,
+  children: NodeChildren,
+  expanded: bool,
+  /// If the children have been displayed, the group of the children is kept
+  /// when the node collapses, so it can shrink smoothly.
+  shown: bool,
+  selected: bool,
+  /// Increased when the slot is released, to tell the node apart from the
+  /// nodes that reuse its slot.
+  generation: usize,
+}
+
+enum NodeChildren {
+  Leaf,
+  Unloaded(TreeChildren),
+  Loading,
+  Loaded(Vec<TreeNodeId>),
+}
+
+#[derive(Clone, PartialEq)]
+enum GroupState {
+  Hidden,
+  Loading,
+  Children(Vec<TreeNodeId>),
+}
+
+impl TreeNode {
+  pub fn new(label: impl Into<CowArc<str>>) -> Self {
+    Self { label: label.into(), supporting: None, icon: None, children: TreeChildren::None }
+  }
+
+  /// Set the supporting text displayed below the label.
+  pub fn with_supporting(mut self, supporting: impl Into<CowArc<str>>) -> Self {
+    self.supporting = Some(supporting.into());
+    self
+  }
+
+  /// Set the icon displayed before the label.
+  pub fn with_icon(mut self, icon: NamedSvg) -> Self {
+    self.icon = Some(icon);
+    self
+  }
+
+  pub fn with_children(mut self, children: Vec<TreeNode>) -> Self {
+    self.children = TreeChildren::Loaded(children);
+    self
+  }
+
+  /// Load the children by `f` when the node is expanded the first time.
+  pub fn with_lazy_children(mut self, f: impl Fn() -> Vec<TreeNode> + 'static) -> Self {
+    self.children = TreeChildren::Lazy(Rc::new(f));
+    self
+  }
+
+  /// Load the children by the future that `f` returns when the node is
+  /// expanded the first time.
+  pub fn with_async_children<F>(mut self, f: impl Fn() -> F + 'static) -> Self
+  where
+    F: Future<Output = Vec<TreeNode>> + 'static,
+  {
+    self.children = TreeChildren::Async(Rc::new(move || Box::pin(f())));
+    self
+  }
+}
+
+impl TreeNodes {
+  fn push(&mut self, node: TreeNode, parent: Option<TreeNodeId>, depth: usize) -> TreeNodeId {
+    let TreeNode { label, supporting, icon, children } = node;
+    let info = NodeInfo {
+      label,
+      supporting,
+      icon,
+      parent,
+      depth,
+      children: NodeChildren::Leaf,
+      expanded: false,
+      shown: false,
+      selected: false,
+      generation: 0,
+    };
+    let id = if let Some(id) = self.free.pop() {
+      let generation = self.nodes[id.0].generation;
+      self.nodes[id.0] = NodeInfo { generation, ..info };
+      id
+    } else {
+      self.nodes.push(info);
+      TreeNodeId(self.nodes.len() - 1)
+    };
+    let children = match children {
+      TreeChildren::None => NodeChildren::Leaf,
+      TreeChildren::Loaded(children) => NodeChildren::Loaded(self.push_all(children, Some(id))),
+      lazy => NodeChildren::Unloaded(lazy),
+    };
+    self.nodes[id.0].children = children;
+    id
+  }
+
+  fn push_all(&mut self, nodes: Vec<TreeNode>, parent: Option<TreeNodeId>) -> Vec<TreeNodeId> {
+    let depth = parent.map_or(0, |p| self.nodes[p.0].depth + 1);
+    nodes
+      .into_iter()
+      .map(|node| self.push(node, parent, depth))
+      .collect()
+  }
+
+  /// Remove the node and its descendants, their slots will be reused.
+  fn release(&mut self, id: TreeNodeId) {
+    let generation = self.nodes[id.0].generation + 1;
+    let vacant = NodeInfo { generation, ..NodeInfo::vacant() };
+    let info = std::mem::replace(&mut self.nodes[id.0], vacant);
+    if let NodeChildren::Loaded(children) = info.children {
+      children.into_iter().for_each(|c| self.release(c));
+    }
+    self.free.push(id);
+  }
+
+  fn siblings_mut(&mut self, id: TreeNodeId) -> &mut Vec<TreeNodeId> {
+    match self.nodes[id.0].parent {
+      Some(p) => match &mut self.nodes[p.0].children {
+        NodeChildren::Loaded(children) => children,
+        _ => unreachable!("a node with parent must be a loaded child"),
+      },
+      None => &mut self.roots,
+    }
+  }
+
+  fn update_depth(&mut self, id: TreeNodeId, depth: usize) {
+    self.nodes[id.0].depth = depth;
+    if let NodeChildren::Loaded(children) = &self.nodes[id.0].children {
+      for child in children.clone() {
+        self.update_depth(child, depth + 1);
+      }
+    }
+  }
+}
+
+impl NodeInfo {
+  fn vacant() -> Self {
+    Self {
+      label: "".into(),
+      supporting: None,
+      icon: None,
+      parent: None,
+      depth: 0,
+      children: NodeChildren::Leaf,
+      expanded: false,
+      shown: false,
+      selected: false,
+      generation: 0,
+    }
+  }
+}
+
+impl From<Vec<TreeNode>> for TreeNodes {
+  fn from(roots: Vec<TreeNode>) -> Self {
+    let mut nodes = TreeNodes::default();
+    nodes.roots = nodes.push_all(roots, None);
+    nodes
+  }
+}
+
+impl TreeView {
+  /// Return the root nodes.
+  pub fn roots(&self) -> &[TreeNodeId] { &self.nodes.roots }
+
+  pub fn label(&self, id: TreeNodeId) -> &CowArc<str> { &self.node(id).label }
+
+  pub fn parent(&self, id: TreeNodeId) -> Option<TreeNodeId> { self.node(id).parent }
+
+  /// Return the depth of the node, the roots are at depth 0.
+  pub fn depth(&self, id: TreeNodeId) -> usize { self.node(id).depth }
+
+  /// Return the children of the node, or `None` if the node is a leaf or its
+  /// children are not loaded yet.
+  pub fn children(&self, id: TreeNodeId) -> Option<&[TreeNodeId]> {
+    match &self.node(id).children {
+      NodeChildren::Loaded(children) => Some(children),
+      _ => None,
+    }
+  }
+
+  /// Return true if the node has no children and can't be expanded.
+  pub fn is_leaf(&self, id: TreeNodeId) -> bool {
+    match &self.node(id).children {
+      NodeChildren::Leaf => true,
+      NodeChildren::Loaded(children) => children.is_empty(),
+      _ => false,
+    }
+  }
+
+  pub fn is_expanded(&self, id: TreeNodeId) -> bool { self.node(id).expanded }
+
+  /// Return true if the children of the node are loading asynchronously.
+  pub fn is_loading(&self, id: TreeNodeId) -> bool {
+    matches!(self.node(id).children, NodeChildren::Loading)
+  }
+
+  pub fn is_selected(&self, id: TreeNodeId) -> bool { self.node(id).selected }
+
+  /// Return the selected nodes in the display order.
+  pub fn selected(&self) -> impl Iterator<Item = TreeNodeId> + '_ {
+    self
+      .descendants(&self.nodes.roots)
+      .into_iter()
+      .filter(|id| self.is_selected(*id))
+  }
+
+  /// Return the node that has the keyboard focus.
+  pub fn focused(&self) -> Option<TreeNodeId> { self.focused }
+
+  /// Move the keyboard focus to the node.
+  pub fn focus(&mut self, id: TreeNodeId) { self.focused = Some(id); }
+
+  /// Return the nodes that are displayed, that's all the roots and the
+  /// children of the expanded nodes, in the display order.
+  pub fn visible_nodes(&self) -> Vec<TreeNodeId> {
+    let mut visible = vec![];
+    let mut stack: Vec<_> = self.nodes.roots.iter().rev().copied().collect();
+    while let Some(id) = stack.pop() {
+      visible.push(id);
+      if let (true, Some(children)) = (self.is_expanded(id), self.children(id)) {
+        stack.extend(children.iter().rev());
+      }
+    }
+    visible
+  }
+
+  /// Expand the node, load its children if they are not loaded yet.
+  pub fn expand(this: &impl StateWriter<Value = Self>, id: TreeNodeId) {
+    let mut tree = this.write();
+    let node = &mut tree.nodes.nodes[id.0];
+    if node.expanded || matches!(node.children, NodeChildren::Leaf) {
+      return;
+    }
+    node.expanded = true;
+    node.shown = true;
+    if !matches!(node.children, NodeChildren::Unloaded(_)) {
+      return;
+    }
+    let NodeChildren::Unloaded(loader) =
+      std::mem::replace(&mut node.children, NodeChildren::Loading)
+    else {
+      unreachable!()
+    };
+    match loader {
+      TreeChildren::None => tree.set_children(id, vec![]),
+      TreeChildren::Loaded(children) => tree.set_children(id, children),
+      TreeChildren::Lazy(f) => tree.set_children(id, f()),
+      TreeChildren::Async(f) => {
+        let generation = tree.node(id).generation;
+        drop(tree);
+        let fut = f();
+        let this = this.clone_writer();
+        AppCtx::spawn_local(async move {
+          let children = fut.await;
+          // The node may be removed and its slot reused by another node while
+          // loading, drop the children then.
+          let loading = {
+            let tree = this.read();
+            let node = tree.node(id);
+            node.generation == generation && matches!(node.children, NodeChildren::Loading)
+          };
+          if loading {
+            this.write().set_children(id, children);
+          }
+        });
+      }
+    }
+  }
+
+  /// Collapse the node, the focus moves to the node if it's in the collapsed
+  /// descendants.
+  pub fn collapse(&mut self, id: TreeNodeId) {
+    self.nodes.nodes[id.0].expanded = false;
+    if self
+      .focused
+      .is_some_and(|f| self.is_ancestor(id, f))
+    {
+      self.focused = Some(id);
+    }
+  }
+
+  /// Expand the node if it's collapsed, otherwise collapse it.
+  pub fn toggle(this: &impl StateWriter<Value = Self>, id: TreeNodeId) {
+    if this.read().is_expanded(id) {
+      this.write().collapse(id);
+    } else {
+      TreeView::expand(this, id);
+    }
+  }
+
+  /// Replace the children of the node, the old children and their
+  /// descendants are removed from the tree. The focus moves to the node if
+  /// it's in the removed nodes.
+  pub fn set_children(&mut self, id: TreeNodeId, children: Vec<TreeNode>) {
+    let old = std::mem::replace(&mut self.nodes.nodes[id.0].children, NodeChildren::Leaf);
+    if let NodeChildren::Loaded(old) = old {
+      let removed = |tree: &Self, n: TreeNodeId| n != id && tree.is_ancestor(id, n);
+      if self.focused.is_some_and(|f| removed(self, f)) {
+        self.focused = Some(id);
+      }
+      if self.dragging.is_some_and(|d| removed(self, d)) {
+        self.dragging = None;
+      }
+      if self
+        .drop_target
+        .is_some_and(|(t, _)| removed(self, t))
+      {
+        self.drop_target = None;
+      }
+      old
+        .into_iter()
+        .for_each(|c| self.nodes.release(c));
+    }
+    let children = self.nodes.push_all(children, Some(id));
+    self.nodes.nodes[id.0].children = NodeChildren::Loaded(children);
+  }
+
+  /// Select the node as the user taps it. In `Single` mode the node becomes
+  /// the only selected one, in `Multi` mode the node is toggled.
+  pub fn select(&mut self, id: TreeNodeId) {
+    match self.select_mode {
+      ListSelectMode::None => {}
+      ListSelectMode::Single => {
+        let selected: Vec<_> = self.selected().collect();
+        for other in selected {
+          self.nodes.nodes[other.0].selected = false;
+        }
+        self.nodes.nodes[id.0].selected = true;
+      }
+      ListSelectMode::Multi => {
+        let node = &mut self.nodes.nodes[id.0];
+        node.selected = !node.selected;
+      }
+    }
+  }
+
+  /// Deselect all the nodes.
+  pub fn deselect_all(&mut self) {
+    self
+      .nodes
+      .nodes
+      .iter_mut()
+      .for_each(|n| n.selected = false);
+  }
+
+  /// Move the node to the position before or after the target node, the node
+  /// becomes a sibling of the target. Return false if the target is the node
+  /// itself or one of its descendants.
+  pub fn move_node(&mut self, id: TreeNodeId, target: TreeNodeId, pos: DropPosition) -> bool {
+    if self.is_ancestor(id, target) {
+      return false;
+    }
+    self.nodes.siblings_mut(id).retain(|c| *c != id);
+    let parent = self.parent(target);
+    let depth = self.depth(target);
+    let siblings = self.nodes.siblings_mut(target);
+    let mut idx = siblings
+      .iter()
+      .position(|c| *c == target)
+      .unwrap();
+    if pos == DropPosition::After {
+      idx += 1;
+    }
+    siblings.insert(idx, id);
+    self.nodes.nodes[id.0].parent = parent;
+    self.nodes.update_depth(id, depth);
+    true
+  }
+
+  fn node(&self, id: TreeNodeId) -> &NodeInfo { &self.nodes.nodes[id.0] }
+
+  /// Return true if `ancestor` is `id` or one of its ancestors.
+  fn is_ancestor(&self, ancestor: TreeNodeId, mut id: TreeNodeId) -> bool {
+    loop {
+      if id == ancestor {
+        return true;
+      }
+      match self.parent(id) {
+        Some(p) => id = p,
+        None => return false,
+      }
+    }
+  }
+
+  fn descendants(&self, ids: &[TreeNodeId]) -> Vec<TreeNodeId> {
+    let mut all = vec![];
+    for id in ids {
+      all.push(*id);
+      if let Some(children) = self.children(*id) {
+        all.extend(self.descendants(children));
+      }
+    }
+    all
+  }
+
+  fn group_state(&self, id: TreeNodeId) -> GroupState {
+    let node = self.node(id);
+    match &node.children {
+      NodeChildren::Loading if node.expanded => GroupState::Loading,
+      NodeChildren::Loaded(children) if node.shown => GroupState::Children(children.clone()),
+      _ => GroupState::Hidden,
+    }
+  }
+
+  fn drop_class(&self, id: TreeNodeId) -> Option<ClassName> {
+    match self.drop_target {
+      Some((target, DropPosition::Before)) if target == id => Some(TREE_VIEW_ITEM_DROP_BEFORE),
+      Some((target, DropPosition::After)) if target == id => Some(TREE_VIEW_ITEM_DROP_AFTER),
+      _ => None,
+    }
+  }
+
+  fn focus_or_first(&self) -> Option<TreeNodeId> {
+    self
+      .focused
+      .or_else(|| self.nodes.roots.first().copied())
+  }
+
+  fn handle_key(this: &impl StateWriter<Value = Self>, key: &VirtualKey) -> bool {
+    let Some(focused) = this.read().focus_or_first() else { return false };
+    let visible = this.read().visible_nodes();
+    let idx = visible
+      .iter()
+      .position(|id| *id == focused)
+      .unwrap_or(0);
+    match key {
+      VirtualKey::Named(NamedKey::ArrowDown) => {
+        let next = visible[(idx + 1).min(visible.len() - 1)];
+        this.write().focus(next);
+      }
+      VirtualKey::Named(NamedKey::ArrowUp) => {
+        let prev = visible[idx.saturating_sub(1)];
+        this.write().focus(prev);
+      }
+      VirtualKey::Named(NamedKey::Home) => this.write().focus(visible[0]),
+      VirtualKey::Named(NamedKey::End) => this.write().focus(visible[visible.len() - 1]),
+      VirtualKey::Named(NamedKey::ArrowRight) => {
+        let tree = this.read();
+        if tree.is_leaf(focused) {
+          return false;
+        }
+        if !tree.is_expanded(focused) {
+          drop(tree);
+          this.write().focus(focused);
+          TreeView::expand(this, focused);
+        } else if let Some(first) = tree
+          .children(focused)
+          .and_then(|c| c.first().copied())
+        {
+          drop(tree);
+          this.write().focus(first);
+        }
+      }
+      VirtualKey::Named(NamedKey::ArrowLeft) => {
+        let tree = this.read();
+        if tree.is_expanded(focused) {
+          drop(tree);
+          let mut tree = this.write();
+          tree.focus(focused);
+          tree.collapse(focused);
+        } else if let Some(parent) = tree.parent(focused) {
+          drop(tree);
+          this.write().focus(parent);
+        }
+      }
+      VirtualKey::Named(NamedKey::Enter | NamedKey::Space) => {
+        let mut tree = this.write();
+        tree.focus(focused);
+        tree.select(focused);
+      }
+      VirtualKey::Character(c) if c == "*" => {
+        let siblings = match this.read().parent(focused) {
+          Some(p) => this
+            .read()
+            .children(p)
+            .unwrap_or_default()
+            .to_vec(),
+          None => this.read().roots().to_vec(),
+        };
+        this.write().focus(focused);
+        siblings
+          .into_iter()
+          .for_each(|id| TreeView::expand(this, id));
+      }
+      _ => return false,
+    }
+    true
+  }
+}
+
+impl Compose for TreeView {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      @Column {
+        class: TREE_VIEW,
+        tab_index: 0_i16,
+        align_items: Align::Stretch,
+        on_key_down: move |e| {
+          if TreeView::handle_key(&$writer(this), e.key()) {
+            e.stop_propagation();
+          }
+        },
+        on_pointer_up: move |_| {
+          if $read(this).dragging.is_some() {
+            let mut tree = $write(this);
+            tree.dragging = None;
+            tree.drop_target = None;
+          }
+        },
+        @ {
+          distinct_pipe!($read(this).roots().to_vec()).map(move |roots| {
+            roots
+              .into_iter()
+              .map(|id| node_widget(&this, id))
+              .collect::<Vec<_>>()
+          })
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn node_widget(this: &impl StateWriter<Value = TreeView>, id: TreeNodeId) -> Widget<'static> {
+  let this = this.clone_writer();
+  fn_widget! {
+    @Column {
+      align_items: Align::Stretch,
+      @ { node_row(&this, id) }
+      @ {
+        distinct_pipe!($read(this).group_state(id)).map(move |state| match state {
+          GroupState::Hidden => None,
+          GroupState::Loading => Some(@LinearProgress { class: TREE_VIEW_LOADING }.into_widget()),
+          GroupState::Children(children) => Some(node_group(&this, id, children)),
+        })
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn node_group(
+  this: &impl StateWriter<Value = TreeView>, id: TreeNodeId, children: Vec<TreeNodeId>,
+) -> Widget<'static> {
+  let (depth, indent) = {
+    let tree = this.read();
+    (tree.depth(id), tree.indent)
+  };
+  let children = children
+    .into_iter()
+    .map(|child| node_widget(this, child))
+    .collect::<Vec<_>>();
+  let this = this.clone_writer();
+  fn_widget! {
+    // The group of a collapsed node is kept with zero height and clipped, so
+    // the class of the group can animate its height both ways.
+    @Stack {
+      class: TREE_VIEW_GROUP,
+      clip_boundary: true,
+      @GroupBox {
+        expanded: distinct_pipe!($read(this).is_expanded(id)),
+        @Column { align_items: Align::Stretch, @ { children } }
+      }
+      @InParentLayout {
+        @Void {
+          class: TREE_VIEW_GUIDE,
+          clamp: BoxClamp::fixed_width(1.),
+          v_align: VAlign::Stretch,
+          margin: EdgeInsets::only_left((depth as f32 + 0.5) * indent),
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
+/// Lay out the children of a node in their natural height, but occupy zero
+/// height when the node is collapsed.
+#[derive(SingleChild, Declare)]
+struct GroupBox {
+  expanded: bool,
+}
+
+impl Render for GroupBox {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let child_clamp = BoxClamp {
+      min: Size::new(clamp.min.width, 0.),
+      max: Size::new(clamp.max.width, f32::INFINITY),
+    };
+    let size = ctx
+      .perform_single_child_layout(child_clamp)
+      .unwrap_or_default();
+    let height = if self.expanded { size.height } else { 0. };
+    clamp.clamp(Size::new(size.width, height))
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+fn node_row(this: &impl StateWriter<Value = TreeView>, id: TreeNodeId) -> Widget<'static> {
+  let this = this.clone_writer();
+  fn_widget! {
+    let (label, supporting, icon, depth, indent) = {
+      let tree = $read(this);
+      let node = tree.node(id);
+      (node.label.clone(), node.supporting.clone(), node.icon, node.depth, tree.indent)
+    };
+
+    let expander = distinct_pipe!(($read(this).is_leaf(id), $read(this).is_expanded(id)))
+      .map(move |(leaf, expanded)| {
+        (!leaf).then(|| {
+          let (cls, svg) = if expanded {
+            (TREE_VIEW_EXPANDER_EXPANDED, svgs::EXPAND_MORE)
+          } else {
+            (TREE_VIEW_EXPANDER, svgs::CHEVRON_RIGHT)
+          };
+          @Icon {
+            class: cls,
+            h_align: HAlign::Center,
+            v_align: VAlign::Center,
+            on_tap: move |e| {
+              TreeView::toggle(&$writer(this), id);
+              e.stop_propagation();
+            },
+            @ { svg }
+          }
+        })
+      });
+    let leading = @Row {
+      align_items: Align::Center,
+      @SizedBox { size: Size::new(depth as f32 * indent, 0.) }
+      @SizedBox {
+        size: Size::splat(indent),
+        @ { expander }
+      }
+      @ { icon.map(|icon| @Icon { @ { icon } }) }
+    };
+
+    let item = @ListItem {
+      selected: distinct_pipe!($read(this).is_selected(id)),
+      on_tap: move |_| {
+        let mut tree = $write(this);
+        tree.focus(id);
+        tree.select(id);
+      },
+      on_double_tap: move |_| TreeView::toggle(&$writer(this), id),
+      on_pointer_down: move |e| {
+        if $read(this).reorderable && e.mouse_buttons() == MouseButtons::PRIMARY {
+          $write(this).dragging = Some(id);
+        }
+      },
+      on_pointer_move: move |e| {
+        let Some(dragging) = $read(this).dragging else { return };
+        let target = (!$read(this).is_ancestor(dragging, id)).then(|| {
+          let height = e.box_size().map_or(0., |s| s.height);
+          let pos = if e.position().y < height / 2. {
+            DropPosition::Before
+          } else {
+            DropPosition::After
+          };
+          (id, pos)
+        });
+        if $read(this).drop_target != target {
+          $write(this).drop_target = target;
+        }
+      },
+      on_pointer_up: move |_| {
+        let drag = ($read(this).dragging, $read(this).drop_target);
+        if let (Some(dragging), Some((target, pos))) = drag {
+          $write(this).move_node(dragging, target, pos);
+        }
+      },
+    };
+    let item = match supporting {
+      Some(supporting) => @(item) {
+        @ { leading }
+        @ListItemHeadline { @ { label } }
+        @ListItemSupporting { @ { supporting } }
+      }
+      .into_widget(),
+      None => @(item) {
+        @ { leading }
+        @ListItemHeadline { @ { label } }
+      }
+      .into_widget(),
+    };
+    let classes = class_array![
+      distinct_pipe!($read(this).drop_class(id)),
+      distinct_pipe!(($read(this).focused == Some(id)).then_some(TREE_VIEW_ITEM_FOCUSED)),
+      TREE_VIEW_ITEM,
+    ];
+    @(classes) { @ { item } }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    cell::RefCell,
+    future::poll_fn,
+    task::{Poll, Waker},
+  };
+
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  fn tree(mode: ListSelectMode) -> TreeView {
+    TreeView {
+      nodes: vec![
+        TreeNode::new("a").with_children(vec![
+          TreeNode::new("a1"),
+          TreeNode::new("a2").with_lazy_children(|| vec![TreeNode::new("a2x")]),
+        ]),
+        TreeNode::new("b"),
+      ]
+      .into(),
+      select_mode: mode,
+      indent: 24.,
+      reorderable: true,
+      focused: None,
+      dragging: None,
+      drop_target: None,
+    }
+  }
+
+  fn labels(tree: &TreeView, ids: &[TreeNodeId]) -> Vec<String> {
+    ids
+      .iter()
+      .map(|id| tree.label(*id).to_string())
+      .collect()
+  }
+
+  #[test]
+  fn expand_lazy_children() {
+    reset_test_env!();
+    let tree = Stateful::new(tree(ListSelectMode::None));
+    let a = tree.read().roots()[0];
+    assert_eq!(labels(&tree.read(), &tree.read().visible_nodes()), ["a", "b"]);
+
+    TreeView::expand(&tree, a);
+    let a2 = tree.read().children(a).unwrap()[1];
+    assert!(!tree.read().is_leaf(a2));
+    assert!(tree.read().children(a2).is_none());
+
+    TreeView::expand(&tree, a2);
+    assert_eq!(labels(&tree.read(), &tree.read().visible_nodes()), ["a", "a1", "a2", "a2x", "b"]);
+
+    tree.write().collapse(a);
+    assert_eq!(labels(&tree.read(), &tree.read().visible_nodes()), ["a", "b"]);
+  }
+
+  #[test]
+  fn reload_reuses_removed_nodes() {
+    let mut tree = tree(ListSelectMode::None);
+    let a = tree.roots()[0];
+    let a2 = tree.children(a).unwrap()[1];
+    tree.set_children(a2, vec![TreeNode::new("x").with_children(vec![TreeNode::new("y")])]);
+    let x = tree.children(a2).unwrap()[0];
+    tree.focus(tree.children(x).unwrap()[0]);
+    let count = tree.nodes.nodes.len();
+
+    for _ in 0..3 {
+      tree.set_children(a2, vec![TreeNode::new("x").with_children(vec![TreeNode::new("y")])]);
+    }
+    assert_eq!(tree.nodes.nodes.len(), count);
+    assert_eq!(tree.focused(), Some(a2));
+    let x = tree.children(a2).unwrap()[0];
+    assert_eq!(labels(&tree, &tree.descendants(&[x])), ["x", "y"]);
+  }
+
+  #[test]
+  fn collapsed_group_has_zero_height() {
+    reset_test_env!();
+    let tree = Stateful::new(tree(ListSelectMode::None));
+    let c_tree = tree.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! { @(c_tree.clone_writer()) {} },
+      Size::new(400., 600.),
+    );
+    wnd.draw_frame();
+    let a = tree.read().roots()[0];
+    let height = || wnd.layout_info_by_path(&[0]).unwrap().size.unwrap().height;
+    let collapsed = height();
+
+    TreeView::expand(&tree, a);
+    wnd.draw_frame();
+    let expanded = height();
+    assert!(expanded > collapsed);
+
+    // The group is kept but takes no space.
+    tree.write().collapse(a);
+    wnd.draw_frame();
+    assert!(wnd.layout_info_by_path(&[0, 0, 1]).is_some());
+    assert_eq!(height(), collapsed);
+  }
+
+  #[test]
+  fn async_children() {
+    reset_test_env!();
+    let tree = Stateful::new(TreeView {
+      nodes: vec![
+        TreeNode::new("remote").with_async_children(|| async { vec![TreeNode::new("file")] }),
+      ]
+      .into(),
+      ..tree(ListSelectMode::None)
+    });
+    let remote = tree.read().roots()[0];
+    TreeView::expand(&tree, remote);
+    assert!(tree.read().is_loading(remote));
+
+    AppCtx::run_until_stalled();
+    assert!(!tree.read().is_loading(remote));
+    assert_eq!(labels(&tree.read(), tree.read().children(remote).unwrap()), ["file"]);
+  }
+
+  #[test]
+  fn drop_async_children_of_released_node() {
+    reset_test_env!();
+    // The loading resolves after the gate is opened.
+    let gate: Rc<RefCell<(bool, Option<Waker>)>> = Rc::default();
+    let c_gate = gate.clone();
+    let tree = Stateful::new(TreeView {
+      nodes: vec![TreeNode::new("dir").with_children(vec![
+        TreeNode::new("remote").with_async_children(move || {
+          let gate = c_gate.clone();
+          async move {
+            poll_fn(|cx| {
+              let mut gate = gate.borrow_mut();
+              if gate.0 {
+                Poll::Ready(())
+              } else {
+                gate.1 = Some(cx.waker().clone());
+                Poll::Pending
+              }
+            })
+            .await;
+            vec![TreeNode::new("stale")]
+          }
+        }),
+      ])]
+      .into(),
+      ..tree(ListSelectMode::None)
+    });
+    let dir = tree.read().roots()[0];
+    let remote = tree.read().children(dir).unwrap()[0];
+    TreeView::expand(&tree, remote);
+    AppCtx::run_until_stalled();
+    assert!(tree.read().is_loading(remote));
+
+    // The new node reuses the slot of the released one.
+    tree
+      .write()
+      .set_children(dir, vec![TreeNode::new("other").with_lazy_children(Vec::new)]);
+    let other = tree.read().children(dir).unwrap()[0];
+    assert_eq!(other, remote);
+
+    let waker = {
+      let mut gate = gate.borrow_mut();
+      gate.0 = true;
+      gate.1.take()
+    };
+    waker.unwrap().wake();
+    AppCtx::run_until_stalled();
+    assert_eq!(labels(&tree.read(), &[other]), ["other"]);
+    assert_eq!(tree.read().children(other), None);
+    assert!(!tree.read().is_expanded(other));
+  }
+
+  #[test]
+  fn selection_modes() {
+    let mut single = tree(ListSelectMode::Single);
+    let [a, b] = [single.roots()[0], single.roots()[1]];
+    single.select(a);
+    single.select(b);
+    assert_eq!(single.selected().collect::<Vec<_>>(), [b]);
+
+    let mut multi = tree(ListSelectMode::Multi);
+    multi.select(a);
+    multi.select(b);
+    assert_eq!(multi.selected().collect::<Vec<_>>(), [a, b]);
+    multi.select(a);
+    assert_eq!(multi.selected().collect::<Vec<_>>(), [b]);
+  }
+
+  #[test]
+  fn move_node() {
+    let mut tree = tree(ListSelectMode::None);
+    let [a, b] = [tree.roots()[0], tree.roots()[1]];
+    let a1 = tree.children(a).unwrap()[0];
+
+    assert!(!tree.move_node(a, a1, DropPosition::After));
+    assert!(tree.move_node(b, a1, DropPosition::Before));
+    assert_eq!(labels(&tree, tree.children(a).unwrap()), ["b", "a1", "a2"]);
+    assert_eq!(tree.depth(b), 1);
+    assert_eq!(tree.parent(b), Some(a));
+
+    assert!(tree.move_node(a1, a, DropPosition::After));
+    assert_eq!(labels(&tree, tree.roots()), ["a", "a1"]);
+    assert_eq!(tree.depth(a1), 0);
+  }
+
+  #[test]
+  fn keyboard_navigation() {
+    reset_test_env!();
+    let tree = Stateful::new(tree(ListSelectMode::Single));
+    let c_tree = tree.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut tree = FatObj::new(c_tree.clone_writer());
+        @(tree) { auto_focus: true }
+      },
+      Size::new(400., 600.),
+    );
+    wnd.draw_frame();
+
+    let key = |key: NamedKey, code: KeyCode| {
+      wnd.process_keyboard_event(
+        PhysicalKey::Code(code),
+        VirtualKey::Named(key),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+      wnd.draw_frame();
+    };
+    let focused = || {
+      let tree = tree.read();
+      tree
+        .focused()
+        .map(|id| tree.label(id).to_string())
+    };
+
+    // Expand `a`, then move into its first child.
+    key(NamedKey::ArrowRight, KeyCode::ArrowRight);
+    assert_eq!(focused().as_deref(), Some("a"));
+    key(NamedKey::ArrowRight, KeyCode::ArrowRight);
+    assert_eq!(focused().as_deref(), Some("a1"));
+    key(NamedKey::ArrowDown, KeyCode::ArrowDown);
+    assert_eq!(focused().as_deref(), Some("a2"));
+    key(NamedKey::End, KeyCode::End);
+    assert_eq!(focused().as_deref(), Some("b"));
+    key(NamedKey::Enter, KeyCode::Enter);
+    assert_eq!(labels(&tree.read(), &tree.read().selected().collect::<Vec<_>>()), ["b"]);
+
+    key(NamedKey::Home, KeyCode::Home);
+    key(NamedKey::ArrowDown, KeyCode::ArrowDown);
+    key(NamedKey::ArrowLeft, KeyCode::ArrowLeft);
+    assert_eq!(focused().as_deref(), Some("a"));
+    key(NamedKey::ArrowLeft, KeyCode::ArrowLeft);
+    assert!(!tree.read().is_expanded(tree.read().roots()[0]));
+  }
+}